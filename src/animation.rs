@@ -0,0 +1,860 @@
+//! Running animations in the terminal
+//!
+//! `run` takes care of the scaffolding every animation needs: it switches to the terminal’s
+//! alternate screen, calls a drawing function at a steady frame rate with a fresh `Canvas` the
+//! size of the terminal, redraws only the rows that changed, and puts the terminal back as it was
+//! afterwards—even if the drawing function panics or the user presses Ctrl-C. `run_interactive`
+//! also passes on keyboard and mouse input, for simple games and other interactive programs, and
+//! `run_game` runs a `Game` whose updates happen at a fixed rate, separately from drawing.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use drawille::animation;
+//!
+//! animation::run(30.0, |canvas, frame, _| {
+//!     let x = frame as usize % 160;
+//!     canvas.line(x, 0, 159 - x, 79);
+//!     frame < 300
+//! }).unwrap();
+//! ```
+
+#[cfg(feature = "tokio")]
+use std::future::Future;
+use std::io::{self, Write};
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use braille::Canvas;
+use console;
+use input::{self, Event, Key};
+
+/// The terminal, set up for drawing animations until it’s dropped.
+///
+/// While a `Terminal` exists, the alternate screen is shown with the cursor hidden, and the
+/// terminal is in raw mode so that keys arrive straight away and Ctrl-C can be handled rather
+/// than killing the program. Input is read on a background thread, which stops when the
+/// `Terminal` is dropped, leaving any input that arrives afterwards to whatever reads it next.
+///
+/// ```
+/// # #[cfg(unix)] extern crate libc;
+/// # extern crate drawille;
+/// use std::io::{self, Read};
+///
+/// use drawille::animation::Terminal;
+///
+/// # #[cfg(unix)] fn main() {
+/// # // Run on a pseudo-terminal, drained of what's drawn to it.
+/// # let master = unsafe {
+/// #     let (mut master, mut slave) = (0, 0);
+/// #     let null = std::ptr::null_mut();
+/// #     assert_eq!(libc::openpty(&mut master, &mut slave, null, null as *mut _, null as *mut _),
+/// #                0);
+/// #     libc::dup2(slave, libc::STDIN_FILENO);
+/// #     libc::dup2(slave, libc::STDOUT_FILENO);
+/// #     std::thread::spawn(move || {
+/// #         let mut buffer = [0u8; 4096];
+/// #         while libc::read(master, buffer.as_mut_ptr() as *mut _, buffer.len()) > 0 {}
+/// #     });
+/// #     master
+/// # };
+/// drop(Terminal::enter().unwrap());
+/// // Then the user types a line:
+/// # unsafe { libc::write(master, b"x\n".as_ptr() as *const _, 2); }
+/// let mut key = [0];
+/// io::stdin().read_exact(&mut key).unwrap();
+/// assert_eq!(&key, b"x");
+/// # }
+/// # #[cfg(not(unix))] fn main() {}
+/// ```
+pub struct Terminal {
+    /// The mode standard input was in before raw mode, to restore.
+    saved: imp::Mode,
+    input: Receiver<u8>,
+    /// The thread reading input, and the flag that tells it to stop.
+    reader: Option<JoinHandle<()>>,
+    stop: Arc<AtomicBool>,
+    /// Bytes received that don’t yet make up a whole event.
+    pending: Vec<u8>,
+    /// The rows last drawn, to compare the next frame against.
+    shown: Vec<String>,
+    /// Whether to clear the screen before the next frame is drawn.
+    clear: bool,
+    out: io::Stdout,
+}
+
+impl Terminal {
+    /// Sets up the terminal for drawing animations.
+    ///
+    /// This fails if standard input isn’t a terminal that can be put in raw mode, such as when
+    /// it’s redirected from a file, since Ctrl-C would then kill the program without putting the
+    /// terminal back as it was.
+    ///
+    /// ```
+    /// # #[cfg(unix)] extern crate libc;
+    /// # extern crate drawille;
+    /// use drawille::animation::Terminal;
+    ///
+    /// # #[cfg(unix)] fn main() {
+    /// # unsafe {
+    /// #     let null = libc::open(b"/dev/null\0".as_ptr() as *const _, libc::O_RDONLY);
+    /// #     libc::dup2(null, libc::STDIN_FILENO);
+    /// # }
+    /// // With standard input redirected from /dev/null:
+    /// assert!(Terminal::enter().is_err());
+    /// # }
+    /// # #[cfg(not(unix))] fn main() {}
+    /// ```
+    pub fn enter() -> io::Result<Terminal> {
+        console::enable_ansi();
+        let saved = imp::enter_raw()?;
+        let (sender, input) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let reader = thread::spawn(move || {
+            let mut buffer = [0; 64];
+            // Only reading once input has arrived, so that the flag is seen soon after it's set
+            // rather than after the next key is pressed.
+            while !stopped.load(Ordering::SeqCst) {
+                match imp::wait_for_input(INPUT_TIMEOUT) {
+                    Ok(true) => {},
+                    Ok(false) => continue,
+                    Err(_) => break,
+                }
+                match imp::read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(count) => if buffer[..count].iter().any(|&b| sender.send(b).is_err()) {
+                        break;
+                    },
+                }
+            }
+        });
+        let mut terminal = Terminal {
+            saved,
+            input,
+            reader: Some(reader),
+            stop,
+            pending: vec![],
+            shown: vec![],
+            clear: false,
+            out: io::stdout(),
+        };
+        // The alternate screen, no cursor, and no wrapping of rows too long for the terminal.
+        terminal.out.write_all(b"\x1b[?1049h\x1b[?25l\x1b[?7l\x1b[2J")?;
+        terminal.out.flush()?;
+        Ok(terminal)
+    }
+
+    /// Returns the size of the terminal in columns and rows, or 80 by 24 if it can’t be found.
+    ///
+    /// This asks the terminal each time, which is cheap enough to do every frame.
+    pub fn size(&self) -> (usize, usize) {
+        imp::size().filter(|&(columns, rows)| columns > 0 && rows > 0).unwrap_or((80, 24))
+    }
+
+    /// Draws rows of characters from the top left corner of the terminal, rewriting only the rows
+    /// that differ from the last rows drawn.
+    pub fn draw(&mut self, rows: &[String]) -> io::Result<()> {
+        let mut output = String::new();
+        if self.clear {
+            output.push_str("\x1b[2J");
+            self.clear = false;
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if self.shown.get(i) != Some(row) {
+                output.push_str(&format!("\x1b[{};1H{}\x1b[0m\x1b[K", i + 1, row));
+            }
+        }
+        if rows.len() < self.shown.len() {
+            output.push_str(&format!("\x1b[{};1H\x1b[J", rows.len() + 1));
+        }
+        self.shown = rows.to_vec();
+        self.out.write_all(output.as_bytes())?;
+        self.out.flush()
+    }
+
+    /// Forgets what was last drawn, so that the next call to `draw` clears the screen and redraws
+    /// everything.
+    pub fn invalidate(&mut self) {
+        self.shown.clear();
+        self.clear = true;
+    }
+
+    /// Starts reporting mouse presses, releases, drags and scrolling as input events.
+    pub fn enable_mouse(&mut self) -> io::Result<()> {
+        self.out.write_all(b"\x1b[?1002h\x1b[?1006h")?;
+        self.out.flush()
+    }
+
+    /// Returns the input events received since this was last called.
+    pub fn poll(&mut self) -> Vec<Event> {
+        self.pending.extend(self.input.try_iter());
+        input::parse(&mut self.pending)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.out.write_all(b"\x1b[0m\x1b[?1002l\x1b[?1006l\x1b[?7h\x1b[?25h\x1b[?1049l");
+        let _ = self.out.flush();
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        imp::restore(&self.saved);
+    }
+}
+
+/// How long the input thread waits for input at a time before checking whether to stop.
+const INPUT_TIMEOUT: Duration = Duration::from_millis(50);
+
+#[cfg(unix)]
+mod imp {
+    use std::io;
+    use std::mem;
+    use std::time::Duration;
+
+    use libc;
+
+    pub type Mode = libc::termios;
+
+    /// Puts standard input in raw mode, without echo, returning the mode it was in before.
+    pub fn enter_raw() -> io::Result<Mode> {
+        unsafe {
+            let mut saved = mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut saved) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = saved;
+            libc::cfmakeraw(&mut raw);
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(saved)
+        }
+    }
+
+    pub fn restore(mode: &Mode) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, mode);
+        }
+    }
+
+    /// Returns the size of the terminal standard output goes to, in columns and rows.
+    pub fn size() -> Option<(usize, usize)> {
+        unsafe {
+            let mut size: libc::winsize = mem::zeroed();
+            if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0 {
+                Some((size.ws_col as usize, size.ws_row as usize))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Waits up to `timeout` for standard input to have something to read, returning whether it
+    /// does.
+    pub fn wait_for_input(timeout: Duration) -> io::Result<bool> {
+        let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        match unsafe { libc::poll(&mut fd, 1, timeout.as_millis() as libc::c_int) } {
+            -1 => {
+                let error = io::Error::last_os_error();
+                if error.kind() == io::ErrorKind::Interrupted { Ok(false) } else { Err(error) }
+            },
+            ready => Ok(ready > 0),
+        }
+    }
+
+    /// Reads what's waiting on standard input, straight from the terminal rather than through
+    /// `io::stdin`, whose buffer would hide bytes from `wait_for_input`.
+    pub fn read(buffer: &mut [u8]) -> io::Result<usize> {
+        let count = unsafe {
+            libc::read(libc::STDIN_FILENO, buffer.as_mut_ptr() as *mut _, buffer.len())
+        };
+        if count < 0 { Err(io::Error::last_os_error()) } else { Ok(count as usize) }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::char;
+    use std::io;
+    use std::os::raw::c_void;
+    use std::ptr;
+    use std::time::Duration;
+
+    type Handle = *mut c_void;
+
+    const STD_INPUT_HANDLE: u32 = -10i32 as u32;
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_PROCESSED_INPUT: u32 = 0x0001;
+    const ENABLE_LINE_INPUT: u32 = 0x0002;
+    const ENABLE_ECHO_INPUT: u32 = 0x0004;
+    const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x0200;
+    const WAIT_OBJECT_0: u32 = 0;
+    const KEY_EVENT: u16 = 1;
+
+    #[repr(C)]
+    struct Coord {
+        x: i16,
+        y: i16,
+    }
+
+    #[repr(C)]
+    struct SmallRect {
+        left: i16,
+        top: i16,
+        right: i16,
+        bottom: i16,
+    }
+
+    #[repr(C)]
+    struct ScreenBufferInfo {
+        size: Coord,
+        cursor: Coord,
+        attributes: u16,
+        window: SmallRect,
+        maximum: Coord,
+    }
+
+    /// An `INPUT_RECORD`, with its event read as a key event.
+    #[repr(C)]
+    struct InputRecord {
+        event_type: u16,
+        key_down: i32,
+        repeat_count: u16,
+        key_code: u16,
+        scan_code: u16,
+        unicode_char: u16,
+        control_key_state: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+        fn GetConsoleScreenBufferInfo(console: Handle, info: *mut ScreenBufferInfo) -> i32;
+        fn WaitForSingleObject(handle: Handle, milliseconds: u32) -> u32;
+        fn PeekConsoleInputW(console: Handle, buffer: *mut InputRecord, length: u32,
+                             read: *mut u32) -> i32;
+        fn ReadConsoleInputW(console: Handle, buffer: *mut InputRecord, length: u32,
+                             read: *mut u32) -> i32;
+        fn ReadConsoleW(console: Handle, buffer: *mut c_void, length: u32, read: *mut u32,
+                        control: *mut c_void) -> i32;
+    }
+
+    pub type Mode = u32;
+
+    /// Puts the console standard input reads from in raw mode, without echo and with keys sent
+    /// as the escape codes a Unix terminal would send, returning the mode it was in before.
+    pub fn enter_raw() -> io::Result<Mode> {
+        unsafe {
+            let handle = GetStdHandle(STD_INPUT_HANDLE);
+            let mut saved = 0;
+            if GetConsoleMode(handle, &mut saved) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let cooked = ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT;
+            if SetConsoleMode(handle, saved & !cooked | ENABLE_VIRTUAL_TERMINAL_INPUT) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(saved)
+        }
+    }
+
+    pub fn restore(mode: &Mode) {
+        unsafe {
+            SetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), *mode);
+        }
+    }
+
+    /// Returns the size of the console window standard output goes to, in columns and rows.
+    pub fn size() -> Option<(usize, usize)> {
+        unsafe {
+            let mut info = ScreenBufferInfo {
+                size: Coord { x: 0, y: 0 },
+                cursor: Coord { x: 0, y: 0 },
+                attributes: 0,
+                window: SmallRect { left: 0, top: 0, right: -1, bottom: -1 },
+                maximum: Coord { x: 0, y: 0 },
+            };
+            if GetConsoleScreenBufferInfo(GetStdHandle(STD_OUTPUT_HANDLE), &mut info) == 0 {
+                return None;
+            }
+            let window = info.window;
+            Some(((window.right - window.left + 1).max(0) as usize,
+                  (window.bottom - window.top + 1).max(0) as usize))
+        }
+    }
+
+    /// Waits up to `timeout` for the console to have a character to read, returning whether it
+    /// does.
+    ///
+    /// The console is also signalled by events that read no characters, such as focus changes and
+    /// keys being released, which would leave a read waiting; those are taken off the console's
+    /// queue and count as nothing to read.
+    pub fn wait_for_input(timeout: Duration) -> io::Result<bool> {
+        unsafe {
+            let handle = GetStdHandle(STD_INPUT_HANDLE);
+            if WaitForSingleObject(handle, timeout.as_millis() as u32) != WAIT_OBJECT_0 {
+                return Ok(false);
+            }
+            let mut record = InputRecord {
+                event_type: 0,
+                key_down: 0,
+                repeat_count: 0,
+                key_code: 0,
+                scan_code: 0,
+                unicode_char: 0,
+                control_key_state: 0,
+            };
+            let mut count = 0;
+            if PeekConsoleInputW(handle, &mut record, 1, &mut count) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if count == 0 {
+                return Ok(false);
+            }
+            if record.event_type == KEY_EVENT && record.key_down != 0 && record.unicode_char != 0 {
+                return Ok(true);
+            }
+            if ReadConsoleInputW(handle, &mut record, 1, &mut count) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(false)
+        }
+    }
+
+    /// Reads the characters waiting on the console as UTF-8. Characters that take two UTF-16
+    /// units and arrive split between reads are replaced by U+FFFD.
+    pub fn read(buffer: &mut [u8]) -> io::Result<usize> {
+        // Each unit takes up to three bytes in UTF-8.
+        let mut units = [0u16; 64];
+        let length = (buffer.len() / 3).clamp(1, units.len());
+        let mut count = 0;
+        let read = unsafe {
+            ReadConsoleW(GetStdHandle(STD_INPUT_HANDLE), units.as_mut_ptr() as *mut c_void,
+                         length as u32, &mut count, ptr::null_mut())
+        };
+        if read == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let text = char::decode_utf16(units[..count as usize].iter().cloned())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect::<String>();
+        buffer[..text.len()].copy_from_slice(text.as_bytes());
+        Ok(text.len())
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use std::io::{self, Read};
+    use std::time::Duration;
+
+    pub type Mode = ();
+
+    pub fn enter_raw() -> io::Result<Mode> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "raw mode isn’t supported here"))
+    }
+
+    pub fn restore(_: &Mode) {}
+
+    pub fn size() -> Option<(usize, usize)> {
+        None
+    }
+
+    pub fn wait_for_input(_: Duration) -> io::Result<bool> {
+        Ok(true)
+    }
+
+    pub fn read(buffer: &mut [u8]) -> io::Result<usize> {
+        io::stdin().read(buffer)
+    }
+}
+
+/// The state kept between frames by the frame loops.
+struct Frames {
+    terminal: Terminal,
+    /// The size of the terminal when the last frame was drawn.
+    size: (usize, usize),
+    count: u64,
+    last: Instant,
+    /// The input events received before the current frame started.
+    events: Vec<Event>,
+}
+
+impl Frames {
+    fn new() -> io::Result<Frames> {
+        let terminal = Terminal::enter()?;
+        let size = terminal.size();
+        Ok(Frames { terminal, size, count: 0, last: Instant::now(), events: vec![] })
+    }
+
+    /// Starts a frame, returning a cleared `Canvas` that fills the terminal, the number of the
+    /// frame and the time since the last frame started, or `None` if the user pressed Ctrl-C.
+    ///
+    /// This doesn’t write to the terminal or wait for anything, so that it can be called from
+    /// `Tick::poll`; everything written waits for `finish`.
+    fn start(&mut self) -> Option<(Canvas, u64, Duration)> {
+        self.events = self.terminal.poll();
+        if self.events.contains(&Event::Key(Key::Ctrl('c'))) {
+            return None;
+        }
+        let now = Instant::now();
+        let dt = now - self.last;
+        self.last = now;
+        let size = self.terminal.size();
+        if size != self.size {
+            self.size = size;
+            self.terminal.invalidate();
+        }
+        let (columns, rows) = size;
+        let canvas = Canvas::new(columns * 2, rows * 4);
+        self.count += 1;
+        Some((canvas, self.count - 1, dt))
+    }
+
+    /// Shows the canvas drawn for a frame.
+    fn finish(&mut self, canvas: &Canvas) -> io::Result<()> {
+        let mut lines = canvas.rows();
+        lines.truncate(self.size.1);
+        self.terminal.draw(&lines)
+    }
+}
+
+/// Returns the time between frames at `fps` frames per second.
+fn period(fps: f64) -> Duration {
+    Duration::from_secs_f64(1.0 / fps.max(f64::MIN_POSITIVE))
+}
+
+/// Runs an animation at `fps` frames per second until `draw` returns `false` or the user presses
+/// Ctrl-C.
+///
+/// For each frame, `draw` is called with a cleared `Canvas` that fills the terminal, the number of
+/// the frame starting from zero, and the time since the last frame started. A frame that takes
+/// too long to draw delays the next rather than causing frames to be skipped.
+pub fn run<F>(fps: f64, mut draw: F) -> io::Result<()>
+    where F: FnMut(&mut Canvas, u64, Duration) -> bool
+{
+    let period = period(fps);
+    let mut frames = Frames::new()?;
+    let mut next = Instant::now();
+    while let Some((mut canvas, frame, dt)) = frames.start() {
+        if !draw(&mut canvas, frame, dt) {
+            break;
+        }
+        frames.finish(&canvas)?;
+        wait(&mut next, period);
+    }
+    Ok(())
+}
+
+/// Moves `next` on by `period` and sleeps until then, or if that time has already passed, moves
+/// `next` to now so the delay doesn’t build up.
+fn wait(next: &mut Instant, period: Duration) {
+    *next += period;
+    let now = Instant::now();
+    if *next > now {
+        thread::sleep(*next - now);
+    } else {
+        *next = now;
+    }
+}
+
+/// Runs an interactive animation at `fps` frames per second until `draw` returns `false` or the
+/// user presses Ctrl-C.
+///
+/// This is like `run`, but `draw` is also given the input events received since the last frame,
+/// including mouse events if `mouse` is true. `Mouse::pixel` gives the position of a mouse event
+/// on the canvas.
+///
+/// ```no_run
+/// use drawille::animation;
+/// use drawille::input::{Event, Key, MouseKind};
+///
+/// let mut points = vec![];
+/// animation::run_interactive(30.0, true, |canvas, _, _, events| {
+///     for event in events {
+///         match *event {
+///             Event::Key(Key::Char('q')) => return false,
+///             Event::Mouse(mouse) if mouse.kind != MouseKind::ScrollUp => {
+///                 points.push(mouse.pixel())
+///             },
+///             _ => {},
+///         }
+///     }
+///     for &(x, y) in points.iter() {
+///         canvas.set(x, y);
+///     }
+///     true
+/// }).unwrap();
+/// ```
+pub fn run_interactive<F>(fps: f64, mouse: bool, mut draw: F) -> io::Result<()>
+    where F: FnMut(&mut Canvas, u64, Duration, &[Event]) -> bool
+{
+    let period = period(fps);
+    let mut frames = Frames::new()?;
+    if mouse {
+        frames.terminal.enable_mouse()?;
+    }
+    let mut next = Instant::now();
+    while let Some((mut canvas, frame, dt)) = frames.start() {
+        if !draw(&mut canvas, frame, dt, &frames.events) {
+            break;
+        }
+        frames.finish(&canvas)?;
+        wait(&mut next, period);
+    }
+    Ok(())
+}
+
+/// The most updates `run_game` makes before drawing a frame, so that a game whose updates take
+/// longer than they simulate slows down rather than falling ever further behind.
+const MAX_UPDATES_PER_FRAME: usize = 8;
+
+/// A game run by `run_game`, whose state moves on in steps of a fixed length however quickly
+/// frames are drawn.
+pub trait Game {
+    /// Moves the game on by `dt`, the same length every time, given the input events received
+    /// since the last update, and returns whether the game should go on.
+    fn update(&mut self, dt: Duration, input: &[Event]) -> bool;
+
+    /// Draws the game onto a cleared `Canvas` that fills the terminal.
+    ///
+    /// `alpha`, from 0 up to 1, is how far the time of the frame is between the last update and
+    /// the next, so that moving things can be drawn part of the way between where they were and
+    /// where they’re going, and move smoothly when frames are drawn more often than updates.
+    fn render(&mut self, canvas: &mut Canvas, alpha: f64);
+}
+
+/// Runs a game until an update returns `false` or the user presses Ctrl-C, updating it
+/// `tick_rate` times a second and drawing it at up to `fps` frames per second.
+///
+/// Each frame, the game is updated as many times as needed to catch up with the time that has
+/// passed, then drawn, with only the rows that changed since the last frame being redrawn. Input
+/// events, including mouse events if `mouse` is true, are given to the first update after they
+/// arrive.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use drawille::animation::{self, Game};
+/// use drawille::braille::Canvas;
+/// use drawille::input::{Event, Key};
+///
+/// struct Ball {
+///     x: f64,
+///     last_x: f64,
+///     speed: f64,
+/// }
+///
+/// impl Game for Ball {
+///     fn update(&mut self, dt: Duration, input: &[Event]) -> bool {
+///         self.last_x = self.x;
+///         self.x += self.speed * dt.as_secs_f64();
+///         if self.x < 0.0 || self.x > 100.0 {
+///             self.speed = -self.speed;
+///         }
+///         !input.contains(&Event::Key(Key::Char('q')))
+///     }
+///
+///     fn render(&mut self, canvas: &mut Canvas, alpha: f64) {
+///         let x = self.last_x + (self.x - self.last_x) * alpha;
+///         canvas.set(x.max(0.0) as usize, 10);
+///     }
+/// }
+///
+/// let mut ball = Ball { x: 0.0, last_x: 0.0, speed: 40.0 };
+/// animation::run_game(&mut ball, 20.0, 60.0, false).unwrap();
+/// ```
+pub fn run_game<G: Game>(game: &mut G, tick_rate: f64, fps: f64, mouse: bool) -> io::Result<()> {
+    let (step, period) = (self::period(tick_rate), self::period(fps));
+    let mut frames = Frames::new()?;
+    if mouse {
+        frames.terminal.enable_mouse()?;
+    }
+    // The time that has passed but hasn’t yet been simulated, and the input not yet handled.
+    let mut lag = Duration::from_secs(0);
+    let mut input = vec![];
+    let mut next = Instant::now();
+    while let Some((mut canvas, _, dt)) = frames.start() {
+        lag += dt;
+        input.append(&mut frames.events);
+        let mut updates = 0;
+        while lag >= step {
+            if !game.update(step, &input) {
+                return Ok(());
+            }
+            input.clear();
+            lag -= step;
+            updates += 1;
+            if updates == MAX_UPDATES_PER_FRAME {
+                lag = Duration::from_secs(0);
+            }
+        }
+        game.render(&mut canvas, lag.as_secs_f64() / step.as_secs_f64());
+        frames.finish(&canvas)?;
+        wait(&mut next, period);
+    }
+    Ok(())
+}
+
+/// An animation driven from async code, using tokio’s timers so other tasks keep running between
+/// frames.
+///
+/// Unlike `run`, the loop belongs to the caller, who can await data sources between frames:
+///
+/// ```no_run,edition2018
+/// # extern crate drawille;
+/// # extern crate tokio;
+/// use drawille::animation::AsyncAnimation;
+///
+/// # async fn fetch_value() -> usize { 0 }
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let mut animation = AsyncAnimation::new(10.0).unwrap();
+///     let mut values = vec![];
+///     while animation.tick().await.is_some() {
+///         values.push(fetch_value().await);
+///         animation.draw(|canvas| {
+///             for (x, &y) in values.iter().enumerate() {
+///                 canvas.set(x, y);
+///             }
+///         }).unwrap();
+///     }
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+pub struct AsyncAnimation {
+    frames: Frames,
+    interval: ::tokio::time::Interval,
+    /// The frame started by the last tick, if it hasn’t been drawn yet.
+    current: Option<(Canvas, u64, Duration)>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncAnimation {
+    /// Sets up the terminal for an animation at `fps` frames per second.
+    ///
+    /// This must be called from within a tokio runtime with timers enabled.
+    pub fn new(fps: f64) -> io::Result<AsyncAnimation> {
+        use tokio::time::{self, MissedTickBehavior};
+
+        let mut interval = time::interval(period(fps));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Ok(AsyncAnimation { frames: Frames::new()?, interval, current: None })
+    }
+
+    /// Returns a future that waits until the next frame is due, and resolves to its number,
+    /// starting from zero, and the time since the last frame started, or `None` once the user has
+    /// pressed Ctrl-C.
+    ///
+    /// Polling the future never blocks, so other tasks carry on between frames even on a
+    /// single-threaded runtime:
+    ///
+    /// ```edition2018
+    /// # #[cfg(unix)] extern crate libc;
+    /// # extern crate drawille;
+    /// # extern crate tokio;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::time::Duration;
+    ///
+    /// use drawille::animation::AsyncAnimation;
+    ///
+    /// # #[cfg(unix)]
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    /// #   // Run on a pseudo-terminal, drained of what's drawn to it.
+    /// #   unsafe {
+    /// #       let (mut master, mut slave) = (0, 0);
+    /// #       let null = std::ptr::null_mut();
+    /// #       assert_eq!(libc::openpty(&mut master, &mut slave, null, null as *mut _,
+    /// #                                null as *mut _), 0);
+    /// #       libc::dup2(slave, libc::STDIN_FILENO);
+    /// #       libc::dup2(slave, libc::STDOUT_FILENO);
+    /// #       std::thread::spawn(move || {
+    /// #           let mut buffer = [0u8; 4096];
+    /// #           while libc::read(master, buffer.as_mut_ptr() as *mut _, buffer.len()) > 0 {}
+    /// #       });
+    /// #   }
+    ///     let count = Arc::new(AtomicUsize::new(0));
+    ///     let counter = count.clone();
+    ///     tokio::spawn(async move {
+    ///         loop {
+    ///             tokio::time::sleep(Duration::from_millis(1)).await;
+    ///             counter.fetch_add(1, Ordering::SeqCst);
+    ///         }
+    ///     });
+    ///
+    ///     let mut animation = AsyncAnimation::new(50.0).unwrap();
+    ///     let mut last = 0;
+    ///     for frame in 0..5 {
+    ///         animation.tick().await.unwrap();
+    ///         animation.draw(|canvas| canvas.set(frame, 0)).unwrap();
+    ///         let now = count.load(Ordering::SeqCst);
+    ///         assert!(frame == 0 || now > last);
+    ///         last = now;
+    ///     }
+    /// }
+    /// # #[cfg(not(unix))] fn main() {}
+    /// ```
+    pub fn tick(&mut self) -> Tick<'_> {
+        Tick { animation: self }
+    }
+
+    /// Returns the input events received before the frame started by the last `tick`.
+    pub fn events(&self) -> &[Event] {
+        &self.frames.events
+    }
+
+    /// Starts reporting mouse events.
+    pub fn enable_mouse(&mut self) -> io::Result<()> {
+        self.frames.terminal.enable_mouse()
+    }
+
+    /// Draws the frame started by the last `tick` onto a cleared `Canvas` that fills the terminal,
+    /// and shows it.
+    ///
+    /// Does nothing if the frame has already been drawn.
+    pub fn draw<F: FnOnce(&mut Canvas)>(&mut self, draw: F) -> io::Result<()> {
+        match self.current.take() {
+            Some((mut canvas, _, _)) => {
+                draw(&mut canvas);
+                self.frames.finish(&canvas)
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+/// The future returned by `AsyncAnimation::tick`.
+#[cfg(feature = "tokio")]
+pub struct Tick<'a> {
+    animation: &'a mut AsyncAnimation,
+}
+
+#[cfg(feature = "tokio")]
+impl Future for Tick<'_> {
+    type Output = Option<(u64, Duration)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<(u64, Duration)>> {
+        let animation = &mut *self.get_mut().animation;
+        match animation.interval.poll_tick(cx) {
+            Poll::Ready(_) => {
+                animation.current = animation.frames.start();
+                Poll::Ready(animation.current.as_ref().map(|&(_, frame, dt)| (frame, dt)))
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Backends that frames are rendered to
+//!
+//! A `RenderBackend` is told each cell of a `frame::Frame` in turn, and then to flush, so the same
+//! drawing can go to different places without the code that drew it knowing about escape codes:
+//! `StringBackend` collects rows of text, plain or with escape codes, as the canvases' `rows`
+//! draw them, `AnsiBackend` writes to a terminal, `BufferBackend` draws into a ratatui `Buffer`
+//! with the `ratatui` feature, `FnBackend` hands each cell to a function, such as one copying it
+//! into the buffer of another TUI library, and `CaptureBackend` records everything it's told, for
+//! tests.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::backend::StringBackend;
+//! use drawille::braille::Canvas;
+//!
+//! let mut canvas = Canvas::new(4, 4);
+//! canvas.line(0, 0, 3, 0);
+//! let mut backend = StringBackend::new();
+//! canvas.to_frame().render(&mut backend).unwrap();
+//! assert_eq!(backend.text(), "⠉⠉");
+//! ```
+
+use std::io::{self, Write};
+
+use frame::Cell;
+use unicode_width::UnicodeWidthChar;
+
+/// Somewhere frames are rendered to, a cell at a time.
+pub trait RenderBackend {
+    /// Writes a cell at the given column and row of characters.
+    fn write_cell(&mut self, column: usize, row: usize, cell: &Cell) -> io::Result<()>;
+
+    /// Shows everything written since the last flush.
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+/// A backend that collects the cells written to it as rows of text, either plain or with ANSI
+/// escape codes for their colours and attributes.
+///
+/// Rows are as long as needed to hold the last cell written to them, with blanks where nothing
+/// was written.
+///
+/// ```
+/// use drawille::backend::{RenderBackend, StringBackend};
+/// use drawille::block::Color;
+/// use drawille::frame::Cell;
+///
+/// let mut backend = StringBackend::new();
+/// backend.write_cell(0, 0, &Cell::new('字')).unwrap();
+/// backend.write_cell(2, 0, &Cell { fg: Some(Color::Red), ..Cell::new('!') }).unwrap();
+/// assert_eq!(backend.rows(), vec!["字!"]);
+/// assert_eq!(backend.ansi_rows(), vec!["字\x1b[31m!\x1b[0m"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringBackend {
+    /// The cells of each row, with `'\0'` in the second cell of each wide character.
+    rows: Vec<Vec<Cell>>,
+}
+
+impl StringBackend {
+    /// Creates a new, empty `StringBackend`.
+    pub fn new() -> StringBackend {
+        StringBackend { rows: vec![] }
+    }
+
+    /// Returns the rows of text written, without their colours and attributes.
+    pub fn rows(&self) -> Vec<String> {
+        self.write_rows(|cell, out| out.push(cell.char))
+    }
+
+    /// Returns the rows of text written, joined by newlines.
+    pub fn text(&self) -> String {
+        self.rows().join("\n")
+    }
+
+    /// Returns the rows of text written, with ANSI escape codes for their colours and attributes.
+    ///
+    /// Each styled character is wrapped in the codes that turn its style on and back off, so rows
+    /// can be written anywhere without the style of one character carrying on to the next.
+    pub fn ansi_rows(&self) -> Vec<String> {
+        self.write_rows(|cell, out| {
+            if cell.is_plain() {
+                out.push(cell.char);
+            } else {
+                out.push_str(&format!("{}{}\x1b[0m", cell.escape(), cell.char));
+            }
+        })
+    }
+
+    fn write_rows<F: Fn(&Cell, &mut String)>(&self, write: F) -> Vec<String> {
+        self.rows.iter().map(|row| {
+            let mut out = String::new();
+            for cell in row.iter().filter(|cell| cell.char != '\0') {
+                write(cell, &mut out);
+            }
+            out
+        }).collect()
+    }
+}
+
+impl RenderBackend for StringBackend {
+    fn write_cell(&mut self, column: usize, row: usize, cell: &Cell) -> io::Result<()> {
+        let width = if cell.char.width() == Some(2) { 2 } else { 1 };
+        if self.rows.len() <= row {
+            self.rows.resize(row + 1, vec![]);
+        }
+        let row = &mut self.rows[row];
+        if row.len() < column + width {
+            row.resize(column + width, Cell::default());
+        }
+        row[column] = *cell;
+        if width == 2 {
+            row[column + 1] = Cell::new('\0');
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A backend that writes cells to a terminal with ANSI escape codes.
+///
+/// The cursor is moved only when a cell doesn't follow straight on from the one written before
+/// it, and the style is only changed when it differs from that of the cell before, so rendering
+/// just the cells that changed with `Frame::render_changes` writes very little. Flushing resets the
+/// style and flushes the writer.
+///
+/// ```
+/// use drawille::backend::{AnsiBackend, RenderBackend};
+/// use drawille::block::Color;
+/// use drawille::frame::Cell;
+///
+/// let mut backend = AnsiBackend::new(vec![]);
+/// backend.write_cell(0, 0, &Cell::new('a')).unwrap();
+/// backend.write_cell(1, 0, &Cell { fg: Some(Color::Red), ..Cell::new('b') }).unwrap();
+/// backend.write_cell(5, 2, &Cell::new('c')).unwrap();
+/// backend.flush().unwrap();
+/// assert_eq!(String::from_utf8(backend.into_inner()).unwrap(),
+///            "\x1b[1;1H\x1b[0ma\x1b[0m\x1b[31mb\x1b[3;6H\x1b[0mc\x1b[0m");
+/// ```
+#[derive(Debug)]
+pub struct AnsiBackend<W: Write> {
+    writer: W,
+    origin: (usize, usize),
+    /// Where the cursor is after the last cell written, if it's known.
+    cursor: Option<(usize, usize)>,
+    /// The style of the last cell written, if it's still on.
+    style: Option<Cell>,
+}
+
+impl<W: Write> AnsiBackend<W> {
+    /// Creates a new `AnsiBackend` writing to `writer`, with the top left cell of frames in the
+    /// top left corner of the terminal.
+    pub fn new(writer: W) -> AnsiBackend<W> {
+        AnsiBackend { writer, origin: (0, 0), cursor: None, style: None }
+    }
+
+    /// Moves the top left cell of frames to the given column and row of the terminal, and
+    /// returns the backend for use again.
+    pub fn origin(mut self, column: usize, row: usize) -> AnsiBackend<W> {
+        self.origin = (column, row);
+        self
+    }
+
+    /// Returns the writer, consuming the backend.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RenderBackend for AnsiBackend<W> {
+    fn write_cell(&mut self, column: usize, row: usize, cell: &Cell) -> io::Result<()> {
+        if self.cursor != Some((column, row)) {
+            let (x, y) = (self.origin.0 + column + 1, self.origin.1 + row + 1);
+            write!(self.writer, "\x1b[{};{}H", y, x)?;
+        }
+        let same = self.style.is_some_and(|style| {
+            (style.fg, style.bg, style.attributes) == (cell.fg, cell.bg, cell.attributes)
+        });
+        if !same {
+            write!(self.writer, "\x1b[0m{}", cell.escape())?;
+            self.style = Some(*cell);
+        }
+        write!(self.writer, "{}", cell.char)?;
+        self.cursor = Some((column + cell.char.width().unwrap_or(1).max(1), row));
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        write!(self.writer, "\x1b[0m")?;
+        self.style = None;
+        self.writer.flush()
+    }
+}
+
+/// A backend that hands each cell to a function, along with its column and row.
+///
+/// This is the way to draw into the cell buffer of a TUI library other than ratatui, which has
+/// `BufferBackend`: the function converts each cell's colours and attributes into the library's
+/// own and sets the cell at the matching position in the buffer.
+///
+/// ```
+/// use drawille::backend::FnBackend;
+/// use drawille::braille::Canvas;
+///
+/// let mut canvas = Canvas::new(4, 4);
+/// canvas.text(0, 0, "hi");
+/// let mut buffer = vec![vec![' '; 2]; 1];
+/// canvas.to_frame().render(&mut FnBackend::new(|column, row, cell| {
+///     buffer[row][column] = cell.char;
+/// })).unwrap();
+/// assert_eq!(buffer, vec![vec!['h', 'i']]);
+/// ```
+pub struct FnBackend<F: FnMut(usize, usize, &Cell)> {
+    write: F,
+}
+
+impl<F: FnMut(usize, usize, &Cell)> FnBackend<F> {
+    /// Creates a new `FnBackend` calling `write` with every cell written.
+    pub fn new(write: F) -> FnBackend<F> {
+        FnBackend { write }
+    }
+}
+
+impl<F: FnMut(usize, usize, &Cell)> RenderBackend for FnBackend<F> {
+    fn write_cell(&mut self, column: usize, row: usize, cell: &Cell) -> io::Result<()> {
+        (self.write)(column, row, cell);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A backend that draws into an area of a ratatui `Buffer`, such as the one a widget is given to
+/// render into, cutting the frame short at the edges of the area.
+///
+/// Cells are converted just as `From<&Cell>` converts them for `ratatui::buffer::Cell`.
+///
+/// ```
+/// # extern crate drawille;
+/// # extern crate ratatui;
+/// use drawille::backend::BufferBackend;
+/// use drawille::braille::Canvas;
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+///
+/// # fn main() {
+/// let mut canvas = Canvas::new(4, 4);
+/// canvas.text(0, 0, "hi");
+/// let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+/// canvas.to_frame().render(&mut BufferBackend::new(&mut buffer, Rect::new(1, 1, 3, 1)))
+///     .unwrap();
+/// assert_eq!(buffer, Buffer::with_lines(["    ", " hi "]));
+/// # }
+/// ```
+#[cfg(feature = "ratatui")]
+pub struct BufferBackend<'a> {
+    buffer: &'a mut ::ratatui::buffer::Buffer,
+    area: ::ratatui::layout::Rect,
+}
+
+#[cfg(feature = "ratatui")]
+impl<'a> BufferBackend<'a> {
+    /// Creates a new `BufferBackend` drawing into `area` of `buffer`, with the top left cell of
+    /// frames in the top left corner of the area.
+    pub fn new(buffer: &'a mut ::ratatui::buffer::Buffer, area: ::ratatui::layout::Rect)
+               -> BufferBackend<'a> {
+        let area = area.intersection(buffer.area);
+        BufferBackend { buffer, area }
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl<'a> RenderBackend for BufferBackend<'a> {
+    fn write_cell(&mut self, column: usize, row: usize, cell: &Cell) -> io::Result<()> {
+        if column >= self.area.width as usize || row >= self.area.height as usize {
+            return Ok(());
+        }
+        let (x, y) = (self.area.x + column as u16, self.area.y + row as u16);
+        self.buffer[(x, y)] = cell.into();
+        // ratatui leaves the cell after a wide character blank.
+        if cell.char.width() == Some(2) && column + 1 < self.area.width as usize {
+            self.buffer[(x + 1, y)].reset();
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A backend that records every cell written and how many times it was flushed, for tests.
+///
+/// ```
+/// use drawille::backend::CaptureBackend;
+/// use drawille::frame::{Cell, Frame};
+///
+/// let (before, mut after) = (Frame::new(3, 1), Frame::new(3, 1));
+/// after[(1, 0)] = Cell::new('x');
+/// let mut capture = CaptureBackend::new();
+/// after.render_changes(&before, &mut capture).unwrap();
+/// assert_eq!(capture.cells, vec![(1, 0, Cell::new('x'))]);
+/// assert_eq!(capture.flushes, 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CaptureBackend {
+    /// The column, row and contents of each cell written, in order.
+    pub cells: Vec<(usize, usize, Cell)>,
+    /// The number of times the backend has been flushed.
+    pub flushes: usize,
+}
+
+impl CaptureBackend {
+    /// Creates a new `CaptureBackend` that hasn't recorded anything.
+    pub fn new() -> CaptureBackend {
+        CaptureBackend { cells: vec![], flushes: 0 }
+    }
+}
+
+impl RenderBackend for CaptureBackend {
+    fn write_cell(&mut self, column: usize, row: usize, cell: &Cell) -> io::Result<()> {
+        self.cells.push((column, row, *cell));
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+//! Large text drawn with pixels
+//!
+//! This module draws text as big letters made of Braille pixels, from a built-in font five pixels
+//! wide and seven high, for title screens and headings. Each letter of the font takes up three
+//! characters across and two down, or more when scaled up.
+//!
+//! The font has the capital letters, digits and common punctuation; lower case letters are drawn
+//! as capitals, and anything else as a question mark.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::banner;
+//! use drawille::block::Color;
+//! use drawille::braille::Canvas;
+//!
+//! assert_eq!(banner::banner("HI"), "⣇⣀⡇⠈⡏ \n⠇ ⠇⠠⠧ ");
+//!
+//! let mut canvas = Canvas::new(80, 16);
+//! banner::draw_colored(&mut canvas, 0, 0, "Game over", 2, Color::Red);
+//! assert_eq!(banner::width("Game over", 2), 106);
+//! ```
+
+use block::Color;
+use braille::Canvas;
+
+/// The width of each letter of the font in pixels, not counting the gap after it.
+const LETTER_WIDTH: usize = 5;
+
+/// The height of each letter of the font in pixels.
+const LETTER_HEIGHT: usize = 7;
+
+/// How far apart lines of text are in pixels, leaving a gap below each line so that it fills
+/// whole rows of Braille characters.
+const LINE_HEIGHT: usize = 8;
+
+/// Each letter of the font, as a row of pixels at a time from the top, with the leftmost pixel
+/// in the highest of the five bits.
+static FONT: [(char, [u8; LETTER_HEIGHT]); 50] = [
+    ('A', [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11]),
+    ('B', [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e]),
+    ('C', [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e]),
+    ('D', [0x1e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1e]),
+    ('E', [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f]),
+    ('F', [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10]),
+    ('G', [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f]),
+    ('H', [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11]),
+    ('I', [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+    ('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c]),
+    ('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11]),
+    ('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f]),
+    ('M', [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11]),
+    ('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11]),
+    ('O', [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e]),
+    ('P', [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10]),
+    ('Q', [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d]),
+    ('R', [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11]),
+    ('S', [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e]),
+    ('T', [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+    ('U', [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e]),
+    ('V', [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04]),
+    ('W', [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a]),
+    ('X', [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11]),
+    ('Y', [0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x04]),
+    ('Z', [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f]),
+    ('0', [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e]),
+    ('1', [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+    ('2', [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f]),
+    ('3', [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e]),
+    ('4', [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02]),
+    ('5', [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e]),
+    ('6', [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e]),
+    ('7', [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+    ('8', [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e]),
+    ('9', [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c]),
+    (' ', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('!', [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04]),
+    ('?', [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04]),
+    ('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c]),
+    (',', [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08]),
+    (':', [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00]),
+    ('-', [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00]),
+    ('+', [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00]),
+    ('/', [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00]),
+    ('\'', [0x04, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('(', [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02]),
+    (')', [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08]),
+    ('=', [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00]),
+    ('*', [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00]),
+];
+
+/// Returns the rows of pixels of the letter for a character.
+fn letter(c: char) -> [u8; LETTER_HEIGHT] {
+    let c = c.to_ascii_uppercase();
+    FONT.iter().find(|&&(letter, _)| letter == c).or_else(|| FONT.iter().find(|l| l.0 == '?'))
+        .unwrap().1
+}
+
+/// Returns how many pixels wide the widest line of text is when drawn at the given scale.
+pub fn width(s: &str, scale: usize) -> usize {
+    s.lines().map(|line| {
+        let letters = line.chars().count();
+        (letters * (LETTER_WIDTH + 1)).saturating_sub(1) * scale
+    }).max().unwrap_or(0)
+}
+
+/// Returns how many pixels high text is when drawn at the given scale.
+pub fn height(s: &str, scale: usize) -> usize {
+    s.lines().count() * LINE_HEIGHT * scale
+}
+
+/// Draws text in large letters onto the canvas, with the top left corner of the first letter at
+/// `(x, y)`, and each pixel of the font drawn as a square `scale` pixels across.
+///
+/// Each line of the text is drawn below the one before.
+pub fn draw(canvas: &mut Canvas, x: usize, y: usize, s: &str, scale: usize) {
+    draw_with(x, y, s, scale, |x, y| canvas.set(x, y));
+}
+
+/// Draws text in large letters just like `draw`, in the given colour.
+pub fn draw_colored(canvas: &mut Canvas, x: usize, y: usize, s: &str, scale: usize,
+                    color: Color) {
+    draw_with(x, y, s, scale, |x, y| canvas.set_colored(x, y, color));
+}
+
+/// Draws text in large letters onto a new canvas just big enough for it, and returns it drawn to
+/// a `String`.
+pub fn banner(s: &str) -> String {
+    let mut canvas = Canvas::new(width(s, 1), height(s, 1));
+    draw(&mut canvas, 0, 0, s, 1);
+    canvas.frame()
+}
+
+/// Calls `set` with each pixel of text drawn in large letters.
+fn draw_with<F: FnMut(usize, usize)>(x: usize, y: usize, s: &str, scale: usize, mut set: F) {
+    for (line, text) in s.lines().enumerate() {
+        let top = y + line * LINE_HEIGHT * scale;
+        for (i, c) in text.chars().enumerate() {
+            let left = x + i * (LETTER_WIDTH + 1) * scale;
+            for (row, bits) in letter(c).iter().enumerate() {
+                for column in 0..LETTER_WIDTH {
+                    if bits & (0x10 >> column) == 0 {
+                        continue;
+                    }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            set(left + column * scale + dx, top + row * scale + dy);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,16 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::cmp;
-use std::default::Default;
-use std::fmt::{self, Formatter};
-use std::ops::{Index, IndexMut};
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign, Index, IndexMut};
+
+use geometry;
+use braille::DrawError;
+use console;
+use frame::{self, Frame};
+use image;
+use palette::{Palette, Rgb};
+use unicode_width::UnicodeWidthChar;
 
 #[derive(Copy, Debug, Clone, PartialEq, Eq)]
 pub enum Color {
@@ -17,89 +24,155 @@ pub enum Color {
     White,
 }
 
-#[derive(Copy, Debug, Clone, PartialEq, Eq)]
-struct ColorPair(Color, Color);
+/// Every colour, in the order of their values, for handing out references to.
+static COLORS: [Color; 8] = [Color::Black, Color::Red, Color::Green, Color::Yellow,
+                             Color::Blue, Color::Magenta, Color::Cyan, Color::White];
 
-impl fmt::Display for ColorPair {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        // TODO: add Windows support if needed
-        let ColorPair(first, second) = *self;
-        let finit = "\x1b[0;";
-        let fend = first as u32;
-        let f = format!("{}4{}m", finit, fend);
-        let sinit = "\x1b[";
-        let send = second as u32;
-        let s = format!("{}3{}m", sinit, send);
-        try!(write!(fmt, "{}{}", f, s));
-        Ok(())
+/// How a colour drawn onto a pixel that is already set combines with the colour there.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Default)]
+pub enum Blend {
+    /// The new colour replaces the old one.
+    #[default]
+    Replace,
+    /// The colours mix like light, giving the palette colour nearest to their sum, so red and
+    /// green make yellow.
+    Mix,
+}
+
+/// A set of text attributes, such as `Attributes::BOLD | Attributes::UNDERLINE`.
+///
+/// Terminals vary in which attributes they support; those they don’t are usually ignored.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Attributes(u8);
+
+impl Attributes {
+    pub const BOLD: Attributes = Attributes(1);
+    pub const DIM: Attributes = Attributes(1 << 1);
+    pub const ITALIC: Attributes = Attributes(1 << 2);
+    pub const UNDERLINE: Attributes = Attributes(1 << 3);
+    pub const BLINK: Attributes = Attributes(1 << 4);
+    pub const REVERSE: Attributes = Attributes(1 << 5);
+
+    /// Returns the empty set of attributes, for plain text.
+    pub fn empty() -> Attributes {
+        Attributes(0)
+    }
+
+    /// Detects whether there are no attributes in the set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Detects whether the set contains all the attributes in `other`.
+    pub fn contains(self, other: Attributes) -> bool {
+        self.0 & other.0 == other.0
     }
 }
 
-#[derive(Copy, Debug, Clone, PartialEq, Eq)]
-enum Pixel {
-    Char(ColorPair, char),
-    Pair(ColorPair),
+impl BitOr for Attributes {
+    type Output = Attributes;
+
+    fn bitor(self, other: Attributes) -> Attributes {
+        Attributes(self.0 | other.0)
+    }
 }
 
-impl Default for Pixel {
-    fn default() -> Pixel {
-        Pixel::Char(ColorPair(Color::Black, Color::Black), ' ')
+impl BitOrAssign for Attributes {
+    fn bitor_assign(&mut self, other: Attributes) {
+        self.0 |= other.0;
     }
 }
 
-impl fmt::Display for Pixel {
+impl fmt::Display for Attributes {
+    /// Writes the SGR escape code that turns on the attributes, or nothing if there are none.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Pixel::Char(cp, a) => try!(write!(f, "{}{}", cp, a)),
-            Pixel::Pair(a) => try!(write!(f, "{}▄", a)),
+        // The SGR codes for each attribute, in the order of their bits.
+        let codes = [1, 2, 3, 4, 5, 7];
+        let on = codes.iter().enumerate().filter(|&(i, _)| self.0 & 1 << i != 0)
+            .map(|(_, code)| code.to_string()).collect::<Vec<_>>();
+        if on.is_empty() {
+            Ok(())
+        } else {
+            write!(f, "\x1b[{}m", on.join(";"))
         }
-        Ok(())
     }
 }
 
-impl Index<usize> for Pixel {
-    type Output = Color;
+/// The shading characters used by monochrome canvases, from darkest to brightest.
+static SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
 
-    fn index<'a>(&'a self, index: usize) -> &'a Color {
-        let cp = match *self {
-            Pixel::Pair(ref cp) => cp,
-            _ => panic!("indexing a text pixel"),
-        };
-        let ColorPair(ref c1, ref c2) = *cp;
-        match index {
-            0 => c1,
-            1 => c2,
-            _ => panic!("ColorPair index out of bounds"),
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+struct ColorPair(Color, Color);
+
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+enum Pixel {
+    Char(ColorPair, Attributes, char),
+    /// The second cell taken up by a wide character in the cell before it.
+    Wide(ColorPair),
+    /// The colours of the top and bottom pixels, if they’ve been drawn.
+    Pair([Option<Color>; 2]),
+}
+
+impl Pixel {
+    /// Returns the pixel as a frame cell: a half block with the colours of its top and bottom
+    /// pixels, showing pixels that haven’t been drawn in `background`, or in the terminal’s own
+    /// background if that’s `None`, or text with its colours and attributes. If `monochrome` is
+    /// set, the cell has no colours, and pixels are shown as `shade` shows them instead.
+    fn cell(&self, background: Option<Color>, monochrome: bool) -> frame::Cell {
+        if monochrome {
+            return frame::Cell::new(self.shade(background));
+        }
+        let colored = |c, fg, bg| frame::Cell { fg, bg, ..frame::Cell::new(c) };
+        match *self {
+            Pixel::Char(ColorPair(bg, fg), attributes, c) => {
+                frame::Cell { attributes, ..colored(c, Some(fg), Some(bg)) }
+            },
+            Pixel::Wide(ColorPair(bg, fg)) => colored(' ', Some(fg), Some(bg)),
+            Pixel::Pair([None, None]) => colored(' ', None, background),
+            Pixel::Pair([top, bottom]) => match (top.or(background), bottom.or(background)) {
+                (Some(top), None) => colored('▀', Some(top), None),
+                (top, bottom) => colored('▄', bottom, top),
+            },
+        }
+    }
+
+    /// Returns the character shown for the pixel without colours, showing the colours of its
+    /// pixels as a shading character as bright as they are on average.
+    fn shade(&self, background: Option<Color>) -> char {
+        match *self {
+            Pixel::Char(_, _, a) => a,
+            Pixel::Wide(_) => ' ',
+            Pixel::Pair(pair) => {
+                let brightness = pair.iter().map(|&color| {
+                    color.or(background).map_or(0.0, |color| {
+                        let [r, g, b] = image::rgb(color);
+                        (0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64) / 255.0
+                    })
+                }).sum::<f64>() / 2.0;
+                // Rounding up means anything lighter than black shows at least the lightest shade.
+                let level = (brightness * (SHADES.len() - 1) as f64).ceil() as usize;
+                SHADES[level.min(SHADES.len() - 1)]
+            },
         }
     }
 }
 
-impl IndexMut<usize> for Pixel {
-    fn index_mut<'a>(&'a mut self, index: usize) -> &'a mut Color {
-        let cp = match *self {
-            Pixel::Pair(ref mut cp) => cp,
+impl Index<usize> for Pixel {
+    type Output = Option<Color>;
+
+    fn index(&self, index: usize) -> &Option<Color> {
+        match *self {
+            Pixel::Pair(ref pair) => &pair[index],
             _ => panic!("indexing a text pixel"),
-        };
-        let ColorPair(ref mut c1, ref mut c2) = *cp;
-        match index {
-            0 => c1,
-            1 => c2,
-            _ => panic!("ColorPair index out of bounds"),
         }
     }
 }
 
-impl Pixel {
-    fn index(&self, index: usize) -> Color {
-        let cp = match *self {
-            Pixel::Pair(cp) => cp,
+impl IndexMut<usize> for Pixel {
+    fn index_mut(&mut self, index: usize) -> &mut Option<Color> {
+        match *self {
+            Pixel::Pair(ref mut pair) => &mut pair[index],
             _ => panic!("indexing a text pixel"),
-        };
-        let ColorPair(c1, c2) = cp;
-        match index {
-            0 => c1,
-            1 => c2,
-            _ => panic!("ColorPair index out of bounds"),
         }
     }
 }
@@ -107,6 +180,11 @@ impl Pixel {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Canvas {
     blocks: HashMap<(usize, usize), Pixel>,
+    blend: Blend,
+    background: Option<Color>,
+    monochrome: bool,
+    /// The exact colours drawn with `set_blend`, with the colours they were quantized to.
+    exact: HashMap<(usize, usize), (Rgb, Color)>,
     width:  usize,
     height: usize,
 }
@@ -115,76 +193,410 @@ impl Canvas {
     pub fn new(width: usize, height: usize) -> Canvas {
         Canvas {
             blocks: HashMap::new(),
-            width: width / 2,
-            height: height / 4,
+            blend: Blend::Replace,
+            background: Some(Color::Black),
+            monochrome: console::no_color(),
+            exact: HashMap::new(),
+            width,
+            height,
         }
     }
 
+    /// Returns the width of the canvas in pixels, as given to `new`.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas in pixels, as given to `new`.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the width of the canvas in characters, which is one per pixel.
+    pub fn char_width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas in characters, rounded up to cover the whole height.
+    pub fn char_height(&self) -> usize {
+        self.height.div_ceil(2)
+    }
+
+    /// Changes the size of the canvas to `width` by `height` pixels, keeping what’s drawn inside
+    /// the new size and deleting everything beyond it.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        let (columns, rows) = (self.char_width(), self.char_height());
+        self.blocks.retain(|&(x, y), _| x < columns && y < rows);
+        self.exact.retain(|&(x, y), _| x < width && y < height);
+        if height % 2 == 1 {
+            // The bottom row of blocks is cut in half.
+            let row = height / 2;
+            for (_, pixel) in self.blocks.iter_mut().filter(|&(&(_, y), _)| y == row) {
+                if let Pixel::Pair(ref mut pair) = *pixel {
+                    pair[1] = None;
+                }
+            }
+            self.blocks.retain(|_, pixel| *pixel != Pixel::Pair([None, None]));
+        }
+    }
+
+    /// Returns how many columns and rows of characters `rows` draws: enough for the size given
+    /// to `new`, and for everything drawn.
+    fn extent(&self) -> (usize, usize) {
+        let columns = self.blocks.keys().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let rows = self.blocks.keys().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        (cmp::max(self.char_width(), columns), cmp::max(self.char_height(), rows))
+    }
+
+    /// Sets how colours drawn over pixels that are already set combine with them, and returns
+    /// the canvas for use again.
+    pub fn blend(mut self, blend: Blend) -> Canvas {
+        self.blend = blend;
+        self
+    }
+
+    /// Sets the colour shown where nothing has been drawn, and returns the canvas for use again.
+    ///
+    /// The background is black unless set otherwise. With `None`, nothing is drawn there at all,
+    /// so the terminal’s own background shows through.
+    ///
+    /// ```
+    /// use drawille::block::{Canvas, Color};
+    ///
+    /// let mut canvas = Canvas::new(2, 2).background(None).monochrome(false);
+    /// canvas.set(0, 0, Color::Red);
+    /// assert_eq!(canvas.rows()[0], "\x1b[31m▀\x1b[0m ");
+    /// ```
+    pub fn background(mut self, color: Option<Color>) -> Canvas {
+        self.background = color;
+        self
+    }
+
+    /// Sets whether the canvas is drawn in shades of grey with shading characters rather than in
+    /// colour, and returns the canvas for use again.
+    ///
+    /// Drawn in shades of grey, the rows have no escape codes at all, which suits log files and
+    /// other output that isn’t going to a terminal. New canvases are drawn this way if colour has
+    /// been turned off with `NO_COLOR`.
+    ///
+    /// ```
+    /// use drawille::block::{Canvas, Color};
+    ///
+    /// let mut canvas = Canvas::new(4, 2).monochrome(true);
+    /// canvas.line(0, 0, 2, 0, Color::White);
+    /// canvas.set(1, 1, Color::White);
+    /// canvas.set(2, 1, Color::Blue);
+    /// assert_eq!(canvas.rows()[0], "▒█▓ ");
+    /// ```
+    pub fn monochrome(mut self, monochrome: bool) -> Canvas {
+        self.monochrome = monochrome;
+        self
+    }
+
     pub fn clear(&mut self) {
         self.blocks.clear();
+        self.exact.clear();
     }
 
+    /// Writes text onto the canvas in the given colours, starting at the cell containing the
+    /// pixel at the specified coordinates.
+    ///
+    /// Wide characters take up two cells, just as they do in the terminal, so the text after them
+    /// stays lined up:
+    ///
+    /// ```
+    /// use drawille::block::{Canvas, Color};
+    ///
+    /// // Leaves out the colour escape codes.
+    /// fn plain(row: &str) -> String {
+    ///     let mut escape = false;
+    ///     row.chars().filter(|&c| {
+    ///         let keep = !escape && c != '\x1b';
+    ///         escape = if escape { c != 'm' } else { c == '\x1b' };
+    ///         keep
+    ///     }).collect()
+    /// }
+    ///
+    /// let mut canvas = Canvas::new(5, 2).monochrome(false);
+    /// canvas.text(0, 0, Color::White, Color::Black, "a世b");
+    /// assert_eq!(plain(&canvas.rows()[0]), "a世b ");
+    ///
+    /// // Drawing over half of a wide character blanks the other half.
+    /// canvas.text(2, 0, Color::White, Color::Black, "e\u{301}🙂");
+    /// assert_eq!(plain(&canvas.rows()[0]), "a e🙂");
+    /// canvas.set(4, 0, Color::Red);
+    /// assert_eq!(plain(&canvas.rows()[0]), "a e ▄");
+    /// ```
     pub fn text<S: AsRef<str>>(&mut self, x: usize, y: usize, fg: Color, bg: Color, s: S) {
-        let (row, col) = (x, y / 2);
-        for (i, c) in s.as_ref().chars().enumerate() {
-            match self.blocks.entry((row + i, col)) {
-                Entry::Occupied(e) => *e.into_mut() = Pixel::Char(ColorPair(bg, fg), c),
-                Entry::Vacant(e) => { e.insert(Pixel::Char(ColorPair(bg, fg), c)); },
+        self.styled_text(x, y, fg, bg, Attributes::empty(), s);
+    }
+
+    /// Writes text just like `text`, or returns an error without writing anything if any of it
+    /// would go beyond `braille::MAX_COORDINATE` or it has a control character.
+    pub fn try_text<S: AsRef<str>>(&mut self, x: usize, y: usize, fg: Color, bg: Color, s: S)
+                                   -> Result<(), DrawError> {
+        geometry::check_text(x, y, 1, s.as_ref())?;
+        self.text(x, y, fg, bg, s);
+        Ok(())
+    }
+
+    /// Writes text onto the canvas just like `text`, with attributes such as bold or underline.
+    ///
+    /// Wide characters, such as most CJK characters and emoji, take up two cells, and characters
+    /// with no width of their own, such as combining accents and control characters, are left out.
+    ///
+    /// ```
+    /// use drawille::block::{Attributes, Canvas, Color};
+    ///
+    /// let mut canvas = Canvas::new(2, 2).monochrome(false);
+    /// canvas.styled_text(0, 0, Color::White, Color::Black, Attributes::BOLD | Attributes::REVERSE,
+    ///                    "!");
+    /// assert!(canvas.rows()[0].starts_with("\x1b[1;7m\x1b[37;40m!"));
+    /// ```
+    pub fn styled_text<S: AsRef<str>>(&mut self, x: usize, y: usize, fg: Color, bg: Color,
+                                      attributes: Attributes, s: S) {
+        let (mut row, col) = (x, y / 2);
+        for c in s.as_ref().chars() {
+            let width = c.width().unwrap_or(0);
+            if width == 0 {
+                continue;
+            }
+            self.blocks.insert((row, col), Pixel::Char(ColorPair(bg, fg), attributes, c));
+            if width == 2 {
+                self.blocks.insert((row + 1, col), Pixel::Wide(ColorPair(bg, fg)));
             }
+            row += width;
         }
     }
 
     pub fn set(&mut self, x: usize, y: usize, c: Color) {
+        let blend = self.blend;
+        self.put(x, y, c, blend);
+    }
+
+    /// Draws an RGB colour over the pixel at the specified coordinates with the given opacity,
+    /// from 0 (invisible) to 1 (covering what’s there), and sets the pixel to the closest of the
+    /// default xterm colours to the result.
+    ///
+    /// The colour is mixed with the exact colour last drawn there with `set_blend`, if the pixel
+    /// hasn’t been drawn over since, so that faint colours drawn over and over build up rather
+    /// than being lost to rounding each time. Otherwise it’s mixed with the pixel’s colour, or the
+    /// background, or black if the background is transparent.
+    ///
+    /// ```
+    /// use drawille::block::{Canvas, Color};
+    /// use drawille::palette::Rgb;
+    ///
+    /// let mut canvas = Canvas::new(1, 2);
+    /// canvas.set_blend(0, 0, Rgb(255, 0, 0), 0.25);
+    /// assert_eq!(canvas.get(0, 0), Color::Black);
+    /// canvas.set_blend(0, 0, Rgb(255, 0, 0), 0.25);
+    /// canvas.set_blend(0, 0, Rgb(255, 0, 0), 0.25);
+    /// assert_eq!(canvas.get(0, 0), Color::Red);
+    /// ```
+    pub fn set_blend(&mut self, x: usize, y: usize, color: Rgb, alpha: f64) {
+        let current = self.pixel_color(x, y);
+        let under = match self.exact.get(&(x, y)) {
+            Some(&(exact, quantized)) if current == Some(quantized) => exact,
+            _ => current.map_or(Rgb(0, 0, 0), |color| {
+                let [r, g, b] = image::rgb(color);
+                Rgb(r, g, b)
+            }),
+        };
+        let alpha = if alpha.is_nan() { 0.0 } else { alpha.clamp(0.0, 1.0) };
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * alpha).round() as u8;
+        let mixed = Rgb(mix(under.0, color.0), mix(under.1, color.1), mix(under.2, color.2));
+        let quantized = Palette::xterm().map(mixed);
+        self.put(x, y, quantized, Blend::Replace);
+        self.exact.insert((x, y), (mixed, quantized));
+    }
+
+    /// Sets a pixel, combining its colour with any already there as `blend` says.
+    fn put(&mut self, x: usize, y: usize, c: Color, blend: Blend) {
         let (row, col) = (x, y / 2);
-        let mut block = match self.blocks.entry((row, col)) {
+        let block = match self.blocks.entry((row, col)) {
             Entry::Occupied(e) => e.into_mut(),
-            Entry::Vacant(e) => e.insert(Default::default()),
+            Entry::Vacant(e) => e.insert(Pixel::Pair([None, None])),
         };
-        match block {
-            ref mut a @ &mut Pixel::Char(_, _) => **a = Pixel::Pair(ColorPair(Color::Black, Color::Black)),
-            _ => {},
+        if let Pixel::Char(..) | Pixel::Wide(_) = *block {
+            *block = Pixel::Pair([None, None]);
         }
 
-        block[y % 2] = c;
+        let half = &mut block[y % 2];
+        *half = Some(match (blend, *half) {
+            (Blend::Mix, Some(old)) => {
+                let (old, new) = (image::rgb(old), image::rgb(c));
+                Palette::xterm().map(Rgb(old[0].saturating_add(new[0]),
+                                         old[1].saturating_add(new[1]),
+                                         old[2].saturating_add(new[2])))
+            },
+            _ => c,
+        });
+    }
+
+    /// Sets a pixel just like `set`, or returns an error without drawing anything if it’s further
+    /// from the origin than `braille::MAX_COORDINATE`.
+    pub fn try_set(&mut self, x: usize, y: usize, c: Color) -> Result<(), DrawError> {
+        geometry::check_pixel(x, y)?;
+        self.set(x, y, c);
+        Ok(())
     }
 
     pub fn unset(&mut self, x: usize, y: usize) {
         let (row, col) = (x, y / 2);
-        let mut block = match self.blocks.entry((row, col)) {
+        let block = match self.blocks.entry((row, col)) {
             Entry::Occupied(e) => e.into_mut(),
-            Entry::Vacant(e) => e.insert(Default::default()),
+            Entry::Vacant(e) => e.insert(Pixel::Pair([None, None])),
         };
-        block[y % 2] = Color::Black;
+        if let Pixel::Char(..) | Pixel::Wide(_) = *block {
+            *block = Pixel::Pair([None, None]);
+        }
+        block[y % 2] = None;
+        // Forgetting empty blocks keeps canvases that look the same equal.
+        if *block == Pixel::Pair([None, None]) {
+            self.blocks.remove(&(row, col));
+        }
     }
 
+    /// Sets the pixel at the specified coordinates to `c` if it isn’t set, and unsets it if it is.
+    pub fn toggle(&mut self, x: usize, y: usize, c: Color) {
+        if self.is_set(x, y) {
+            self.unset(x, y);
+        } else {
+            self.set(x, y, c);
+        }
+    }
+
+    /// Returns the colour of the pixel at the specified coordinates, or the background colour if
+    /// it isn’t set, or black if the background is transparent.
+    ///
+    /// Text counts as its background colour.
     pub fn get(&self, x: usize, y: usize) -> Color {
-        let (col, row) = (x, y / 2);
-        let col = self.blocks.get(&(row, col));
+        self.pixel_color(x, y).unwrap_or(Color::Black)
+    }
 
-        match col {
-            None => Color::Black,
-            Some(c) => c.index(y % 2),
+    /// Detects whether the pixel at the specified coordinates has been drawn, telling pixels set
+    /// to black apart from those never drawn or since unset.
+    ///
+    /// Text doesn’t count as drawn pixels.
+    pub fn is_set(&self, x: usize, y: usize) -> bool {
+        match self.blocks.get(&(x, y / 2)) {
+            Some(pixel @ &Pixel::Pair(_)) => pixel[y % 2].is_some(),
+            _ => false,
         }
     }
 
+    /// Draws each row of the canvas, as the frame from `to_frame` renders to a
+    /// `backend::StringBackend`, with ANSI escape codes for its colours unless the canvas is
+    /// monochrome.
     pub fn rows(&self) -> Vec<String> {
-        let maxrow = cmp::max(self.width, self.blocks.keys().map(|&(x, _)| x).max().unwrap_or(0));
-        let maxcol = cmp::max(self.height, self.blocks.keys().map(|&(_, y)| y).max().unwrap_or(0));
+        let frame = self.to_frame();
+        if self.monochrome {
+            frame.plain_rows()
+        } else {
+            frame.ansi_rows()
+        }
+    }
 
-        let mut result = vec![];
-        for y in (0..maxcol + 1) {
-            let mut row = String::new();
-            for x in (0..maxrow + 1) {
-                let col = *self.blocks.get(&(x, y)).unwrap_or(&Default::default());
-                row.push_str(&format!("{}", col));
+    /// Returns the characters drawn on the canvas, with their colours and attributes, as a
+    /// `Frame`, which is what `rows` draws.
+    ///
+    /// Pixels are shown as half blocks with the colours of the pixels above and below, or as
+    /// shading characters without colours if the canvas is monochrome.
+    ///
+    /// ```
+    /// use drawille::block::{Canvas, Color};
+    ///
+    /// let mut canvas = Canvas::new(2, 2).monochrome(false);
+    /// canvas.set(0, 1, Color::Red);
+    /// let frame = canvas.to_frame();
+    /// assert_eq!((frame[(0, 0)].char, frame[(0, 0)].fg, frame[(0, 0)].bg),
+    ///            ('▄', Some(Color::Red), Some(Color::Black)));
+    /// assert_eq!(frame.plain_rows(), vec!["▄ "]);
+    /// ```
+    pub fn to_frame(&self) -> Frame {
+        let (columns, rows) = self.extent();
+        let mut frame = Frame::new(columns, rows);
+        for y in 0..rows {
+            for x in 0..columns {
+                frame[(x, y)] = match self.shown(x, y) {
+                    Some(pixel) => pixel.cell(self.background, self.monochrome),
+                    None => frame::Cell::new('\0'),
+                };
             }
-            result.push(format!("{}\x1b[0m", row));
         }
-        result
+        frame
+    }
+
+    /// Returns the pixel shown in the cell at the given column and row, or `None` for the second
+    /// cell of a wide character, which is shown along with the first.
+    fn shown(&self, x: usize, y: usize) -> Option<Pixel> {
+        let pixel = *self.blocks.get(&(x, y)).unwrap_or(&Pixel::Pair([None, None]));
+        let next = self.blocks.get(&(x + 1, y));
+        let previous = if x > 0 { self.blocks.get(&(x - 1, y)) } else { None };
+        // A wide character and the cell after it are shown together, or as spaces if something
+        // has since been drawn over one of them.
+        match pixel {
+            Pixel::Char(cp, attributes, c) if c.width() == Some(2) => {
+                let c = if let Some(&Pixel::Wide(_)) = next { c } else { ' ' };
+                Some(Pixel::Char(cp, attributes, c))
+            },
+            Pixel::Wide(_) => match previous {
+                Some(&Pixel::Char(_, _, c)) if c.width() == Some(2) => None,
+                _ => Some(pixel),
+            },
+            _ => Some(pixel),
+        }
     }
 
     pub fn frame(&self) -> String {
-        self.rows().connect("\n")
+        self.rows().join("\n")
+    }
+
+    /// Draws the canvas as a sixel image and returns the escape sequence, with each pixel drawn
+    /// `scale` image pixels across.
+    ///
+    /// The image covers the same area as `rows`, with text drawn as its background colour, and
+    /// is transparent where nothing has been drawn if the background is.
+    #[cfg(feature = "sixel")]
+    pub fn sixel(&self, scale: usize) -> String {
+        let (width, height) = self.pixel_size();
+        ::sixel::encode(width, height, scale, |x, y| self.pixel_color(x, y))
+    }
+
+    /// Draws the canvas as a PNG image and returns the kitty graphics protocol escape sequences
+    /// that show it at the cursor, with each pixel drawn `scale` image pixels across.
+    ///
+    /// The image is drawn just like `sixel`.
+    pub fn kitty(&self, scale: usize) -> String {
+        let (width, height) = self.pixel_size();
+        image::kitty(&image::png(width, height, scale, |x, y| self.pixel_color(x, y)))
+    }
+
+    /// Draws the canvas as a PNG image and returns the iTerm2 inline image escape sequence that
+    /// shows it at the cursor, with each pixel drawn `scale` image pixels across.
+    ///
+    /// The image is drawn just like `sixel`.
+    pub fn iterm2(&self, scale: usize) -> String {
+        let (width, height) = self.pixel_size();
+        image::iterm2(&image::png(width, height, scale, |x, y| self.pixel_color(x, y)))
+    }
+
+    /// Returns the size in pixels of the area drawn by `rows`.
+    fn pixel_size(&self) -> (usize, usize) {
+        let (columns, rows) = self.extent();
+        (columns, rows * 2)
+    }
+
+    /// Returns the colour a pixel is drawn in as an image, or `None` if it’s transparent.
+    fn pixel_color(&self, x: usize, y: usize) -> Option<Color> {
+        match self.blocks.get(&(x, y / 2)) {
+            Some(&Pixel::Char(ColorPair(bg, _), _, _)) | Some(&Pixel::Wide(ColorPair(bg, _))) => Some(bg),
+            Some(pixel) => pixel[y % 2].or(self.background),
+            None => self.background,
+        }
     }
 
     pub fn line_vec(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> Vec<(usize, usize)> {
@@ -196,7 +608,7 @@ impl Canvas {
         let r = cmp::max(xdiff, ydiff);
 
         let mut result = vec![];
-        for i in (0..r + 1) {
+        for i in 0..r + 1 {
             let mut x = x1 as isize;
             let mut y = y1 as isize;
 
@@ -212,9 +624,126 @@ impl Canvas {
         result
     }
 
+    /// Draws a line just like `line`, or returns an error without drawing anything if either end
+    /// is further from the origin than `braille::MAX_COORDINATE`.
+    pub fn try_line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, c: Color)
+                    -> Result<(), DrawError> {
+        geometry::check_pixel(x1, y1)?;
+        geometry::check_pixel(x2, y2)?;
+        self.line(x1, y1, x2, y2, c);
+        Ok(())
+    }
+
     pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, c: Color) {
         for &(x, y) in self.line_vec(x1, y1, x2, y2).iter() {
             self.set(x, y, c);
         }
     }
+
+    /// Draws the outline of a rectangle with corners `(x1, y1)` and `(x2, y2)`.
+    pub fn rect(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, c: Color) {
+        self.polygon(&[(x1, y1), (x2, y1), (x2, y2), (x1, y2)], c);
+    }
+
+    /// Fills a rectangle with corners `(x1, y1)` and `(x2, y2)`, including its edges.
+    pub fn fill_rect(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, c: Color) {
+        for y in cmp::min(y1, y2)..cmp::max(y1, y2) + 1 {
+            for x in cmp::min(x1, x2)..cmp::max(x1, x2) + 1 {
+                self.set(x, y, c);
+            }
+        }
+    }
+
+    /// Draws the outline of a circle.
+    ///
+    /// Block pixels are about as wide as they are tall, so the circle looks round.
+    pub fn circle(&mut self, x: usize, y: usize, radius: usize, c: Color) {
+        self.ellipse(x, y, radius, radius, c);
+    }
+
+    /// Draws the outline of an ellipse with radii `rx` across and `ry` down.
+    pub fn ellipse(&mut self, x: usize, y: usize, rx: usize, ry: usize, c: Color) {
+        let center = (x as f64, y as f64);
+        let points = geometry::ellipse_points(center, (rx as f64, ry as f64), 1.0);
+        for pair in points.windows(2) {
+            geometry::segment(pair[0], pair[1], geometry::BOUNDS, |x, y| self.set(x, y, c));
+        }
+    }
+
+    /// Draws the outline of a polygon, joining the last point back to the first.
+    pub fn polygon(&mut self, points: &[(usize, usize)], c: Color) {
+        for (&(x1, y1), &(x2, y2)) in points.iter().zip(points.iter().cycle().skip(1)) {
+            self.line(x1, y1, x2, y2, c);
+        }
+    }
+
+    /// Fills the area around `(x, y)` that is the same colour as it with `c`, spreading up, down,
+    /// left and right.
+    ///
+    /// The fill stays within the area drawn by `rows`; text counts as its background colour and
+    /// is painted over. The area is painted with `c` itself, whatever the canvas’s blend.
+    ///
+    /// ```
+    /// use drawille::block::{Blend, Canvas, Color};
+    ///
+    /// let mut canvas = Canvas::new(4, 4).blend(Blend::Mix);
+    /// canvas.fill_rect(0, 0, 3, 3, Color::White);
+    /// canvas.flood_fill(0, 0, Color::Red);
+    /// assert_eq!(canvas[(3, 3)], Color::Red);
+    /// ```
+    pub fn flood_fill(&mut self, x: usize, y: usize, c: Color) {
+        let (width, height) = self.pixel_size();
+        if x >= width || y >= height {
+            return;
+        }
+        let target = self.pixel_color(x, y);
+        if target == Some(c) {
+            return;
+        }
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if self.pixel_color(x, y) != target {
+                continue;
+            }
+            self.put(x, y, c, Blend::Replace);
+            if x > 0 {
+                stack.push((x - 1, y));
+            }
+            if y > 0 {
+                stack.push((x, y - 1));
+            }
+            if x + 1 < width {
+                stack.push((x + 1, y));
+            }
+            if y + 1 < height {
+                stack.push((x, y + 1));
+            }
+        }
+    }
+}
+
+/// Reads pixels as `canvas[(x, y)]`, just like `get`.
+///
+/// ```
+/// use drawille::block::{Canvas, Color};
+///
+/// let mut canvas = Canvas::new(4, 4);
+/// canvas.set(1, 2, Color::Red);
+/// assert_eq!(canvas[(1, 2)], Color::Red);
+/// assert_eq!(canvas[(2, 1)], Color::Black);
+/// ```
+impl Index<(usize, usize)> for Canvas {
+    type Output = Color;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Color {
+        &COLORS[self.get(x, y) as usize]
+    }
+}
+
+#[cfg(feature = "gif")]
+impl From<&Canvas> for ::gif::Frame {
+    fn from(canvas: &Canvas) -> ::gif::Frame {
+        let (width, height) = canvas.pixel_size();
+        ::gif::Frame::from_fn(width, height, |x, y| canvas.pixel_color(x, y))
+    }
 }
@@ -1,79 +1,733 @@
 //! Terminal graphics using Braille characters
 //!
 //! This module provides an interface for utilising Braille characters to draw a picture to a
-//! terminal, allowing for much smaller pixels but losing proper colour support.
+//! terminal, allowing for much smaller pixels but losing proper colour support: a colour can only
+//! be given to a whole character at a time.
+//!
+//! The characters drawn can be swapped for another font with a `GlyphTable`, such as one mapping
+//! each arrangement of dots to a private-use character, while keeping all of the drawing code.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::collections::hash_map::Entry;
 use std::char;
 use std::cmp;
+use std::error::Error;
 use std::f32;
+use std::fmt;
+use std::ops::{Index, Range};
+
+use block::Color;
+use console;
+use frame::{self, Frame};
+use geometry;
+use image;
+
+pub use geometry::Rect;
+
+static PIXEL_MAP: [[u8; 2]; 4] = [[0x01, 0x08],
+                                  [0x02, 0x10],
+                                  [0x04, 0x20],
+                                  [0x40, 0x80]];
+
+/// Returns the bit for the pixel at the specified coordinates in the mask of dots for its
+/// character.
+///
+/// The bits follow the numbering of Braille dots: the lowest three are the top three pixels of the
+/// left column, the next three the top three of the right column, and the highest two the bottom
+/// left and bottom right pixels.
+pub fn dot(x: usize, y: usize) -> u8 {
+    PIXEL_MAP[y % 4][x % 2]
+}
+
+/// Calls `f` with each pixel on the line from `(x1, y1)` to `(x2, y2)`, in order from the first
+/// end to the second, without drawing anything.
+///
+/// These are exactly the pixels `Canvas::line` sets, so this can be used to check what a line
+/// would pass through, such as for lines of sight.
+///
+/// ```
+/// use drawille::braille::for_line_pixels;
+///
+/// let mut pixels = vec![];
+/// for_line_pixels(0, 0, 3, 1, |x, y| pixels.push((x, y)));
+/// assert_eq!(pixels, vec![(0, 0), (1, 0), (2, 0), (3, 1)]);
+/// ```
+pub fn for_line_pixels<F: FnMut(usize, usize)>(x1: usize, y1: usize, x2: usize, y2: usize, f: F) {
+    geometry::line_pixels(x1, y1, x2, y2, f);
+}
+
+/// Clips the line from `p1` to `p2` to a rectangle given as its left, top, right and bottom
+/// edges, returning the part of the line inside the rectangle, or `None` if none of it is.
+///
+/// ```
+/// use drawille::braille::clip_line;
+///
+/// assert_eq!(clip_line((0.0, 0.0, 10.0, 10.0), (-10.0, 5.0), (20.0, 5.0)),
+///            Some(((0.0, 5.0), (10.0, 5.0))));
+/// assert_eq!(clip_line((0.0, 0.0, 10.0, 10.0), (-10.0, -5.0), (20.0, -5.0)), None);
+/// ```
+pub fn clip_line(rect: Rect, p1: (f64, f64), p2: (f64, f64))
+                 -> Option<((f64, f64), (f64, f64))> {
+    geometry::clip(p1, p2, rect)
+}
+
+/// Returns each character a rectangle of pixels touches, with the mask of its dots inside the
+/// rectangle.
+fn region_masks(x: usize, y: usize, width: usize, height: usize)
+                -> Vec<((usize, usize), u8)> {
+    if width == 0 || height == 0 {
+        return vec![];
+    }
+    let (right, bottom) = (x.saturating_add(width), y.saturating_add(height));
+    let mut masks = vec![];
+    for row in y / 4..(bottom - 1) / 4 + 1 {
+        for column in x / 2..(right - 1) / 2 + 1 {
+            let whole = column * 2 >= x && column * 2 + 2 <= right &&
+                        row * 4 >= y && row * 4 + 4 <= bottom;
+            let mask = if whole {
+                0xff
+            } else {
+                let mut mask = 0;
+                for (dy, bits) in PIXEL_MAP.iter().enumerate() {
+                    for (dx, &bit) in bits.iter().enumerate() {
+                        let (px, py) = (column * 2 + dx, row * 4 + dy);
+                        if px >= x && px < right && py >= y && py < bottom {
+                            mask |= bit;
+                        }
+                    }
+                }
+                mask
+            };
+            masks.push(((column, row), mask));
+        }
+    }
+    masks
+}
+
+/// The character drawn for each of the 256 arrangements of dots in a character, indexed by the
+/// mask of dots described by [`dot`].
+///
+/// ```
+/// use drawille::braille::{self, Canvas, GlyphTable};
+///
+/// // Show each character as a dot matrix character that is lit if any of its dots are set.
+/// let glyphs = GlyphTable::from_fn(|mask| if mask == 0 { '·' } else { '●' });
+/// let mut canvas = Canvas::new(4, 4).glyphs(glyphs);
+/// canvas.set(1, 2);
+/// assert_eq!(canvas.rows()[0], "●·");
+/// assert_eq!(GlyphTable::braille().glyph(braille::dot(0, 3)), '⡀');
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlyphTable {
+    glyphs: Box<[char; 256]>,
+}
+
+impl GlyphTable {
+    /// Creates a new `GlyphTable` from the character for each mask of dots.
+    pub fn new(glyphs: [char; 256]) -> GlyphTable {
+        GlyphTable { glyphs: Box::new(glyphs) }
+    }
+
+    /// Creates a new `GlyphTable` by calling `glyph` with every mask of dots.
+    pub fn from_fn<F: Fn(u8) -> char>(glyph: F) -> GlyphTable {
+        let mut glyphs = [' '; 256];
+        for (mask, c) in glyphs.iter_mut().enumerate() {
+            *c = glyph(mask as u8);
+        }
+        GlyphTable::new(glyphs)
+    }
+
+    /// Creates a new `GlyphTable` from a run of 256 characters starting at `base`, in the same
+    /// order as the Braille block, or `None` if the run contains a code point that isn’t a valid
+    /// character.
+    ///
+    /// The empty mask is still drawn as a space.
+    pub fn from_base(base: u32) -> Option<GlyphTable> {
+        let mut glyphs = [' '; 256];
+        for (mask, c) in glyphs.iter_mut().enumerate().skip(1) {
+            *c = char::from_u32(base.checked_add(mask as u32)?)?;
+        }
+        Some(GlyphTable::new(glyphs))
+    }
 
-static PIXEL_MAP: [[isize; 2]; 4] = [[0x01, 0x08],
-                                       [0x02, 0x10],
-                                       [0x04, 0x20],
-                                       [0x40, 0x80]];
+    /// Returns the table of Braille characters used by default.
+    pub fn braille() -> GlyphTable {
+        GlyphTable::from_base(0x2800).unwrap()
+    }
+
+    /// Returns the character drawn for a mask of dots.
+    pub fn glyph(&self, mask: u8) -> char {
+        self.glyphs[mask as usize]
+    }
+}
+
+impl Default for GlyphTable {
+    fn default() -> GlyphTable {
+        GlyphTable::braille()
+    }
+}
+
+/// The furthest from the origin the `try_` drawing methods draw, in pixels, in either direction.
+///
+/// Canvases grow to fit whatever is drawn on them, so a stray coordinate far beyond this would
+/// make every row drawn enormous.
+pub const MAX_COORDINATE: usize = geometry::LIMIT as usize;
+
+/// An error from one of the `try_` drawing methods, which check what they’re given rather than
+/// drawing it regardless.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawError {
+    /// A pixel further from the origin than `MAX_COORDINATE`.
+    OutOfRange { x: usize, y: usize },
+    /// A fractional coordinate that is negative, too far from the origin, or not a number.
+    InvalidCoordinate(f64),
+    /// A control character, such as a newline, in text, which would break up the rows drawn.
+    InvalidText(char),
+}
+
+impl fmt::Display for DrawError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DrawError::OutOfRange { x, y } => {
+                write!(f, "pixel ({}, {}) is beyond the largest coordinate, {}", x, y,
+                       MAX_COORDINATE)
+            },
+            DrawError::InvalidCoordinate(c) => write!(f, "invalid coordinate {}", c),
+            DrawError::InvalidText(c) => write!(f, "control character {:?} in text", c),
+        }
+    }
+}
+
+impl Error for DrawError {}
+
+/// How the lines of a grid are drawn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum LineStyle {
+    Solid,
+    /// Every other pixel.
+    #[default]
+    Dotted,
+    /// Four pixels on, two off.
+    Dashed,
+}
+
+impl LineStyle {
+    /// Detects whether the pixel `i` pixels along a line in this style is drawn.
+    pub fn draws(self, i: usize) -> bool {
+        match self {
+            LineStyle::Solid => true,
+            LineStyle::Dotted => i.is_multiple_of(2),
+            LineStyle::Dashed => i % 6 < 4,
+        }
+    }
+}
+
+/// The shape of pixels set around each point drawn with `Canvas::stamp` and `Canvas::line_brush`,
+/// or by a `Turtle`, as offsets from the point.
+///
+/// ```
+/// use drawille::braille::{Brush, Canvas};
+///
+/// let mut canvas = Canvas::new(8, 4);
+/// canvas.line_brush(1, 1, 6, 1, &Brush::square(2));
+/// assert_eq!(canvas.frame(), "⠰⠶⠶⠶");
+/// canvas.clear();
+/// canvas.stamp(2, 1, &Brush::from_rows(&[".#.", "###", ".#."]));
+/// assert_eq!(canvas.frame(), "⠐⠗  ");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Brush {
+    offsets: Vec<(isize, isize)>,
+}
+
+impl Brush {
+    /// Creates a new `Brush` setting the pixels at the given offsets from each point.
+    pub fn new(offsets: Vec<(isize, isize)>) -> Brush {
+        Brush { offsets }
+    }
+
+    /// Creates a new `Brush` from rows of a picture of it, where spaces and `.` are pixels left
+    /// alone and any other character is a pixel set, centred on the middle of the picture.
+    pub fn from_rows<S: AsRef<str>>(rows: &[S]) -> Brush {
+        let width = rows.iter().map(|row| row.as_ref().chars().count()).max().unwrap_or(0);
+        let (cx, cy) = ((width as isize - 1) / 2, (rows.len() as isize - 1) / 2);
+        let mut offsets = vec![];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.as_ref().chars().enumerate() {
+                if c != ' ' && c != '.' {
+                    offsets.push((x as isize - cx, y as isize - cy));
+                }
+            }
+        }
+        Brush::new(offsets)
+    }
+
+    /// Returns a `Brush` setting just the point itself, as `Canvas::set` does.
+    pub fn point() -> Brush {
+        Brush::new(vec![(0, 0)])
+    }
+
+    /// Returns a `Brush` setting a square `size` pixels across, with the point at its centre, or
+    /// just up and to the left of the centre for an even size.
+    pub fn square(size: usize) -> Brush {
+        let start = -(size as isize - 1) / 2;
+        let range = start..start + size as isize;
+        Brush::new(range.clone().flat_map(|y| range.clone().map(move |x| (x, y))).collect())
+    }
+
+    /// Returns a `Brush` setting a plus sign, with arms `arm` pixels long either side of the
+    /// point.
+    pub fn plus(arm: usize) -> Brush {
+        let arm = arm as isize;
+        let mut offsets = vec![(0, 0)];
+        for i in 1..arm + 1 {
+            offsets.extend_from_slice(&[(-i, 0), (i, 0), (0, -i), (0, i)]);
+        }
+        Brush::new(offsets)
+    }
+
+    /// Returns a `Brush` setting a disc of pixels within about `radius` pixels of the point.
+    pub fn circle(radius: usize) -> Brush {
+        let r = radius as isize;
+        Brush::new((-r..r + 1).flat_map(|y| {
+            (-r..r + 1).filter(move |&x| x * x + y * y <= r * r + r).map(move |x| (x, y))
+        }).collect())
+    }
+
+    /// Returns the offsets of the pixels the `Brush` sets from each point.
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+}
+
+impl Default for Brush {
+    fn default() -> Brush {
+        Brush::point()
+    }
+}
+
+/// A shape drawn around a point by `Canvas::draw_marker`, such as to tell the points of
+/// different series apart without colour.
+///
+/// Each shape but `Dot` is sized by how many pixels it reaches from the point, and other than
+/// `Plus` and `Cross` they’re drawn as outlines.
+///
+/// ```
+/// use drawille::braille::{Canvas, Marker};
+///
+/// let mut canvas = Canvas::new(6, 4);
+/// canvas.draw_marker(1, 1, Marker::Cross(1));
+/// canvas.draw_marker(4, 1, Marker::Square(1));
+/// assert_eq!(canvas.frame(), "⠕⠽⠽");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Marker {
+    Dot,
+    Plus(usize),
+    Cross(usize),
+    Circle(usize),
+    Square(usize),
+    Diamond(usize),
+}
+
+impl Marker {
+    /// Returns a `Brush` setting the pixels of the marker.
+    pub fn brush(self) -> Brush {
+        let (size, within): (usize, fn(isize, isize, isize) -> bool) = match self {
+            Marker::Dot => return Brush::point(),
+            Marker::Plus(size) => return Brush::plus(size),
+            Marker::Cross(size) => (size, |x, y, _| x.abs() == y.abs()),
+            Marker::Circle(size) => (size, |x, y, r| {
+                x * x + y * y <= r * r + r && x * x + y * y > r * r - r
+            }),
+            Marker::Square(size) => (size, |x, y, r| cmp::max(x.abs(), y.abs()) == r),
+            Marker::Diamond(size) => (size, |x, y, r| x.abs() + y.abs() == r),
+        };
+        let r = size as isize;
+        if r == 0 {
+            return Brush::point();
+        }
+        Brush::new((-r..r + 1).flat_map(|y| {
+            (-r..r + 1).filter(move |&x| within(x, y, r)).map(move |x| (x, y))
+        }).collect())
+    }
+}
+
+/// A character as it’s drawn on a canvas, with its colour if it has one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub char: char,
+    pub color: Option<Color>,
+}
+
+/// A character drawn differently on two canvases, as found by `Canvas::diff`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CellDiff {
+    pub column: usize,
+    pub row: usize,
+    pub left: Cell,
+    pub right: Cell,
+}
+
+/// Asserts that two `braille::Canvas`es draw the same characters in the same colours.
+///
+/// On failure, this panics with the two frames side by side, the differing characters marked,
+/// and a list of the differences.
+///
+/// ```should_panic
+/// #[macro_use]
+/// extern crate drawille;
+///
+/// use drawille::braille::Canvas;
+///
+/// fn main() {
+///     let (mut left, mut right) = (Canvas::new(4, 4), Canvas::new(4, 4));
+///     left.line(0, 0, 3, 3);
+///     right.line(0, 0, 3, 2);
+///     assert_frames_eq!(left, right);
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_frames_eq {
+    ($left:expr, $right:expr) => {
+        if let Some(report) = $left.diff_report(&$right) {
+            panic!("assertion failed: frames differ\n{}", report);
+        }
+    };
+}
 
 /// A canvas object that can be used to draw to the terminal using Braille characters.
+///
+/// Canvases compare equal when they’re the same size and have the same pixels, colours and text,
+/// however they were drawn: characters whose pixels have all been unset are forgotten, along with
+/// their colours.
+///
+/// ```
+/// use drawille::block::Color;
+/// use drawille::braille::Canvas;
+///
+/// let mut canvas = Canvas::new(4, 4);
+/// let blank = canvas.clone();
+/// canvas.set_colored(1, 1, Color::Red);
+/// canvas.toggle(1, 1);
+/// assert_eq!(canvas, blank);
+/// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Canvas {
-    chars: HashMap<(usize, usize), isize>,
+    chars: HashMap<(usize, usize), u8>,
+    text: HashMap<(usize, usize), char>,
+    colors: HashMap<(usize, usize), Color>,
+    glyphs: GlyphTable,
+    monochrome: bool,
     width:  usize,
     height: usize,
 }
 
 impl Canvas {
-    /// Creates a new `Canvas` with the given width and height.
+    /// Creates a new `Canvas` with the given width and height in pixels.
+    ///
+    /// The canvas is drawn with enough characters to cover the whole size, so a size that isn’t a
+    /// whole number of characters is rounded up. Note that the `Canvas` can still draw outside the
+    /// given dimensions (expanding the canvas) if a pixel is set outside the dimensions.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
     ///
-    /// Note that the `Canvas` can still draw outside the given dimensions (expanding the canvas)
-    /// if a pixel is set outside the dimensions.
+    /// let canvas = Canvas::new(3, 5);
+    /// assert_eq!((canvas.width(), canvas.height()), (3, 5));
+    /// assert_eq!((canvas.char_width(), canvas.char_height()), (2, 2));
+    /// assert_eq!(canvas.frame(), "  \n  ");
+    /// ```
     pub fn new(width: usize, height: usize) -> Canvas {
         Canvas {
             chars: HashMap::new(),
-            width: width / 2,
-            height: height / 4,
+            text: HashMap::new(),
+            colors: HashMap::new(),
+            glyphs: GlyphTable::braille(),
+            monochrome: console::no_color(),
+            width,
+            height,
         }
     }
 
+    /// Returns the width of the canvas in pixels, as given to `new` or `resize`.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas in pixels, as given to `new` or `resize`.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the width of the canvas in characters, rounded up to cover the whole width.
+    pub fn char_width(&self) -> usize {
+        self.width.div_ceil(2)
+    }
+
+    /// Returns the height of the canvas in characters, rounded up to cover the whole height.
+    pub fn char_height(&self) -> usize {
+        self.height.div_ceil(4)
+    }
+
+    /// Sets the characters drawn for each arrangement of dots, and returns the canvas for use
+    /// again.
+    pub fn glyphs(mut self, glyphs: GlyphTable) -> Canvas {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Sets whether the canvas is drawn without colours, and returns the canvas for use again.
+    ///
+    /// New canvases are drawn without colours if colour has been turned off with `NO_COLOR`.
+    ///
+    /// ```
+    /// use drawille::block::Color;
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(2, 4).monochrome(false);
+    /// canvas.set_colored(0, 0, Color::Red);
+    /// assert_eq!(canvas.frame(), "\x1b[31m⠁\x1b[0m");
+    /// assert_eq!(canvas.monochrome(true).frame(), "⠁");
+    /// ```
+    pub fn monochrome(mut self, monochrome: bool) -> Canvas {
+        self.monochrome = monochrome;
+        self
+    }
+
     /// Clears the canvas.
     pub fn clear(&mut self) {
         self.chars.clear();
+        self.text.clear();
+        self.colors.clear();
+    }
+
+    /// Changes the size of the canvas to `width` by `height` pixels, keeping what’s drawn inside
+    /// the new size and deleting everything beyond it.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(4, 4);
+    /// canvas.line(0, 0, 3, 3);
+    /// canvas.resize(3, 2);
+    /// assert_eq!(canvas.frame(), "⠑ ");
+    /// canvas.resize(6, 4);
+    /// assert_eq!(canvas.frame(), "⠑  ");
+    /// ```
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        let (columns, rows) = (self.char_width(), self.char_height());
+        let inside = |&(column, row): &(usize, usize)| column < columns && row < rows;
+        self.chars.retain(|cell, _| inside(cell));
+        self.text.retain(|cell, _| inside(cell));
+        // Characters cut through by the new edges keep only their dots inside.
+        for (&(column, row), mask) in self.chars.iter_mut() {
+            for (y, bits) in PIXEL_MAP.iter().enumerate() {
+                for (x, &bit) in bits.iter().enumerate() {
+                    if column * 2 + x >= width || row * 4 + y >= height {
+                        *mask &= !bit;
+                    }
+                }
+            }
+        }
+        self.chars.retain(|_, &mut mask| mask != 0);
+        let chars = &self.chars;
+        self.colors.retain(|cell, _| chars.contains_key(cell));
+    }
+
+    /// Writes text onto the canvas, starting at the character containing the pixel at the
+    /// specified coordinates.
+    ///
+    /// Each character of the text takes up a whole Braille character, and is shown in place of any
+    /// pixels there.
+    pub fn text<S: AsRef<str>>(&mut self, x: usize, y: usize, s: S) {
+        let (row, col) = (x / 2, y / 4);
+        for (i, c) in s.as_ref().chars().enumerate() {
+            self.text.insert((row + i, col), c);
+        }
     }
 
     /// Sets a pixel at the specified coordinates.
     pub fn set(&mut self, x: usize, y: usize) {
         let (row, col) = (x / 2, y / 4);
-        match self.chars.entry((row, col)) {
-            Entry::Occupied(_) => {},
-            Entry::Vacant(e) => { e.insert(0); },
-        }
-        self.chars.get_mut(&(row, col)).map(|a| *a |= PIXEL_MAP[y % 4][x % 2]);
+        *self.chars.entry((row, col)).or_insert(0) |= PIXEL_MAP[y % 4][x % 2];
+    }
+
+    /// Sets a pixel at the specified coordinates, and colours the character containing it.
+    ///
+    /// Since a colour applies to a whole character, this also changes the colour of any other pixels
+    /// already set in the same character.
+    pub fn set_colored(&mut self, x: usize, y: usize, color: Color) {
+        self.set(x, y);
+        self.colors.insert((x / 2, y / 4), color);
     }
 
     /// Deletes a pixel at the specified coordinates.
     pub fn unset(&mut self, x: usize, y: usize) {
         let (row, col) = (x / 2, y / 4);
-        match self.chars.entry((row, col)) {
-            Entry::Occupied(_) => {},
-            Entry::Vacant(e) => { e.insert(0); },
+        if let Entry::Occupied(mut e) = self.chars.entry((row, col)) {
+            *e.get_mut() &= !PIXEL_MAP[y % 4][x % 2];
+            if *e.get() == 0 {
+                e.remove();
+                self.colors.remove(&(row, col));
+            }
         }
-        self.chars.get_mut(&(row, col)).map(|a| *a &= !PIXEL_MAP[y % 4][x % 2]);
     }
 
     /// Toggles a pixel at the specified coordinates.
     pub fn toggle(&mut self, x: usize, y: usize) {
         let (row, col) = (x / 2, y / 4);
-        match self.chars.entry((row, col)) {
-            Entry::Occupied(_) => {},
-            Entry::Vacant(e) => { e.insert(0); },
+        let char = self.chars.entry((row, col)).or_insert(0);
+        *char ^= PIXEL_MAP[y % 4][x % 2];
+        if *char == 0 {
+            self.chars.remove(&(row, col));
+            self.colors.remove(&(row, col));
+        }
+    }
+
+    /// Sets a pixel at the specified coordinates, or returns an error without drawing anything if
+    /// it’s further from the origin than `MAX_COORDINATE`.
+    ///
+    /// ```
+    /// use drawille::braille::{Canvas, DrawError, MAX_COORDINATE};
+    ///
+    /// let mut canvas = Canvas::new(10, 10);
+    /// assert_eq!(canvas.try_set(3, 4), Ok(()));
+    /// assert_eq!(canvas.try_set(MAX_COORDINATE + 1, 0),
+    ///            Err(DrawError::OutOfRange { x: MAX_COORDINATE + 1, y: 0 }));
+    /// ```
+    pub fn try_set(&mut self, x: usize, y: usize) -> Result<(), DrawError> {
+        geometry::check_pixel(x, y)?;
+        self.set(x, y);
+        Ok(())
+    }
+
+    /// Sets and colours a pixel just like `set_colored`, or returns an error just like `try_set`.
+    pub fn try_set_colored(&mut self, x: usize, y: usize, color: Color) -> Result<(), DrawError> {
+        geometry::check_pixel(x, y)?;
+        self.set_colored(x, y, color);
+        Ok(())
+    }
+
+    /// Deletes a pixel just like `unset`, or returns an error just like `try_set`.
+    pub fn try_unset(&mut self, x: usize, y: usize) -> Result<(), DrawError> {
+        geometry::check_pixel(x, y)?;
+        self.unset(x, y);
+        Ok(())
+    }
+
+    /// Toggles a pixel just like `toggle`, or returns an error just like `try_set`.
+    pub fn try_toggle(&mut self, x: usize, y: usize) -> Result<(), DrawError> {
+        geometry::check_pixel(x, y)?;
+        self.toggle(x, y);
+        Ok(())
+    }
+
+    /// Sets the pixel nearest to fractional coordinates, or returns an error without drawing
+    /// anything if either coordinate is negative, beyond `MAX_COORDINATE`, or not a number.
+    pub fn try_point(&mut self, x: f64, y: f64) -> Result<(), DrawError> {
+        let (x, y) = (geometry::checked_pixel(x)?, geometry::checked_pixel(y)?);
+        self.set(x, y);
+        Ok(())
+    }
+
+    /// Writes text just like `text`, or returns an error without writing anything if any of it
+    /// would go beyond `MAX_COORDINATE` or it has a control character.
+    pub fn try_text<S: AsRef<str>>(&mut self, x: usize, y: usize, s: S) -> Result<(), DrawError> {
+        geometry::check_text(x, y, 2, s.as_ref())?;
+        self.text(x, y, s);
+        Ok(())
+    }
+
+    /// Sets every pixel in the rectangle `width` by `height` pixels with its top left corner at
+    /// `(x, y)`.
+    ///
+    /// Characters wholly inside the rectangle are filled all at once, rather than a pixel at a
+    /// time.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(6, 4);
+    /// canvas.fill_region(1, 0, 4, 4);
+    /// assert_eq!(canvas.frame(), "⢸⣿⡇");
+    /// canvas.clear_region(2, 1, 2, 2);
+    /// assert_eq!(canvas.frame(), "⢸⣉⡇");
+    /// ```
+    pub fn fill_region(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for ((column, row), mask) in region_masks(x, y, width, height) {
+            *self.chars.entry((column, row)).or_insert(0) |= mask;
+        }
+    }
+
+    /// Deletes every pixel in the rectangle `width` by `height` pixels with its top left corner at
+    /// `(x, y)`, along with text in any character the rectangle touches.
+    ///
+    /// Characters wholly inside the rectangle are cleared all at once, rather than a pixel at a
+    /// time.
+    pub fn clear_region(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for ((column, row), mask) in region_masks(x, y, width, height) {
+            self.text.remove(&(column, row));
+            if let Entry::Occupied(mut e) = self.chars.entry((column, row)) {
+                *e.get_mut() &= !mask;
+                if *e.get() == 0 {
+                    e.remove();
+                    self.colors.remove(&(column, row));
+                }
+            }
+        }
+    }
+
+    /// Draws another `Canvas` onto this one with its top left corner at the given column and row of
+    /// characters, in place of the pixels, colours and text in every character it covers.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(8, 4);
+    /// canvas.fill_region(0, 0, 8, 4);
+    /// let mut pane = Canvas::new(4, 4);
+    /// pane.text(0, 0, "ok");
+    /// canvas.paste(&pane, 1, 0);
+    /// assert_eq!(canvas.frame(), "⣿ok⣿");
+    /// ```
+    pub fn paste(&mut self, other: &Canvas, column: usize, row: usize) {
+        let (columns, rows) = other.extent();
+        for y in 0..rows {
+            for x in 0..columns {
+                let at = (column + x, row + y);
+                self.chars.remove(&at);
+                self.text.remove(&at);
+                self.colors.remove(&at);
+                let mask = other.get_cell(x, y);
+                if mask != 0 {
+                    self.chars.insert(at, mask);
+                    if let Some(&color) = other.colors.get(&(x, y)) {
+                        self.colors.insert(at, color);
+                    }
+                }
+                if let Some(&c) = other.text.get(&(x, y)) {
+                    self.text.insert(at, c);
+                }
+            }
         }
-        self.chars.get_mut(&(row, col)).map(|a| *a ^= PIXEL_MAP[y % 4][x % 2]);
     }
 
     /// Detects whether the pixel at the given coordinates is set.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(10, 10);
+    /// canvas.set(4, 1);
+    /// assert!(canvas.get(4, 1));
+    /// assert!(!canvas.get(1, 4));
+    /// ```
     pub fn get(&self, x: usize, y: usize) -> bool {
         let dot_index = PIXEL_MAP[y % 4][x % 2];
-        let (col, row) = (x / 2, y / 4);
+        let (row, col) = (x / 2, y / 4);
         let char = self.chars.get(&(row, col));
 
         match char {
@@ -82,65 +736,679 @@ impl Canvas {
         }
     }
 
+    /// Sets or deletes the pixel at the specified coordinates, so that it matches `value`.
+    pub fn set_px(&mut self, x: usize, y: usize, value: bool) {
+        if value {
+            self.set(x, y);
+        } else {
+            self.unset(x, y);
+        }
+    }
+
+    /// Returns the mask of dots set in the character at the given column and row, with the bits
+    /// described by [`dot`].
+    ///
+    /// ```
+    /// use drawille::braille::{self, Canvas};
+    ///
+    /// let mut canvas = Canvas::new(4, 4);
+    /// canvas.set(2, 0);
+    /// canvas.set(3, 3);
+    /// assert_eq!(canvas.get_cell(1, 0), braille::dot(0, 0) | braille::dot(1, 3));
+    /// canvas.set_cell(0, 0, 0xff);
+    /// assert_eq!(canvas.frame(), "⣿⢁");
+    /// ```
+    pub fn get_cell(&self, column: usize, row: usize) -> u8 {
+        *self.chars.get(&(column, row)).unwrap_or(&0)
+    }
+
+    /// Sets the dots in the character at the given column and row to exactly those in `mask`,
+    /// replacing whatever pixels were there.
+    ///
+    /// Setting a mask of zero forgets the character along with its colour, just as unsetting its
+    /// last pixel does.
+    pub fn set_cell(&mut self, column: usize, row: usize, mask: u8) {
+        if mask == 0 {
+            self.chars.remove(&(column, row));
+            self.colors.remove(&(column, row));
+        } else {
+            self.chars.insert((column, row), mask);
+        }
+    }
+
+    /// Returns how many of the eight pixels around each pixel are set, for every pixel next to at
+    /// least one that is.
+    ///
+    /// Only the pixels set are visited, rather than every pixel of the canvas, which makes this
+    /// much quicker than calling `get` around each pixel for something like a cellular automaton.
+    /// Pixels beyond the top or left of the canvas aren’t counted.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(10, 10);
+    /// canvas.line(1, 2, 3, 2);
+    /// let counts = canvas.neighbor_counts();
+    /// assert_eq!(counts[&(2, 1)], 3);
+    /// assert_eq!(counts[&(2, 2)], 2);
+    /// assert_eq!(counts[&(0, 3)], 1);
+    /// assert!(!counts.contains_key(&(5, 2)));
+    /// ```
+    pub fn neighbor_counts(&self) -> HashMap<(usize, usize), u8> {
+        let mut counts = HashMap::new();
+        for (x, y) in self.pixels() {
+            for ny in y.saturating_sub(1)..y + 2 {
+                for nx in x.saturating_sub(1)..x + 2 {
+                    if (nx, ny) != (x, y) {
+                        *counts.entry((nx, ny)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    /// Replaces the pixels of the canvas with the next generation of a cellular automaton, where
+    /// `rule` is given whether each pixel is set and how many of the eight around it are, and
+    /// returns whether it’s set in the next generation.
+    ///
+    /// Only pixels that are set or next to one that is are visited, so `rule` should leave a blank
+    /// pixel with no neighbours blank. Text is left alone, and colours stay with the characters
+    /// that still have pixels set.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// // Conway’s Game of Life, with a blinker turning from across to down.
+    /// let life = |alive: bool, neighbors: u8| neighbors == 3 || alive && neighbors == 2;
+    /// let mut canvas = Canvas::new(4, 4);
+    /// canvas.line(0, 1, 2, 1);
+    /// canvas.step_ca(life);
+    /// assert_eq!(canvas.frame(), "⠸ ");
+    /// canvas.step_ca(life);
+    /// assert_eq!(canvas.frame(), "⠒⠂");
+    /// ```
+    pub fn step_ca<F: Fn(bool, u8) -> bool>(&mut self, rule: F) {
+        let mut counts = self.neighbor_counts();
+        for pixel in self.pixels() {
+            counts.entry(pixel).or_insert(0);
+        }
+        let mut chars = HashMap::new();
+        for ((x, y), count) in counts {
+            if rule(self.get(x, y), count) {
+                *chars.entry((x / 2, y / 4)).or_insert(0) |= PIXEL_MAP[y % 4][x % 2];
+            }
+        }
+        self.colors.retain(|cell, _| chars.contains_key(cell));
+        self.chars = chars;
+    }
+
+    /// Returns the coordinates of every pixel set on the canvas, in no particular order.
+    fn pixels(&self) -> Vec<(usize, usize)> {
+        let mut pixels = vec![];
+        for (&(column, row), &mask) in self.chars.iter() {
+            for (y, bits) in PIXEL_MAP.iter().enumerate() {
+                for (x, &bit) in bits.iter().enumerate() {
+                    if mask & bit != 0 {
+                        pixels.push((column * 2 + x, row * 4 + y));
+                    }
+                }
+            }
+        }
+        pixels
+    }
+
+    /// Returns every pixel of the area drawn by `rows`, a row at a time from the top, with `true`
+    /// for each pixel that is set.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(2, 4);
+    /// canvas.line(0, 0, 1, 1);
+    /// let matrix = canvas.to_bitmatrix();
+    /// assert_eq!(matrix, vec![vec![true, false], vec![false, true],
+    ///                         vec![false, false], vec![false, false]]);
+    /// assert_eq!(Canvas::from_bitmatrix(&matrix), canvas);
+    /// ```
+    pub fn to_bitmatrix(&self) -> Vec<Vec<bool>> {
+        let (width, height) = self.pixel_size();
+        (0..height).map(|y| (0..width).map(|x| self.get(x, y)).collect()).collect()
+    }
+
+    /// Returns the canvas as an array of whether each pixel is set, indexed by row and then column,
+    /// just as `to_bitmatrix` lays it out.
+    ///
+    /// ```
+    /// # extern crate drawille;
+    /// # extern crate ndarray;
+    /// use drawille::braille::Canvas;
+    ///
+    /// # fn main() {
+    /// let mut canvas = Canvas::new(2, 4);
+    /// canvas.set(1, 2);
+    /// let array = canvas.to_array2();
+    /// assert_eq!(array.dim(), (4, 2));
+    /// assert!(array[[2, 1]]);
+    /// assert_eq!(Canvas::from(&array), canvas);
+    /// assert_eq!(Canvas::from(&array.view()), canvas);
+    /// # }
+    /// ```
+    #[cfg(feature = "ndarray")]
+    pub fn to_array2(&self) -> ::ndarray::Array2<bool> {
+        let (width, height) = self.pixel_size();
+        ::ndarray::Array2::from_shape_fn((height, width), |(y, x)| self.get(x, y))
+    }
+
+    /// Creates a new `Canvas` as wide as the longest row of `matrix` and as high as it has rows,
+    /// with the pixels set that are `true` in `matrix`, given a row at a time from the top.
+    pub fn from_bitmatrix<R: AsRef<[bool]>>(matrix: &[R]) -> Canvas {
+        let width = matrix.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+        let mut canvas = Canvas::new(width, matrix.len());
+        for (y, row) in matrix.iter().enumerate() {
+            for (x, &set) in row.as_ref().iter().enumerate() {
+                if set {
+                    canvas.set(x, y);
+                }
+            }
+        }
+        canvas
+    }
+
+    /// Returns how many pixels are set on the canvas.
+    pub fn pixel_count(&self) -> usize {
+        self.chars.values().map(|mask| mask.count_ones() as usize).sum()
+    }
+
+    /// Returns the smallest rectangle containing every pixel set on the canvas, as the
+    /// coordinates of its top left and bottom right pixels, or `None` if nothing is set.
+    ///
+    /// Text doesn’t count, since it has no pixels.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(20, 20);
+    /// assert_eq!(canvas.bounding_box(), None);
+    /// canvas.line(3, 9, 6, 5);
+    /// assert_eq!(canvas.bounding_box(), Some((3, 5, 6, 9)));
+    /// assert_eq!(canvas.pixel_count(), 5);
+    /// ```
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.chars.iter().filter(|&(_, &mask)| mask != 0).map(|(&(column, row), &mask)| {
+            let (mut left, mut top, mut right, mut bottom) = (1, 3, 0, 0);
+            for (y, bits) in PIXEL_MAP.iter().enumerate() {
+                for (x, &bit) in bits.iter().enumerate() {
+                    if mask & bit != 0 {
+                        left = cmp::min(left, x);
+                        right = cmp::max(right, x);
+                        top = cmp::min(top, y);
+                        bottom = cmp::max(bottom, y);
+                    }
+                }
+            }
+            let (x, y) = (column * 2, row * 4);
+            (x + left, y + top, x + right, y + bottom)
+        }).fold(None, |bounds, (x0, y0, x1, y1)| match bounds {
+            None => Some((x0, y0, x1, y1)),
+            Some((a0, b0, a1, b1)) => {
+                Some((cmp::min(a0, x0), cmp::min(b0, y0), cmp::max(a1, x1), cmp::max(b1, y1)))
+            },
+        })
+    }
+
+    /// Detects whether any pixel set on `other`, moved `dx` pixels right and `dy` pixels down, is
+    /// also set on this canvas.
+    ///
+    /// When the offset moves whole characters, the characters’ masks of dots are compared
+    /// directly.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut wall = Canvas::new(20, 20);
+    /// wall.line(10, 0, 10, 19);
+    /// let mut ball = Canvas::new(4, 4);
+    /// ball.set(1, 1);
+    /// assert!(!wall.collides_with(&ball, 8, 8));
+    /// assert!(wall.collides_with(&ball, 9, 8));
+    /// ```
+    pub fn collides_with(&self, other: &Canvas, dx: isize, dy: isize) -> bool {
+        if dx % 2 == 0 && dy % 4 == 0 {
+            return other.chars.iter().any(|(&(row, col), &mask)| {
+                let (row, col) = (row as isize + dx / 2, col as isize + dy / 4);
+                row >= 0 && col >= 0 && self.chars.get(&(row as usize, col as usize))
+                                                  .is_some_and(|&c| c & mask != 0)
+            });
+        }
+        other.chars.iter().any(|(&(row, col), &mask)| {
+            PIXEL_MAP.iter().enumerate().any(|(y, bits)| {
+                bits.iter().enumerate().any(|(x, &bit)| {
+                    let x = (row * 2 + x) as isize + dx;
+                    let y = (col * 4 + y) as isize + dy;
+                    mask & bit != 0 && x >= 0 && y >= 0 && self.get(x as usize, y as usize)
+                })
+            })
+        })
+    }
+
+    /// Returns the characters drawn differently on this canvas and `other`, a row at a time from
+    /// the top left.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let (mut left, mut right) = (Canvas::new(4, 4), Canvas::new(4, 4));
+    /// left.set(0, 0);
+    /// right.set(0, 1);
+    /// right.text(2, 0, "!");
+    /// let diffs = left.diff(&right);
+    /// assert_eq!(diffs.len(), 2);
+    /// assert_eq!((diffs[0].left.char, diffs[0].right.char), ('⠁', '⠂'));
+    /// assert_eq!((diffs[1].column, diffs[1].left.char, diffs[1].right.char), (1, ' ', '!'));
+    /// ```
+    pub fn diff(&self, other: &Canvas) -> Vec<CellDiff> {
+        let (columns, rows) = self.extent();
+        let (other_columns, other_rows) = other.extent();
+        let mut diffs = vec![];
+        for row in 0..cmp::max(rows, other_rows) {
+            for column in 0..cmp::max(columns, other_columns) {
+                let (left, right) = (self.cell(column, row), other.cell(column, row));
+                if left != right {
+                    diffs.push(CellDiff { column, row, left, right });
+                }
+            }
+        }
+        diffs
+    }
+
+    /// Describes how this canvas and `other` differ for a test failure, with the two frames side
+    /// by side, or returns `None` if they’re drawn the same.
+    pub fn diff_report(&self, other: &Canvas) -> Option<String> {
+        let diffs = self.diff(other);
+        if diffs.is_empty() {
+            return None;
+        }
+        let (columns, rows) = self.extent();
+        let (other_columns, other_rows) = other.extent();
+        let columns = cmp::max(columns, other_columns);
+        let line = |canvas: &Canvas, row| (0..columns).map(|column| canvas.cell(column, row).char)
+                                                      .collect::<String>();
+        let differing = diffs.iter().map(|d| (d.column, d.row)).collect::<HashSet<_>>();
+        let mut report = format!("{} characters differ (left │ right):\n", diffs.len());
+        for row in 0..cmp::max(rows, other_rows) {
+            let marks = (0..columns).map(|column| {
+                if differing.contains(&(column, row)) { '^' } else { ' ' }
+            }).collect::<String>();
+            let changed = marks.contains('^');
+            report.push_str(&format!("{} {} │ {}\n", if changed { '>' } else { ' ' },
+                                     line(self, row), line(other, row)));
+            if changed {
+                report.push_str(&format!("  {} │ {}\n", marks, marks));
+            }
+        }
+        let describe = |cell: Cell| match cell.color {
+            Some(color) => format!("{:?} in {:?}", cell.char, color),
+            None => format!("{:?}", cell.char),
+        };
+        for d in diffs.iter() {
+            report.push_str(&format!("column {}, row {}: {} != {}\n", d.column, d.row,
+                                     describe(d.left), describe(d.right)));
+        }
+        Some(report)
+    }
+
+    /// Returns a hash of the characters drawn on the canvas and their colours.
+    ///
+    /// The hash is the same for canvases that draw the same characters, however they were drawn,
+    /// and doesn’t change between runs or versions of Rust, so it can be stored as a cache key or
+    /// to check a frame against later. The size given to `new` doesn’t count, since it only adds
+    /// blank characters.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let (mut a, mut b) = (Canvas::new(10, 10), Canvas::new(20, 20));
+    /// a.line(0, 0, 9, 9);
+    /// b.set(15, 15);
+    /// b.unset(15, 15);
+    /// b.line(9, 9, 0, 0);
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut cells = self.chars.keys().chain(self.text.keys()).cloned().collect::<Vec<_>>();
+        cells.sort_by_key(|&(column, row)| (row, column));
+        cells.dedup();
+        // 64-bit FNV-1a, which unlike the standard library’s hasher is fixed.
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        let mut feed = |value: u64| {
+            for byte in value.to_le_bytes().iter() {
+                hash = (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for (column, row) in cells {
+            if !self.text.contains_key(&(column, row)) && self.chars[&(column, row)] == 0 {
+                continue;
+            }
+            let cell = self.cell(column, row);
+            feed(column as u64);
+            feed(row as u64);
+            feed(cell.char as u64);
+            feed(cell.color.map_or(0, |color| color as u64 + 1));
+        }
+        hash
+    }
+
+    /// Returns how many columns and rows of characters `rows` draws: enough for the size given
+    /// to `new`, and for everything drawn.
+    pub fn extent(&self) -> (usize, usize) {
+        let keys = || self.chars.keys().chain(self.text.keys());
+        let columns = keys().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let rows = keys().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        (cmp::max(self.char_width(), columns), cmp::max(self.char_height(), rows))
+    }
+
+    /// Returns the character drawn at the given column and row, with its colour.
+    pub fn cell(&self, column: usize, row: usize) -> Cell {
+        if let Some(&c) = self.text.get(&(column, row)) {
+            return Cell { char: c, color: None };
+        }
+        let mask = *self.chars.get(&(column, row)).unwrap_or(&0);
+        let color = if mask == 0 { None } else { self.colors.get(&(column, row)).cloned() };
+        Cell { char: self.glyphs.glyph(mask), color }
+    }
+
     /// Returns a `Vec` of each row of the `Canvas`.
     ///
     /// Note that each row is actually four pixels high due to the fact that a single Braille
-    /// character spans two by four pixels.
+    /// character spans two by four pixels. Characters coloured with `set_colored` are wrapped in
+    /// ANSI escape codes, unless the canvas is monochrome.
     pub fn rows(&self) -> Vec<String> {
-        let maxrow = cmp::max(self.width, self.chars.keys().map(|&(x, _)| x).max().unwrap_or(0));
-        let maxcol = cmp::max(self.height, self.chars.keys().map(|&(_, y)| y).max().unwrap_or(0));
-
-        let mut result = vec![];
-        for y in (0..maxcol + 1) {
-            let mut row = String::new();
-            for x in (0..maxrow + 1) {
-                let char = *self.chars.get(&(x, y)).unwrap_or(&0);
-                row.push(if char == 0 {
-                    ' '
-                } else {
-                    char::from_u32((0x2800 + char) as u32).unwrap()
-                })
+        let (columns, rows) = self.extent();
+        self.rows_within(0..columns, 0..rows)
+    }
+
+    /// Returns the rows of characters in the given ranges of rows and columns, just as `rows`
+    /// draws them.
+    fn rows_within(&self, columns: Range<usize>, rows: Range<usize>) -> Vec<String> {
+        let frame = self.frame_within(columns, rows);
+        if self.monochrome { frame.plain_rows() } else { frame.ansi_rows() }
+    }
+
+    /// Returns the characters drawn on the canvas, with their colours, as a `Frame` of the same
+    /// characters that `rows` draws.
+    pub fn to_frame(&self) -> Frame {
+        let (columns, rows) = self.extent();
+        self.frame_within(0..columns, 0..rows)
+    }
+
+    /// Returns the characters in the given ranges of rows and columns as a `Frame`.
+    fn frame_within(&self, columns: Range<usize>, rows: Range<usize>) -> Frame {
+        let mut frame = Frame::new(columns.len(), rows.len());
+        for (y, row) in rows.enumerate() {
+            for (x, column) in columns.clone().enumerate() {
+                let cell = self.cell(column, row);
+                frame[(x, y)] = frame::Cell { fg: cell.color, ..frame::Cell::new(cell.char) };
             }
-            result.push(row);
         }
-        result
+        frame
     }
 
     /// Draws the canvas to a `String` and returns it.
     pub fn frame(&self) -> String {
-        self.rows().into_iter().collect::<Vec<String>>().connect("\n")
+        self.rows().join("\n")
+    }
+
+    /// Draws only the characters with something drawn in them to a `String` and returns it, with
+    /// `margin` blank characters around them where there’s room.
+    ///
+    /// Unlike `frame`, this doesn’t start from the top left of the canvas, so a drawing far from
+    /// the origin isn’t drawn with a large blank area before it. Nothing is drawn if the canvas is
+    /// blank.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(100, 100);
+    /// canvas.line(40, 40, 43, 40);
+    /// canvas.text(40, 44, "hi");
+    /// assert_eq!(canvas.frame_cropped(0), "⠉⠉\nhi");
+    /// assert_eq!(canvas.frame_cropped(1), "    \n ⠉⠉ \n hi \n    ");
+    /// ```
+    pub fn frame_cropped(&self, margin: usize) -> String {
+        let drawn = self.chars.iter().filter(|&(_, &mask)| mask != 0).map(|(&cell, _)| cell)
+                                     .chain(self.text.keys().cloned()).collect::<Vec<_>>();
+        if drawn.is_empty() {
+            return String::new();
+        }
+        let left = drawn.iter().map(|&(x, _)| x).min().unwrap().saturating_sub(margin);
+        let top = drawn.iter().map(|&(_, y)| y).min().unwrap().saturating_sub(margin);
+        let right = drawn.iter().map(|&(x, _)| x).max().unwrap() + margin + 1;
+        let bottom = drawn.iter().map(|&(_, y)| y).max().unwrap() + margin + 1;
+        self.rows_within(left..right, top..bottom).join("\n")
     }
 
-    fn line_vec(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> Vec<(usize, usize)> {
-        let xdiff = cmp::max(x1, x2) - cmp::min(x1, x2);
-        let ydiff = cmp::max(y1, y2) - cmp::min(y1, y2);
-        let xdir = if x1 <= x2 { 1 } else { -1 };
-        let ydir = if y1 <= y2 { 1 } else { -1 };
+    /// Draws the canvas as a sixel image and returns the escape sequence, with each pixel drawn
+    /// `scale` image pixels across.
+    ///
+    /// The image covers the same area as `rows`. Set pixels are drawn in the colour of their
+    /// character, or white if it has none, and everything else is left transparent; text isn’t
+    /// drawn.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(2, 4);
+    /// canvas.set(0, 0);
+    /// canvas.set(1, 1);
+    /// assert!(canvas.sixel(1).ends_with("#7@A-\x1b\\"));
+    /// ```
+    #[cfg(feature = "sixel")]
+    pub fn sixel(&self, scale: usize) -> String {
+        let (width, height) = self.pixel_size();
+        ::sixel::encode(width, height, scale, |x, y| self.pixel_color(x, y))
+    }
 
-        let r = cmp::max(xdiff, ydiff);
+    /// Draws the canvas as a PNG image and returns the kitty graphics protocol escape sequences
+    /// that show it at the cursor, with each pixel drawn `scale` image pixels across.
+    ///
+    /// The image is drawn just like `sixel`. Kitty, Ghostty and WezTerm all understand the
+    /// protocol.
+    pub fn kitty(&self, scale: usize) -> String {
+        let (width, height) = self.pixel_size();
+        image::kitty(&image::png(width, height, scale, |x, y| self.pixel_color(x, y)))
+    }
 
-        let mut result = vec![];
-        for i in (0..r + 1) {
-            let mut x = x1 as isize;
-            let mut y = y1 as isize;
+    /// Draws the canvas as a PNG image and returns the iTerm2 inline image escape sequence that
+    /// shows it at the cursor, with each pixel drawn `scale` image pixels across.
+    ///
+    /// The image is drawn just like `sixel`.
+    pub fn iterm2(&self, scale: usize) -> String {
+        let (width, height) = self.pixel_size();
+        image::iterm2(&image::png(width, height, scale, |x, y| self.pixel_color(x, y)))
+    }
 
-            if ydiff != 0 {
-                y += ((i * ydiff) / r) as isize * ydir;
+    /// Returns the size in pixels of the area drawn by `rows`.
+    fn pixel_size(&self) -> (usize, usize) {
+        let (columns, rows) = self.extent();
+        (columns * 2, rows * 4)
+    }
+
+    /// Returns the colour a pixel is drawn in as an image, or `None` if it isn’t set.
+    fn pixel_color(&self, x: usize, y: usize) -> Option<Color> {
+        if self.get(x, y) {
+            Some(*self.colors.get(&(x / 2, y / 4)).unwrap_or(&Color::White))
+        } else {
+            None
+        }
+    }
+
+    /// Draws a line just like `line`, or returns an error without drawing anything if either end
+    /// is further from the origin than `MAX_COORDINATE`.
+    pub fn try_line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize)
+                    -> Result<(), DrawError> {
+        geometry::check_pixel(x1, y1)?;
+        geometry::check_pixel(x2, y2)?;
+        self.line(x1, y1, x2, y2);
+        Ok(())
+    }
+
+    /// Draws reference lines across the size of the canvas every `spacing_x` pixels across and
+    /// every `spacing_y` pixels down, starting from the top left corner.
+    ///
+    /// A spacing of zero leaves out the lines in that direction.
+    ///
+    /// ```
+    /// use drawille::braille::{Canvas, LineStyle};
+    ///
+    /// let mut canvas = Canvas::new(8, 8);
+    /// canvas.draw_grid(4, 4, LineStyle::Dotted);
+    /// assert_eq!(canvas.frame(), "⠅⠁⠅⠁\n⠅⠁⠅⠁");
+    /// ```
+    pub fn draw_grid(&mut self, spacing_x: usize, spacing_y: usize, style: LineStyle) {
+        let (width, height) = (self.width, self.height);
+        if spacing_x > 0 {
+            for x in (0..width).step_by(spacing_x) {
+                for y in (0..height).filter(|&y| style.draws(y)) {
+                    self.set(x, y);
+                }
             }
-            if xdiff != 0 {
-                x += ((i * xdiff) / r) as isize * xdir;
+        }
+        if spacing_y > 0 {
+            for y in (0..height).step_by(spacing_y) {
+                for x in (0..width).filter(|&x| style.draws(x)) {
+                    self.set(x, y);
+                }
             }
+        }
+    }
 
-            result.push((x as usize, y as usize));
+    /// Draws axes across the size of the canvas crossing at `origin`, with ticks every `ticks.0`
+    /// pixels along the x axis and `ticks.1` pixels along the y axis, counting from the origin.
+    ///
+    /// A tick spacing of zero leaves out the ticks on that axis.
+    pub fn draw_axes(&mut self, origin: (usize, usize), ticks: (usize, usize)) {
+        let (width, height) = (self.width, self.height);
+        let (ox, oy) = origin;
+        for x in 0..width {
+            self.set(x, oy);
+        }
+        for y in 0..height {
+            self.set(ox, y);
+        }
+        // Ticks stick out a pixel either side of the axis, where there’s room.
+        let is_tick = |a: usize, b: usize, spacing: usize| {
+            spacing > 0 && a != b && (cmp::max(a, b) - cmp::min(a, b)).is_multiple_of(spacing)
+        };
+        for x in (0..width).filter(|&x| is_tick(x, ox, ticks.0)) {
+            self.set(x, oy + 1);
+            if oy > 0 {
+                self.set(x, oy - 1);
+            }
+        }
+        for y in (0..height).filter(|&y| is_tick(y, oy, ticks.1)) {
+            self.set(ox + 1, y);
+            if ox > 0 {
+                self.set(ox - 1, y);
+            }
+        }
+    }
+
+    /// Draws the part of a line from `from` to `to` that lies within the size of the canvas.
+    ///
+    /// The line is clipped to the canvas before it’s drawn, so however far outside the canvas its
+    /// ends are, no time is spent on pixels outside it and the canvas doesn’t grow.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(4, 4);
+    /// canvas.line_clipped((-1_000_000, 2), (1_000_000, 2));
+    /// assert_eq!(canvas.frame(), "⠤⠤");
+    /// ```
+    pub fn line_clipped(&mut self, from: (isize, isize), to: (isize, isize)) {
+        if self.width == 0 || self.height == 0 {
+            return;
         }
-        result
+        let rect = (0.0, 0.0, (self.width - 1) as f64, (self.height - 1) as f64);
+        let (from, to) = ((from.0 as f64, from.1 as f64), (to.0 as f64, to.1 as f64));
+        geometry::segment(from, to, rect, |x, y| self.set(x, y));
     }
 
     /// Draws a line from `(x1, y1)` to `(x2, y2)` onto the `Canvas`.
     pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
-        for &(x, y) in self.line_vec(x1, y1, x2, y2).iter() {
-            self.set(x, y);
+        for_line_pixels(x1, y1, x2, y2, |x, y| self.set(x, y));
+    }
+
+    /// Sets the pixels of `brush` around the point at the specified coordinates.
+    ///
+    /// Pixels that would fall at negative coordinates aren’t drawn.
+    pub fn stamp(&mut self, x: usize, y: usize, brush: &Brush) {
+        for &(dx, dy) in brush.offsets() {
+            let (x, y) = (x as isize + dx, y as isize + dy);
+            if x >= 0 && y >= 0 {
+                self.set(x as usize, y as usize);
+            }
+        }
+    }
+
+    /// Draws `marker` centred on the pixel at the specified coordinates.
+    ///
+    /// Pixels of the marker that would fall at negative coordinates aren’t drawn.
+    pub fn draw_marker(&mut self, x: usize, y: usize, marker: Marker) {
+        self.stamp(x, y, &marker.brush());
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)`, stamping `brush` at each of its pixels, for
+    /// lines thicker than one pixel.
+    pub fn line_brush(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, brush: &Brush) {
+        for_line_pixels(x1, y1, x2, y2, |x, y| self.stamp(x, y, brush));
+    }
+}
+
+/// Reads pixels as `canvas[(x, y)]`, just like `get`.
+///
+/// Pixels are bits packed into characters, so they can’t be borrowed mutably; use `set_px` to
+/// write them instead.
+///
+/// ```
+/// use drawille::braille::Canvas;
+///
+/// let mut canvas = Canvas::new(4, 4);
+/// canvas.set_px(1, 2, true);
+/// assert!(canvas[(1, 2)]);
+/// assert!(!canvas[(2, 1)]);
+/// ```
+impl Index<(usize, usize)> for Canvas {
+    type Output = bool;
+
+    fn index(&self, (x, y): (usize, usize)) -> &bool {
+        if self.get(x, y) { &true } else { &false }
+    }
+}
+
+/// Creates a new `Canvas` with a pixel for each element of an array indexed by row and then
+/// column, set where the element is `true`.
+#[cfg(feature = "ndarray")]
+impl<'a, S: ::ndarray::Data<Elem = bool>> From<&'a ::ndarray::ArrayBase<S, ::ndarray::Ix2>>
+    for Canvas {
+    fn from(array: &'a ::ndarray::ArrayBase<S, ::ndarray::Ix2>) -> Canvas {
+        let (height, width) = array.dim();
+        let mut canvas = Canvas::new(width, height);
+        for ((y, x), &set) in array.indexed_iter() {
+            if set {
+                canvas.set(x, y);
+            }
         }
+        canvas
+    }
+}
+
+#[cfg(feature = "gif")]
+impl From<&Canvas> for ::gif::Frame {
+    fn from(canvas: &Canvas) -> ::gif::Frame {
+        let (width, height) = canvas.pixel_size();
+        ::gif::Frame::from_fn(width, height, |x, y| canvas.pixel_color(x, y))
     }
 }
 
@@ -151,6 +1419,20 @@ pub struct Turtle {
     pub brush: bool,
     pub rotation: f32,
     pub cvs: Canvas,
+    pub wrap: bool,
+    shape: Brush,
+    history: VecDeque<Step>,
+    history_limit: usize,
+}
+
+/// The state of a `Turtle` before a single command, along with the pixels that command set.
+#[derive(Clone, Debug)]
+struct Step {
+    x: f32,
+    y: f32,
+    brush: bool,
+    rotation: f32,
+    pixels: Vec<(usize, usize)>,
 }
 
 impl Turtle {
@@ -160,10 +1442,14 @@ impl Turtle {
     pub fn new(x: f32, y: f32) -> Turtle {
         Turtle {
             cvs: Canvas::new(0, 0),
-            x: x,
-            y: y,
+            x,
+            y,
             brush: true,
             rotation: 0.0,
+            wrap: false,
+            shape: Brush::point(),
+            history: VecDeque::new(),
+            history_limit: DEFAULT_HISTORY_LIMIT,
         }
     }
 
@@ -172,38 +1458,87 @@ impl Turtle {
     /// The turtle starts with its brush down, facing right.
     pub fn from_canvas(x: f32, y: f32, cvs: Canvas) -> Turtle {
         Turtle {
-            cvs: cvs,
-            x: x,
-            y: y,
+            cvs,
+            x,
+            y,
             brush: true,
             rotation: 0.0,
+            wrap: false,
+            shape: Brush::point(),
+            history: VecDeque::new(),
+            history_limit: DEFAULT_HISTORY_LIMIT,
         }
     }
 
-    /// Sets the width of a `Turtle`’s `Canvas`, and return it for use again.
+    /// Sets the width of a `Turtle`’s `Canvas` in pixels, and return it for use again.
     pub fn width(mut self, width: usize) -> Turtle {
         self.cvs.width = width;
         self
     }
 
-    /// Sets the height of a `Turtle`’s `Canvas`, and return it for use again.
+    /// Sets the height of a `Turtle`’s `Canvas` in pixels, and return it for use again.
     pub fn height(mut self, height: usize) -> Turtle {
         self.cvs.height = height;
         self
     }
 
+    /// Sets the `Brush` the `Turtle` draws its lines with, and return it for use again.
+    ///
+    /// The turtle starts out drawing lines a single pixel wide.
+    pub fn brush_shape(mut self, brush: Brush) -> Turtle {
+        self.shape = brush;
+        self
+    }
+
+    /// Sets whether the `Turtle` wraps around the edges of its `Canvas`, and return it for use again.
+    ///
+    /// When wrapping, a `Turtle` that walks off one edge of the canvas reappears at the opposite
+    /// edge instead of expanding the canvas. An axis along which the canvas has no size never
+    /// wraps.
+    pub fn wrapping(mut self, wrap: bool) -> Turtle {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets how many of the latest commands the `Turtle` remembers to be undone, and return it
+    /// for use again.
+    ///
+    /// The turtle starts out remembering [`DEFAULT_HISTORY_LIMIT`] commands, forgetting the
+    /// oldest as new ones are given; a limit of zero remembers none at all.
+    ///
+    /// ```
+    /// use drawille::braille::Turtle;
+    ///
+    /// let mut turtle = Turtle::new(0.0, 0.0).history_limit(2);
+    /// for _ in 0..3 {
+    ///     turtle.forward(4.0);
+    /// }
+    /// assert_eq!(turtle.undo(3), 2);
+    /// assert_eq!(turtle.x, 4.0);
+    /// ```
+    pub fn history_limit(mut self, limit: usize) -> Turtle {
+        self.history_limit = limit;
+        while self.history.len() > limit {
+            self.history.pop_front();
+        }
+        self
+    }
+
     /// Lifts the `Turtle`’s brush.
     pub fn up(&mut self) {
+        self.record();
         self.brush = false;
     }
 
     /// Puts down the `Turtle`’s brush.
     pub fn down(&mut self) {
+        self.record();
         self.brush = true;
     }
 
     /// Toggles the `Turtle`’s brush.
     pub fn toggle(&mut self) {
+        self.record();
         self.brush = !self.brush;
     }
 
@@ -222,35 +1557,419 @@ impl Turtle {
     /// Teleports the `Turtle` to the given coordinates.
     ///
     /// Note that this draws a line between the old position and the new one if the `Turtle`’s
-    /// brush is down.
+    /// brush is down. If the `Turtle` is wrapping, the line wraps around the edges of the canvas
+    /// and the new position is brought back within the canvas; otherwise, any part of the line at
+    /// negative coordinates isn’t drawn.
+    ///
+    /// The line is rasterized from the exact (unrounded) positions, so many short moves end up
+    /// drawing the same pixels as one long one. No line is drawn to or from a position that isn’t
+    /// finite, and only the part of a line within reach of `MAX_COORDINATE` is drawn.
+    ///
+    /// ```
+    /// use drawille::braille::Turtle;
+    ///
+    /// let mut turtle = Turtle::new(0.0, 0.0);
+    /// turtle.teleport(std::f32::INFINITY, 0.0);
+    /// turtle.teleport(std::f32::NAN, 8.0);
+    /// turtle.teleport(4.0, 4.0);
+    /// assert!(turtle.frame().trim().is_empty());
+    /// turtle.teleport(1e12, 4.0);
+    /// assert!(!turtle.frame().trim().is_empty());
+    /// ```
     pub fn teleport(&mut self, x: f32, y: f32) {
+        self.record();
         if self.brush {
-            self.cvs.line(cmp::max(0, self.x.round() as isize) as usize,
-                          cmp::max(0, self.y.round() as isize) as usize,
-                          cmp::max(0, x.round() as isize) as usize,
-                          cmp::max(0, y.round() as isize) as usize);
+            let (width, height) = self.wrap_size();
+            let wrap = |n: isize, size: usize| if size > 0 {
+                Some(n.rem_euclid(size as isize) as usize)
+            } else if n >= 0 {
+                Some(n as usize)
+            } else {
+                None
+            };
+            // A wrapping line is followed for `LIMIT` pixels either side of the canvas, and any
+            // other line only where the brush could reach the canvas from it.
+            let reach = self.shape.offsets().iter()
+                .map(|&(dx, dy)| cmp::max(dx.abs(), dy.abs()))
+                .max().unwrap_or(0) as f64;
+            let limit = geometry::LIMIT;
+            let margin = if self.wrap { limit } else { reach.min(limit) + 0.5 };
+            let rect = (-margin, -margin, limit + margin, limit + margin);
+            let (cvs, shape) = (&mut self.cvs, &self.shape);
+            let mut pixels = vec![];
+            subpixel_line(self.x, self.y, x, y, rect, |px, py| {
+                for &(dx, dy) in shape.offsets() {
+                    if let (Some(px), Some(py)) = (wrap(px + dx, width), wrap(py + dy, height)) {
+                        if !cvs.get(px, py) {
+                            cvs.set(px, py);
+                            pixels.push((px, py));
+                        }
+                    }
+                }
+            });
+            if let Some(step) = self.history.back_mut() {
+                step.pixels = pixels;
+            }
         }
 
-        self.x = x;
-        self.y = y;
+        let (width, height) = self.wrap_size();
+        self.x = if width > 0 { x.rem_euclid(width as f32) } else { x };
+        self.y = if height > 0 { y.rem_euclid(height as f32) } else { y };
+    }
+
+    /// The size in pixels of the area the `Turtle` wraps around, or zero along an axis that doesn’t
+    /// wrap.
+    fn wrap_size(&self) -> (usize, usize) {
+        if self.wrap {
+            (self.cvs.width, self.cvs.height)
+        } else {
+            (0, 0)
+        }
     }
 
     /// Turns the `Turtle` right (clockwise) by `angle` degrees.
     pub fn right(&mut self, angle: f32) {
+        self.record();
         self.rotation += angle;
     }
 
     /// Turns the `Turtle` left (clockwise) by `angle` degrees.
     pub fn left(&mut self, angle: f32) {
+        self.record();
         self.rotation -= angle;
     }
 
+    /// Reverts the last `n` commands given to the `Turtle`, returning how many were undone.
+    ///
+    /// Every movement, turn and brush change counts as one command. Undoing a movement unsets
+    /// the pixels it drew, but leaves alone any pixels that were already set beforehand. Only
+    /// the commands within the history limit can be undone.
+    ///
+    /// ```
+    /// use drawille::braille::Turtle;
+    ///
+    /// let mut turtle = Turtle::new(0.0, 0.0);
+    /// turtle.cvs.set(2, 0);
+    /// turtle.forward(4.0);
+    /// assert!(turtle.cvs.get(1, 0));
+    /// assert_eq!(turtle.undo(5), 1);
+    /// assert!(!turtle.cvs.get(1, 0));
+    /// assert!(turtle.cvs.get(2, 0));
+    /// assert_eq!((turtle.x, turtle.y), (0.0, 0.0));
+    ///
+    /// turtle.forward(4.0);
+    /// turtle.clear_history();
+    /// assert_eq!(turtle.undo(1), 0);
+    /// assert!(turtle.cvs.get(4, 0));
+    /// ```
+    pub fn undo(&mut self, n: usize) -> usize {
+        let mut undone = 0;
+        while undone < n {
+            let step = match self.history.pop_back() {
+                Some(step) => step,
+                None => break,
+            };
+            for &(x, y) in step.pixels.iter() {
+                self.cvs.unset(x, y);
+            }
+            self.x = step.x;
+            self.y = step.y;
+            self.brush = step.brush;
+            self.rotation = step.rotation;
+            undone += 1;
+        }
+        undone
+    }
+
+    /// Forgets all recorded commands, so that they can no longer be undone.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Interprets a program written in a small Logo-like command language, driving the `Turtle`.
+    ///
+    /// Commands are separated by whitespace or commas and are case-insensitive. A numeric argument
+    /// may follow its command directly (`F100`) or as a separate word (`forward 100`).
+    ///
+    /// | Command                      | Effect                              |
+    /// |------------------------------|-------------------------------------|
+    /// | `F n`, `FD n`, `FORWARD n`   | move forward by `n` steps           |
+    /// | `B n`, `BK n`, `BACK n`      | move backward by `n` steps          |
+    /// | `R n`, `RT n`, `RIGHT n`     | turn right by `n` degrees           |
+    /// | `L n`, `LT n`, `LEFT n`      | turn left by `n` degrees            |
+    /// | `U`, `PU`, `UP`, `PENUP`     | lift the brush                      |
+    /// | `D`, `PD`, `DOWN`, `PENDOWN` | put down the brush                  |
+    /// | `T`, `TOGGLE`                | toggle the brush                    |
+    /// | `M x y`, `SETXY x y`         | teleport to `(x, y)`                |
+    /// | `REPEAT n [ ... ]`           | run the bracketed commands `n` times |
+    ///
+    /// The whole program is parsed before anything is drawn, so a program containing an error
+    /// leaves the `Turtle` untouched. So does a program that would run more than
+    /// [`MAX_PROGRAM_COMMANDS`] commands once its repeats are unrolled, or one that moves by a
+    /// distance or to a coordinate further from zero than [`MAX_COORDINATE`].
+    ///
+    /// ```
+    /// use drawille::braille::{ProgramError, Turtle};
+    ///
+    /// let mut turtle = Turtle::new(0.0, 0.0);
+    /// turtle.run("REPEAT 4 [F10 R90] U F20 D F5").unwrap();
+    /// assert!(turtle.run("F").is_err());
+    /// assert_eq!(turtle.run("REPEAT 100000 [ REPEAT 100000 [ F1 ] ]"),
+    ///            Err(ProgramError::TooManyCommands));
+    /// assert_eq!(turtle.run("F1e12"), Err(ProgramError::InvalidNumber("1e12".to_string())));
+    /// ```
+    pub fn run(&mut self, program: &str) -> Result<(), ProgramError> {
+        let tokens = tokenize(program);
+        let mut pos = 0;
+        let commands = try_parse_block(&tokens, &mut pos, false)?;
+        if count_commands(&commands) > MAX_PROGRAM_COMMANDS {
+            return Err(ProgramError::TooManyCommands);
+        }
+        self.execute(&commands);
+        Ok(())
+    }
+
+    fn execute(&mut self, commands: &[Command]) {
+        for command in commands.iter() {
+            match *command {
+                Command::Forward(dist) => self.forward(dist),
+                Command::Back(dist) => self.back(dist),
+                Command::Right(angle) => self.right(angle),
+                Command::Left(angle) => self.left(angle),
+                Command::Up => self.up(),
+                Command::Down => self.down(),
+                Command::Toggle => self.toggle(),
+                Command::Teleport(x, y) => self.teleport(x, y),
+                Command::Repeat(n, ref body) => for _ in 0..n {
+                    self.execute(body);
+                },
+            }
+        }
+    }
+
+    fn record(&mut self) {
+        if self.history_limit == 0 {
+            return;
+        }
+        if self.history.len() == self.history_limit {
+            self.history.pop_front();
+        }
+        self.history.push_back(Step {
+            x: self.x,
+            y: self.y,
+            brush: self.brush,
+            rotation: self.rotation,
+            pixels: vec![],
+        });
+    }
+
     /// Writes the `Turtle`’s `Canvas` to a `String` and returns it.
     pub fn frame(&self) -> String {
         self.cvs.frame()
     }
 }
 
+/// How many of its latest commands a new `Turtle` remembers to be undone.
+pub const DEFAULT_HISTORY_LIMIT: usize = 1000;
+
+/// The most commands a `Turtle` program may run, counting each repeat of a `REPEAT` block’s
+/// commands.
+///
+/// Nested repeats could otherwise keep a turtle running for hours from a program a few words
+/// long.
+pub const MAX_PROGRAM_COMMANDS: usize = 1_000_000;
+
+/// An error encountered while parsing a `Turtle` program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgramError {
+    /// A word that isn’t a known command.
+    UnknownCommand(String),
+    /// A command was missing one of its arguments.
+    MissingArgument(String),
+    /// An argument couldn’t be parsed as a number, or is out of range.
+    InvalidNumber(String),
+    /// A `[` without a matching `]`, or vice versa.
+    UnbalancedBrackets,
+    /// The program would run more than [`MAX_PROGRAM_COMMANDS`] commands.
+    TooManyCommands,
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProgramError::UnknownCommand(ref s) => write!(f, "unknown command `{}`", s),
+            ProgramError::MissingArgument(ref s) => write!(f, "missing argument for `{}`", s),
+            ProgramError::InvalidNumber(ref s) => write!(f, "invalid number `{}`", s),
+            ProgramError::UnbalancedBrackets => write!(f, "unbalanced brackets"),
+            ProgramError::TooManyCommands => {
+                write!(f, "more than {} commands to run", MAX_PROGRAM_COMMANDS)
+            },
+        }
+    }
+}
+
+impl Error for ProgramError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Command {
+    Forward(f32),
+    Back(f32),
+    Right(f32),
+    Left(f32),
+    Up,
+    Down,
+    Toggle,
+    Teleport(f32, f32),
+    Repeat(usize, Vec<Command>),
+}
+
+/// Splits a program into words, numbers and brackets, separating a command from a number written
+/// directly after it (so `F100` becomes `F`, `100`).
+fn tokenize(program: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    for c in program.chars() {
+        let starts_number = c.is_ascii_digit() || c == '-' || c == '+' || c == '.';
+        if c.is_whitespace() || c == ',' || c == '[' || c == ']' {
+            if !current.is_empty() {
+                tokens.push(current.clone());
+                current.clear();
+            }
+            if c == '[' || c == ']' {
+                tokens.push(c.to_string());
+            }
+        } else if starts_number && current.chars().all(|c| c.is_alphabetic()) && !current.is_empty() {
+            tokens.push(current.clone());
+            current.clear();
+            current.push(c);
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn try_parse_number(tokens: &[String], pos: &mut usize, command: &str) -> Result<f32, ProgramError> {
+    let token = match tokens.get(*pos) {
+        Some(token) if token != "[" && token != "]" => token,
+        _ => return Err(ProgramError::MissingArgument(command.to_string())),
+    };
+    *pos += 1;
+    match token.parse::<f32>() {
+        Ok(n) if n.is_finite() => Ok(n),
+        _ => Err(ProgramError::InvalidNumber(token.clone())),
+    }
+}
+
+/// Parses a distance or coordinate, which may be no further from zero than `MAX_COORDINATE` so
+/// that a single move can’t draw a line too long to rasterize.
+fn try_parse_distance(tokens: &[String], pos: &mut usize, command: &str)
+                      -> Result<f32, ProgramError> {
+    let n = try_parse_number(tokens, pos, command)?;
+    if n.abs() > MAX_COORDINATE as f32 {
+        return Err(ProgramError::InvalidNumber(tokens[*pos - 1].clone()));
+    }
+    Ok(n)
+}
+
+/// Counts the commands run by a parsed program, with each `REPEAT` counting as one command along
+/// with every command of each repeat of its block.
+fn count_commands(commands: &[Command]) -> usize {
+    commands.iter().fold(0, |count, command| {
+        let repeated = match *command {
+            Command::Repeat(n, ref body) => n.saturating_mul(count_commands(body)),
+            _ => 0,
+        };
+        count.saturating_add(1).saturating_add(repeated)
+    })
+}
+
+fn try_parse_block(tokens: &[String], pos: &mut usize, nested: bool)
+                   -> Result<Vec<Command>, ProgramError> {
+    let mut commands = vec![];
+    while *pos < tokens.len() {
+        let word = tokens[*pos].clone();
+        *pos += 1;
+        let command = match &*word.to_uppercase() {
+            "]" if nested => return Ok(commands),
+            "[" | "]" => return Err(ProgramError::UnbalancedBrackets),
+            "F" | "FD" | "FORWARD" => Command::Forward(try_parse_distance(tokens, pos, &word)?),
+            "B" | "BK" | "BACK" => Command::Back(try_parse_distance(tokens, pos, &word)?),
+            "R" | "RT" | "RIGHT" => Command::Right(try_parse_number(tokens, pos, &word)?),
+            "L" | "LT" | "LEFT" => Command::Left(try_parse_number(tokens, pos, &word)?),
+            "U" | "PU" | "UP" | "PENUP" => Command::Up,
+            "D" | "PD" | "DOWN" | "PENDOWN" => Command::Down,
+            "T" | "TOGGLE" => Command::Toggle,
+            "M" | "SETXY" => {
+                let x = try_parse_distance(tokens, pos, &word)?;
+                let y = try_parse_distance(tokens, pos, &word)?;
+                Command::Teleport(x, y)
+            },
+            "REPEAT" => {
+                let n = try_parse_number(tokens, pos, &word)?;
+                if n < 0.0 || n.fract() != 0.0 {
+                    return Err(ProgramError::InvalidNumber(tokens[*pos - 1].clone()));
+                }
+                if tokens.get(*pos).map(|t| &**t) != Some("[") {
+                    return Err(ProgramError::MissingArgument(word));
+                }
+                *pos += 1;
+                Command::Repeat(n as usize, try_parse_block(tokens, pos, true)?)
+            },
+            _ => return Err(ProgramError::UnknownCommand(word)),
+        };
+        commands.push(command);
+    }
+    if nested {
+        Err(ProgramError::UnbalancedBrackets)
+    } else {
+        Ok(commands)
+    }
+}
+
+/// Calls `visit` with each pixel on the line between two points given with sub-pixel precision,
+/// leaving out the pixels outside `rect`.
+///
+/// Steps one pixel at a time along the major axis and rounds the exact position on the line along
+/// the minor axis, so the result doesn’t depend on how the endpoints happen to round. There are
+/// no pixels on a line with an endpoint that isn’t finite.
+fn subpixel_line<F: FnMut(isize, isize)>(x1: f32, y1: f32, x2: f32, y2: f32, rect: Rect,
+                                         mut visit: F) {
+    if ![x1, y1, x2, y2].iter().all(|n| n.is_finite()) {
+        return;
+    }
+    let (a, b) = match geometry::clip((x1 as f64, y1 as f64), (x2 as f64, y2 as f64), rect) {
+        Some(clipped) => clipped,
+        None => return,
+    };
+    let (xdiff, ydiff) = (x2 - x1, y2 - y1);
+    let x_major = xdiff.abs() >= ydiff.abs();
+    let (major1, minor1, slope, start, end) = if x_major {
+        (x1, y1, if xdiff == 0.0 { 0.0 } else { ydiff / xdiff }, a.0, b.0)
+    } else {
+        (y1, x1, xdiff / ydiff, a.1, b.1)
+    };
+
+    let (start, end) = (start.round() as isize, end.round() as isize);
+    let dir = if start <= end { 1 } else { -1 };
+    let mut major = start;
+    loop {
+        let minor = (minor1 + (major as f32 - major1) * slope).round() as isize;
+        if x_major {
+            visit(major, minor);
+        } else {
+            visit(minor, major);
+        }
+        if major == end {
+            break;
+        }
+        major += dir;
+    }
+}
+
 fn degrees_to_radians(deg: f32) -> f32 {
     deg * (f32::consts::PI / 180.0f32)
 }
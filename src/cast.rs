@@ -0,0 +1,125 @@
+//! Recording frames as asciinema casts
+//!
+//! A `Recorder` collects a sequence of frames, such as those from `Canvas::frame`, and writes them
+//! out in the asciinema cast format (version 2), so animations can be played back in a terminal
+//! with `asciinema play` or shared on asciinema.org. Each frame is drawn over the last from the
+//! top left corner of the terminal.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use drawille::braille::Canvas;
+//! use drawille::cast::Recorder;
+//!
+//! let mut recorder = Recorder::new(21, 11);
+//! let mut canvas = Canvas::new(40, 40);
+//! for i in 0..40 {
+//!     canvas.line(0, i, 39, 39 - i);
+//!     recorder.push(canvas.frame(), Duration::from_millis(50));
+//! }
+//! recorder.save("lines.cast").unwrap();
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Collects frames and writes them as an asciinema cast.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recorder {
+    width: usize,
+    height: usize,
+    frames: Vec<(String, Duration)>,
+}
+
+impl Recorder {
+    /// Creates a new `Recorder` for a terminal `width` columns by `height` rows.
+    pub fn new(width: usize, height: usize) -> Recorder {
+        Recorder {
+            width,
+            height,
+            frames: vec![],
+        }
+    }
+
+    /// Adds a frame to be shown for `delay`.
+    pub fn push<S: AsRef<str>>(&mut self, frame: S, delay: Duration) {
+        self.frames.push((frame.as_ref().to_string(), delay));
+    }
+
+    /// Returns the number of frames recorded so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Detects whether no frames have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes the frames recorded so far as an asciinema cast.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use drawille::cast::Recorder;
+    ///
+    /// let mut recorder = Recorder::new(2, 1);
+    /// recorder.push("⠁", Duration::from_millis(500));
+    /// let mut cast = vec![];
+    /// recorder.write(&mut cast).unwrap();
+    /// assert_eq!(String::from_utf8(cast).unwrap().lines().nth(1).unwrap(),
+    ///            r#"[0.000000, "o", "\u001b[?25l\u001b[2J\u001b[H⠁\u001b[K\u001b[J"]"#);
+    /// ```
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, r#"{{"version": 2, "width": {}, "height": {}}}"#, self.width, self.height)?;
+        let mut time = Duration::from_secs(0);
+        // Hide the cursor while playing, and start from a clear screen.
+        let mut output = String::from("\x1b[?25l\x1b[2J");
+        for &(ref frame, delay) in self.frames.iter() {
+            output.push_str("\x1b[H");
+            for (i, line) in frame.lines().enumerate() {
+                if i > 0 {
+                    output.push_str("\r\n");
+                }
+                output.push_str(line);
+                // Clear whatever is left of the last frame after each line.
+                output.push_str("\x1b[K");
+            }
+            output.push_str("\x1b[J");
+            write_event(&mut out, time, &output)?;
+            output.clear();
+            time += delay;
+        }
+        // Showing the cursor again at the end keeps the last frame up for its delay.
+        write_event(&mut out, time, "\x1b[?25h")
+    }
+
+    /// Writes the frames recorded so far as an asciinema cast to a file at the given path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()
+    }
+}
+
+/// Writes a line of output shown at `time` as a cast event.
+fn write_event<W: Write>(out: &mut W, time: Duration, output: &str) -> io::Result<()> {
+    let mut escaped = String::with_capacity(output.len());
+    for c in output.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c if (c as u32) < 0x20 || c == '\x7f' => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32))
+            },
+            c => escaped.push(c),
+        }
+    }
+    writeln!(out, r#"[{:.6}, "o", "{}"]"#, time.as_secs_f64(), escaped)
+}
@@ -0,0 +1,149 @@
+//! The pixel storage shared by canvases that pack a small grid of pixels into each character.
+//!
+//! Each character cell keeps a bit mask of its pixels, numbered left to right and then top to
+//! bottom, and the canvas turns masks into characters with a glyph table when it’s drawn.
+
+use std::cmp;
+use std::collections::HashMap;
+
+use block::Color;
+use console;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CellCanvas {
+    cells: HashMap<(usize, usize), u8>,
+    colors: HashMap<(usize, usize), Color>,
+    /// The width and height of each cell in pixels.
+    cell: (usize, usize),
+    monochrome: bool,
+    width: usize,
+    height: usize,
+}
+
+impl CellCanvas {
+    /// Creates a new `CellCanvas` with the given width and height in pixels, and cells `cell.0` by
+    /// `cell.1` pixels, which must have no more than eight pixels in all.
+    pub fn new(width: usize, height: usize, cell: (usize, usize)) -> CellCanvas {
+        CellCanvas {
+            cells: HashMap::new(),
+            colors: HashMap::new(),
+            cell,
+            monochrome: console::no_color(),
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn char_width(&self) -> usize {
+        self.width.div_ceil(self.cell.0)
+    }
+
+    pub fn char_height(&self) -> usize {
+        self.height.div_ceil(self.cell.1)
+    }
+
+    /// Sets whether `rows` leaves out colours.
+    pub fn set_monochrome(&mut self, monochrome: bool) {
+        self.monochrome = monochrome;
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.colors.clear();
+    }
+
+    /// Returns the cell containing a pixel, and the pixel's bit within that cell's mask.
+    fn locate(&self, x: usize, y: usize) -> ((usize, usize), u8) {
+        let (w, h) = self.cell;
+        ((x / w, y / h), 1 << ((y % h) * w + x % w))
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        let (cell, bit) = self.locate(x, y);
+        *self.cells.entry(cell).or_insert(0) |= bit;
+    }
+
+    pub fn set_colored(&mut self, x: usize, y: usize, color: Color) {
+        self.set(x, y);
+        self.colors.insert(self.locate(x, y).0, color);
+    }
+
+    pub fn unset(&mut self, x: usize, y: usize) {
+        let (cell, bit) = self.locate(x, y);
+        if let Some(mask) = self.cells.get_mut(&cell) {
+            *mask &= !bit;
+        }
+        self.forget_if_empty(cell);
+    }
+
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        let (cell, bit) = self.locate(x, y);
+        *self.cells.entry(cell).or_insert(0) ^= bit;
+        self.forget_if_empty(cell);
+    }
+
+    /// Removes a cell and its colour once none of its pixels are set, so that canvases with the
+    /// same pixels compare equal.
+    fn forget_if_empty(&mut self, cell: (usize, usize)) {
+        if self.cells.get(&cell) == Some(&0) {
+            self.cells.remove(&cell);
+            self.colors.remove(&cell);
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (cell, bit) = self.locate(x, y);
+        self.cells.get(&cell).is_some_and(|&mask| mask & bit != 0)
+    }
+
+    /// Returns each row of characters, turning each cell's mask into a character with `glyph`.
+    pub fn rows<F: Fn(u8) -> char>(&self, glyph: F) -> Vec<String> {
+        let columns = self.cells.keys().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let rows = self.cells.keys().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        let (columns, rows) = (cmp::max(self.char_width(), columns),
+                               cmp::max(self.char_height(), rows));
+
+        let mut result = vec![];
+        for y in 0..rows {
+            let mut row = String::new();
+            for x in 0..columns {
+                let mask = *self.cells.get(&(x, y)).unwrap_or(&0);
+                let c = glyph(mask);
+                match self.colors.get(&(x, y)) {
+                    Some(&color) if !self.monochrome && mask != 0 => {
+                        row.push_str(&format!("\x1b[3{}m{}\x1b[0m", color as u32, c))
+                    },
+                    _ => row.push(c),
+                }
+            }
+            result.push(row);
+        }
+        result
+    }
+
+    pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        let xdiff = cmp::max(x1, x2) - cmp::min(x1, x2);
+        let ydiff = cmp::max(y1, y2) - cmp::min(y1, y2);
+        let r = cmp::max(xdiff, ydiff);
+        for i in 0..r + 1 {
+            let step = |from: usize, to: usize, diff: usize| {
+                if diff == 0 {
+                    from
+                } else if from <= to {
+                    from + i * diff / r
+                } else {
+                    from - i * diff / r
+                }
+            };
+            self.set(step(x1, x2, xdiff), step(y1, y2, ydiff));
+        }
+    }
+}
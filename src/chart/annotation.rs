@@ -0,0 +1,170 @@
+use std::cmp;
+
+use braille::{Canvas, LineStyle};
+use dither;
+use super::PlotArea;
+
+/// The density of the dots shading a region.
+const SHADE_DENSITY: f64 = 0.125;
+
+/// Something drawn over a chart, above its series, to point out values in it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Annotation {
+    /// A line across the whole plot at a y value, such as the threshold an alert fires at, with an
+    /// optional label above its right-hand end.
+    HorizontalLine { y: f64, style: LineStyle, label: Option<String> },
+    /// A line down the whole plot at an x value, such as the time of a deploy, with an optional
+    /// label beside its top.
+    VerticalLine { x: f64, style: LineStyle, label: Option<String> },
+    /// A band across the whole plot between two y values, shaded with sparse dots. Either value
+    /// may be infinite, to shade everything above or below the other.
+    HorizontalRegion { from: f64, to: f64 },
+    /// A band down the whole plot between two x values, shaded with sparse dots. Either value may
+    /// be infinite, to shade everything before or after the other.
+    VerticalRegion { from: f64, to: f64 },
+    /// Text labelling a data point, placed a little above and to the right of it where there's
+    /// room, and joined to it by a leader line.
+    Label { x: f64, y: f64, text: String },
+}
+
+impl Annotation {
+    /// Creates a dashed `HorizontalLine` at `y` with the given label.
+    pub fn threshold<S: Into<String>>(y: f64, label: S) -> Annotation {
+        Annotation::HorizontalLine { y, style: LineStyle::Dashed, label: Some(label.into()) }
+    }
+
+    /// Creates a `Label` of the point `(x, y)` with the given text.
+    pub fn label<S: Into<String>>(x: f64, y: f64, text: S) -> Annotation {
+        Annotation::Label { x, y, text: text.into() }
+    }
+}
+
+/// Draws annotations over the plot area, in order, cut short at the edges of the area.
+///
+/// Annotations of values outside the area's scales aren't drawn, apart from the parts of regions
+/// that reach into it.
+///
+/// ```
+/// use drawille::braille::{Canvas, LineStyle};
+/// use drawille::chart::{self, Annotation, Scale};
+///
+/// let mut canvas = Canvas::new(80, 40);
+/// let area = chart::draw_axes(&mut canvas, 80, 40, Scale::new(0.0, 60.0), Scale::new(99.0, 100.0))
+///     .unwrap();
+/// chart::draw_annotations(&mut canvas, &area, &[
+///     Annotation::HorizontalRegion { from: 99.0, to: 99.9 },
+///     Annotation::threshold(99.9, "SLO"),
+///     Annotation::VerticalLine { x: 42.0, style: LineStyle::Dotted,
+///                                label: Some("deploy".to_string()) },
+///     Annotation::label(30.0, 99.5, "outage"),
+/// ]);
+/// ```
+pub fn draw_annotations(canvas: &mut Canvas, area: &PlotArea, annotations: &[Annotation]) {
+    let (right, bottom) = (area.left + area.width, area.top + area.height);
+    let x_position = |x: f64| area.project(x, area.y_scale.min).0;
+    let y_position = |y: f64| area.project(area.x_scale.min, y).1;
+    for annotation in annotations.iter() {
+        match *annotation {
+            Annotation::HorizontalLine { y, style, ref label } => {
+                let py = match covered(area.top, area.height, y_position(y), y_position(y)) {
+                    Some((py, _)) => py,
+                    None => continue,
+                };
+                for px in (area.left..right).filter(|&px| style.draws(px - area.left)) {
+                    canvas.set(px, py);
+                }
+                if let Some(ref label) = *label {
+                    // Above the line, unless it's in the top row of characters of the plot.
+                    let row = if py / 4 > area.top.div_ceil(4) { py / 4 - 1 } else { py / 4 + 1 };
+                    let col = (right / 2).saturating_sub(label.chars().count());
+                    write_label(canvas, area, col, row, label);
+                }
+            },
+            Annotation::VerticalLine { x, style, ref label } => {
+                let px = match covered(area.left, area.width, x_position(x), x_position(x)) {
+                    Some((px, _)) => px,
+                    None => continue,
+                };
+                for py in (area.top..bottom).filter(|&py| style.draws(py - area.top)) {
+                    canvas.set(px, py);
+                }
+                if let Some(ref label) = *label {
+                    // To the right of the line, or its left if there isn't room on the right.
+                    let len = label.chars().count();
+                    let col = if px / 2 + 1 + len <= right / 2 {
+                        px / 2 + 1
+                    } else {
+                        (px / 2).saturating_sub(len)
+                    };
+                    write_label(canvas, area, col, area.top.div_ceil(4), label);
+                }
+            },
+            Annotation::HorizontalRegion { from, to } => {
+                if let Some((top, bottom)) = covered(area.top, area.height, y_position(from),
+                                                     y_position(to)) {
+                    shade(canvas, (area.left, right - 1), (top, bottom));
+                }
+            },
+            Annotation::VerticalRegion { from, to } => {
+                if let Some((left, right)) = covered(area.left, area.width, x_position(from),
+                                                     x_position(to)) {
+                    shade(canvas, (left, right), (area.top, bottom - 1));
+                }
+            },
+            Annotation::Label { x, y, ref text } => {
+                if let Some(point) = area.pixel(x, y) {
+                    point_label(canvas, area, point, text);
+                }
+            },
+        }
+    }
+}
+
+/// Returns the first and last of the `length` pixels from `start` covered between the positions
+/// `a` and `b`, or `None` if they cover none of them.
+fn covered(start: usize, length: usize, a: f64, b: f64) -> Option<(usize, usize)> {
+    let (low, high) = (a.min(b).round(), a.max(b).round());
+    let end = (start + length) as f64 - 1.0;
+    if length == 0 || !(low <= end && high >= start as f64) {
+        return None;
+    }
+    Some((low.max(start as f64) as usize, high.min(end) as usize))
+}
+
+/// Sets sparse dots between the given pixel columns and rows, inclusive.
+fn shade(canvas: &mut Canvas, (left, right): (usize, usize), (top, bottom): (usize, usize)) {
+    for y in top..bottom + 1 {
+        for x in (left..right + 1).filter(|&x| dither::is_set(x, y, SHADE_DENSITY)) {
+            canvas.set(x, y);
+        }
+    }
+}
+
+/// Writes a label in a row of characters from column `col`, moving it left as far as needed to
+/// end inside the plot area, and cutting it short if it's wider than the area.
+fn write_label(canvas: &mut Canvas, area: &PlotArea, col: usize, row: usize, label: &str) {
+    let (first, end) = (area.left.div_ceil(2), (area.left + area.width) / 2);
+    let label = label.chars().take(end.saturating_sub(first)).collect::<String>();
+    let col = cmp::max(first, cmp::min(col, end.saturating_sub(label.chars().count())));
+    canvas.text(col * 2, row * 4, label);
+}
+
+/// Writes the text of a `Label` two rows of characters above the point at pixel `(px, py)`, or
+/// below it if there isn't room above, and joins it to the point with a leader line.
+fn point_label(canvas: &mut Canvas, area: &PlotArea, (px, py): (usize, usize), text: &str) {
+    let (first, end) = (area.left.div_ceil(2), (area.left + area.width) / 2);
+    let text = text.chars().take(end.saturating_sub(first)).collect::<String>();
+    let len = text.chars().count();
+    let above = py / 4 >= area.top.div_ceil(4) + 2;
+    let row = if above { py / 4 - 2 } else { py / 4 + 2 };
+    // To the right of the point, or its left if there isn't room on the right.
+    let right = px / 2 + 2 + len <= end;
+    let col = if right { px / 2 + 2 } else { cmp::max(first, (px / 2).saturating_sub(1 + len)) };
+
+    // The leader line ends in the pixel just off the corner of the text nearest the point, since
+    // text is shown in place of any pixels in its characters.
+    let leader_x = if right { col * 2 - 1 } else { (col + len) * 2 };
+    let leader_y = if above { row * 4 + 4 } else { row * 4 - 1 };
+    canvas.line(px, py, leader_x, leader_y);
+    canvas.text(col * 2, row * 4, text);
+}
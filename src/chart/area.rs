@@ -0,0 +1,281 @@
+use block::{self, Color};
+use braille::Canvas;
+use dither;
+use super::{Chart, ChartLayout, LegendEntry, PlotArea, Scale, max_y_ticks};
+use super::axis::AxisSpec;
+
+/// The dot densities used to fill successive series of an area chart, so that neighbouring layers
+/// can be told apart.
+static FILL_DENSITIES: [f64; 3] = [0.25, 0.625, 0.4375];
+
+/// The colours given to series of an area chart drawn on a block canvas without a colour of their
+/// own.
+pub static FILL_COLORS: [Color; 6] = [Color::Blue, Color::Green, Color::Yellow, Color::Red,
+                                      Color::Magenta, Color::Cyan];
+
+/// An area chart of one or more series, each filled down to the x axis or, when stacked, down to
+/// the series below it.
+///
+/// Each filled area is outlined by its series and shaded with dithered dots, at a different
+/// density for each series. The chart can also be drawn onto a block canvas, where every series is
+/// filled with a solid colour instead.
+///
+/// ```
+/// use drawille::chart::AreaChart;
+///
+/// let user = [12.0, 18.0, 25.0, 22.0, 30.0, 28.0];
+/// let system = [5.0, 6.0, 9.0, 7.0, 8.0, 11.0];
+/// let chart = AreaChart::new(80, 32).series(&user).series(&system).stacked()
+///                                   .labels(&["user", "system"]);
+/// println!("{}", chart.frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AreaChart {
+    width: usize,
+    height: usize,
+    series: Vec<AreaSeries>,
+    stacked: bool,
+    x_axis: AxisSpec,
+    y_axis: AxisSpec,
+    layout: ChartLayout,
+    labels: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct AreaSeries {
+    points: Vec<(f64, f64)>,
+    color: Option<Color>,
+}
+
+impl AreaChart {
+    /// Creates a new, empty `AreaChart` with the given width and height in pixels, including its
+    /// axes and their labels.
+    pub fn new(width: usize, height: usize) -> AreaChart {
+        AreaChart {
+            width,
+            height,
+            series: vec![],
+            stacked: false,
+            x_axis: AxisSpec::new(),
+            y_axis: AxisSpec::new(),
+            layout: ChartLayout::new(width, height),
+            labels: vec![],
+        }
+    }
+
+    /// Adds a series of values, plotted against their indices, and returns the chart for use
+    /// again.
+    pub fn series(self, values: &[f64]) -> AreaChart {
+        let points = values.iter().enumerate().map(|(i, &y)| (i as f64, y)).collect::<Vec<_>>();
+        self.series_xy(&points)
+    }
+
+    /// Adds a series of `(x, y)` points, and returns the chart for use again.
+    pub fn series_xy(mut self, points: &[(f64, f64)]) -> AreaChart {
+        self.series.push(AreaSeries { points: points.to_vec(), color: None });
+        self
+    }
+
+    /// Adds a series of `(x, y)` points, filled with the given colour when drawn on a block
+    /// canvas, and returns the chart for use again.
+    pub fn colored_series_xy(mut self, points: &[(f64, f64)], color: Color) -> AreaChart {
+        self.series.push(AreaSeries { points: points.to_vec(), color: Some(color) });
+        self
+    }
+
+    /// Stacks each series on top of the ones added before it, and returns the chart for use again.
+    pub fn stacked(mut self) -> AreaChart {
+        self.stacked = true;
+        self
+    }
+
+    /// Sets the range of the x axis instead of fitting it to the data, and returns the chart for
+    /// use again.
+    pub fn x_range(mut self, min: f64, max: f64) -> AreaChart {
+        self.x_axis.range = Some((min, max));
+        self
+    }
+
+    /// Sets the range of the y axis instead of fitting it to the data, and returns the chart for
+    /// use again.
+    pub fn y_range(mut self, min: f64, max: f64) -> AreaChart {
+        self.y_axis.range = Some((min, max));
+        self
+    }
+
+    /// Sets the title shown above the chart, and returns the chart for use again.
+    pub fn title<S: Into<String>>(mut self, title: S) -> AreaChart {
+        self.layout = self.layout.title(title);
+        self
+    }
+
+    /// Names the series in the order they were added, listing them in a legend, and returns the
+    /// chart for use again.
+    pub fn labels<S: AsRef<str>>(mut self, labels: &[S]) -> AreaChart {
+        self.labels = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Returns the scales to plot the chart with, fitting the y axis to include zero and the tops
+    /// of any stacked series.
+    fn scales(&self, max_y_ticks: usize) -> (Scale, Scale) {
+        let xs = || self.series.iter().flat_map(|s| s.points.iter().map(|p| p.0));
+        let x_scale = self.x_axis.scale(xs(), None);
+        let tops = xs().map(|x| self.bands(x).last().map_or(0.0, |band| band.1))
+                       .collect::<Vec<_>>();
+        let ys = self.series.iter().flat_map(|s| s.points.iter().map(|p| p.1));
+        let ys = ys.chain(tops).chain(Some(0.0)).collect::<Vec<_>>();
+        (x_scale, self.y_axis.scale(ys, Some(max_y_ticks)))
+    }
+
+    /// Returns the bottom and top of each series' filled area at `x`, or `NaN`s where a series
+    /// doesn't cover `x`.
+    fn bands(&self, x: f64) -> Vec<(f64, f64)> {
+        let mut base = 0.0;
+        self.series.iter().map(|series| {
+            let y = interpolate(&series.points, x);
+            if !self.stacked {
+                return (0.0, y);
+            }
+            let band = (base, base + y);
+            if y.is_finite() {
+                base += y;
+            }
+            band
+        }).collect()
+    }
+
+    /// Draws the chart onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let legend = self.labels.iter().map(LegendEntry::new).collect();
+        let layout = self.layout.clone().legend(legend);
+        let (x_scale, y_scale) = self.scales(max_y_ticks(layout.axes_height()));
+        let area = match layout.draw(canvas, x_scale, y_scale) {
+            Some(area) => area,
+            None => return,
+        };
+        for px in area.left..area.left + area.width {
+            let x = x_scale.value_at((px - area.left) as f64, area.width);
+            for (i, (bottom, top)) in self.bands(x).into_iter().enumerate() {
+                if let Some((top_px, bottom_px)) = rows(&area, x, bottom, top) {
+                    let density = FILL_DENSITIES[i % FILL_DENSITIES.len()];
+                    fill_column(canvas, px, top_px, bottom_px, density);
+                }
+            }
+        }
+    }
+
+    /// Draws the filled areas onto a block `Canvas`, stretched over the whole chart with no axes,
+    /// with its top left corner at the origin.
+    ///
+    /// Series without a colour of their own are given one from a fixed palette.
+    pub fn draw_colored(&self, canvas: &mut block::Canvas) {
+        let (x_scale, y_scale) = self.scales(2);
+        let area = PlotArea {
+            left: 0,
+            top: 0,
+            width: self.width,
+            height: self.height,
+            x_scale,
+            y_scale,
+        };
+        for px in 0..self.width {
+            let x = x_scale.value_at(px as f64, self.width);
+            for (i, (bottom, top)) in self.bands(x).into_iter().enumerate() {
+                let color = self.series[i].color.unwrap_or(FILL_COLORS[i % FILL_COLORS.len()]);
+                if let Some((top_px, bottom_px)) = rows(&area, x, bottom, top) {
+                    for py in top_px..bottom_px + 1 {
+                        canvas.set(px, py, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws the chart to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+/// Fills the region between two curves across a plot area with dithered dots, outlining both
+/// curves.
+///
+/// The curves are given as `(x, y)` points and joined by straight lines; the region is only filled
+/// where both curves are defined. `density` runs from zero for no dots to one for every dot.
+pub fn fill_between(canvas: &mut Canvas, area: &PlotArea, lower: &[(f64, f64)],
+                    upper: &[(f64, f64)], density: f64) {
+    for px in area.left..area.left + area.width {
+        let x = area.x_scale.value_at((px - area.left) as f64, area.width);
+        let (a, b) = (interpolate(lower, x), interpolate(upper, x));
+        if let Some((top_px, bottom_px)) = rows(area, x, a.min(b), a.max(b)) {
+            fill_column(canvas, px, top_px, bottom_px, density);
+        }
+    }
+}
+
+/// Returns the top and bottom pixel rows of the band from `bottom` to `top` at `x`, clipped to the
+/// plot area, or `None` if it's empty or not finite.
+pub fn rows(area: &PlotArea, x: f64, bottom: f64, top: f64) -> Option<(usize, usize)> {
+    let (_, top_y) = area.project(x, top);
+    let (_, bottom_y) = area.project(x, bottom);
+    if !top_y.is_finite() || !bottom_y.is_finite() {
+        return None;
+    }
+    let last = (area.top + area.height - 1) as f64;
+    let top_y = top_y.round().clamp(area.top as f64, last);
+    let bottom_y = bottom_y.round().clamp(area.top as f64, last);
+    if top_y > bottom_y {
+        return None;
+    }
+    Some((top_y as usize, bottom_y as usize))
+}
+
+/// Sets the two ends of a column of pixels, and dithered pixels in between.
+fn fill_column(canvas: &mut Canvas, x: usize, top: usize, bottom: usize, density: f64) {
+    canvas.set(x, top);
+    canvas.set(x, bottom);
+    for y in top + 1..bottom {
+        if dither::is_set(x, y, density) {
+            canvas.set(x, y);
+        }
+    }
+}
+
+/// Returns the y value at `x` along straight lines joining the points, in order, or `NaN` if `x`
+/// falls outside them.
+fn interpolate(points: &[(f64, f64)], x: f64) -> f64 {
+    for pair in points.windows(2) {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        if (x1 <= x && x <= x2) || (x2 <= x && x <= x1) {
+            if x1 == x2 {
+                return y1.max(y2);
+            }
+            return y1 + (y2 - y1) * (x - x1) / (x2 - x1);
+        }
+    }
+    match points {
+        [(px, py)] if *px == x => *py,
+        _ => f64::NAN,
+    }
+}
+
+impl Chart for AreaChart {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        AreaChart::draw(self, canvas);
+    }
+
+    fn x_extent(&self) -> Option<(f64, f64)> {
+        self.x_axis.extent(self.series.iter().flat_map(|s| s.points.iter().map(|p| p.0)))
+    }
+
+    fn draw_x_range(&self, canvas: &mut Canvas, min: f64, max: f64) {
+        self.clone().x_range(min, max).draw(canvas);
+    }
+}
@@ -0,0 +1,163 @@
+use std::f64::consts::PI;
+
+use braille::Canvas;
+
+/// The quietest level shown by `spectrum`, in decibels relative to a full-scale sine wave.
+const SPECTRUM_FLOOR_DB: f64 = -60.0;
+
+/// Draws a waveform of PCM samples, from -1 to 1, `size.0` by `size.1` pixels, and returns it as a
+/// `String`.
+///
+/// ```
+/// use drawille::chart::waveform;
+///
+/// let samples = (0..4410).map(|i| {
+///     let t = i as f32 / 44100.0;
+///     (t * 440.0 * 6.2832).sin() * (t * 20.0).min(1.0)
+/// }).collect::<Vec<_>>();
+/// println!("{}", waveform(&samples, (80, 16)));
+/// ```
+pub fn waveform(samples: &[f32], size: (usize, usize)) -> String {
+    let mut canvas = Canvas::new(size.0, size.1);
+    draw_waveform(&mut canvas, samples, size);
+    canvas.frame()
+}
+
+/// Draws a waveform of PCM samples, from -1 to 1, onto the `Canvas`, `size.0` by `size.1` pixels
+/// with its top left corner at the origin.
+///
+/// Each pixel column covers an equal share of the samples and is drawn from the lowest to the
+/// highest of them, so peaks are never lost however many samples share a column. Neighbouring
+/// columns are joined so that quiet passages and short slices still draw a continuous line.
+/// Samples beyond full scale are clipped to the edges.
+pub fn draw_waveform(canvas: &mut Canvas, samples: &[f32], (width, height): (usize, usize)) {
+    if samples.is_empty() || width == 0 || height == 0 {
+        return;
+    }
+    let n = samples.len();
+    let centre = (height - 1) as f64 / 2.0;
+    let row = |sample: f32| {
+        let sample = if sample.is_finite() { sample.clamp(-1.0, 1.0) } else { 0.0 };
+        (centre - sample as f64 * centre).round() as usize
+    };
+    let mut last = None;
+    for x in 0..width {
+        // The samples under this column; with fewer samples than columns, the nearest one.
+        let (start, end) = (x * n / width, ((x + 1) * n / width).max(x * n / width + 1));
+        let column = &samples[start..end.min(n)];
+        let (mut low, mut high) = column.iter().fold((f32::INFINITY, f32::NEG_INFINITY),
+                                                     |(lo, hi), &s| (lo.min(s), hi.max(s)));
+        // Stretch the column to meet the end of the previous one.
+        if let Some(previous) = last {
+            low = low.min(previous);
+            high = high.max(previous);
+        }
+        last = column.last().cloned();
+        canvas.line(x, row(high), x, row(low));
+    }
+}
+
+/// Returns the level of PCM samples in `bands` frequency bands, from zero for 60dB or more below
+/// full scale to one for full scale, using a fast Fourier transform.
+///
+/// The samples are windowed and padded to a power of two. The bands are spaced logarithmically,
+/// from the lowest frequency the samples resolve up to half the sample rate, and each takes the
+/// level of its loudest frequency. The levels suit `draw_spectrum`, or rows of a `Spectrogram`.
+///
+/// ```
+/// use std::f32::consts::FRAC_PI_2;
+/// use drawille::chart::spectrum;
+///
+/// // A full-scale sine wave at a quarter of the sample rate is loudest in the top band.
+/// let sine = (0..1024).map(|i| (i as f32 * FRAC_PI_2).sin()).collect::<Vec<_>>();
+/// let levels = spectrum(&sine, 8);
+/// assert!(levels[7] > 0.95 && levels[0] < 0.1);
+/// ```
+pub fn spectrum(samples: &[f32], bands: usize) -> Vec<f64> {
+    let magnitudes = if samples.len() > 2 { fft_magnitudes(samples) } else { vec![] };
+    let bins = magnitudes.len();
+    if bins < 2 {
+        return vec![0.0; bands];
+    }
+    // Skip the constant bin. Low bands narrower than a bin share it with their neighbours.
+    let edge = |b: usize| (bins as f64).powf(b as f64 / bands as f64).round() as usize;
+    (0..bands).map(|b| {
+        let start = edge(b).clamp(1, bins - 1);
+        let end = edge(b + 1).clamp(start + 1, bins);
+        let peak = magnitudes[start..end].iter().cloned().fold(0.0, f64::max);
+        let db = 20.0 * peak.log10();
+        ((db - SPECTRUM_FLOOR_DB) / -SPECTRUM_FLOOR_DB).clamp(0.0, 1.0)
+    }).collect()
+}
+
+/// Draws levels from zero to one, such as those returned by `spectrum`, as bars rising from the
+/// bottom of an area `size.0` by `size.1` pixels onto the `Canvas`, with its top left corner at
+/// the origin.
+///
+/// The bars share the width equally, with a one pixel gap between bars at least three pixels wide.
+pub fn draw_spectrum(canvas: &mut Canvas, levels: &[f64], (width, height): (usize, usize)) {
+    if levels.is_empty() || height == 0 {
+        return;
+    }
+    for (i, &level) in levels.iter().enumerate() {
+        let (left, right) = (i * width / levels.len(), (i + 1) * width / levels.len());
+        let right = if right - left >= 3 { right - 1 } else { right };
+        let level = if level.is_finite() { level.clamp(0.0, 1.0) } else { 0.0 };
+        let bar = (level * height as f64).round() as usize;
+        for x in left..right {
+            for y in height - bar..height {
+                canvas.set(x, y);
+            }
+        }
+    }
+}
+
+/// Returns the amplitude of each frequency in the samples below half the sample rate, with a full
+/// scale sine wave having an amplitude of one, after applying a Hann window.
+fn fft_magnitudes(samples: &[f32]) -> Vec<f64> {
+    let n = samples.len();
+    let size = n.next_power_of_two();
+    let window = |i: usize| 0.5 - 0.5 * (2.0 * PI * i as f64 / (n - 1) as f64).cos();
+    let mut re = (0..size).map(|i| if i < n { samples[i] as f64 * window(i) } else { 0.0 })
+                          .collect::<Vec<_>>();
+    let mut im = vec![0.0; size];
+    fft(&mut re, &mut im);
+    // The window halves a sine wave's amplitude; the factor of two counts negative frequencies.
+    let gain = 2.0 / (0..n).map(window).sum::<f64>();
+    (0..size / 2).map(|k| (re[k] * re[k] + im[k] * im[k]).sqrt() * gain).collect()
+}
+
+/// Transforms `re + i im` in place with an iterative radix-2 FFT. Both slices must have the same
+/// power-of-two length.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (wr, wi) = ((angle * k as f64).cos(), (angle * k as f64).sin());
+                let (a, b) = (start + k, start + k + len / 2);
+                let (tr, ti) = (re[b] * wr - im[b] * wi, re[b] * wi + im[b] * wr);
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
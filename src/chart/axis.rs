@@ -0,0 +1,451 @@
+use std::cmp;
+use std::f64;
+
+use braille::Canvas;
+use super::time;
+
+/// How a `Scale` spaces values along its axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScaleKind {
+    /// Equal differences between values take up equal distances.
+    Linear,
+    /// Equal ratios between values take up equal distances. Only positive values can be shown.
+    Logarithmic,
+    /// Like `Linear`, for values that are times in seconds since the Unix epoch, as given by
+    /// `chart::timestamp`. Ticks fall on round numbers of seconds, minutes, hours or days, and
+    /// are labelled with the time or date in UTC.
+    Time,
+}
+
+/// A range of data values mapped onto one axis of a chart.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Scale {
+    pub min: f64,
+    pub max: f64,
+    pub kind: ScaleKind,
+}
+
+impl Scale {
+    /// Creates a new linear `Scale` covering the values from `min` to `max`.
+    pub fn new(min: f64, max: f64) -> Scale {
+        Scale { min, max, kind: ScaleKind::Linear }
+    }
+
+    /// Creates a new logarithmic `Scale` covering the values from `min` to `max`, both of which
+    /// should be positive.
+    pub fn log(min: f64, max: f64) -> Scale {
+        Scale { min, max, kind: ScaleKind::Logarithmic }
+    }
+
+    /// Creates a new time `Scale` covering the times from `min` to `max`, in seconds since the
+    /// Unix epoch.
+    ///
+    /// ```
+    /// use drawille::chart::Scale;
+    ///
+    /// // Ten minutes either side of midnight on the 2nd of January 2024.
+    /// let midnight = 1_704_153_600.0;
+    /// let scale = Scale::time(midnight - 600.0, midnight + 600.0);
+    /// let labels = scale.tick_labels(5).into_iter().map(|t| t.1).collect::<Vec<_>>();
+    /// assert_eq!(labels, ["23:50", "23:55", "01-02", "00:05", "00:10"]);
+    /// ```
+    pub fn time(min: f64, max: f64) -> Scale {
+        Scale { min, max, kind: ScaleKind::Time }
+    }
+
+    /// Creates a `Scale` just covering the finite values given.
+    ///
+    /// If there are no finite values, the scale covers zero to one; if they are all the same, the
+    /// scale extends one unit either side of them. Values so far apart that the distance between
+    /// them overflows are only covered to within a quarter of `f64::MAX` of zero.
+    ///
+    /// ```
+    /// use drawille::chart::{LineChart, Scale};
+    ///
+    /// let scale = Scale::fit(vec![std::f64::MAX, -std::f64::MAX]);
+    /// assert!((scale.max - scale.min).is_finite());
+    /// assert!((scale.nice(5).max - scale.nice(5).min).is_finite());
+    ///
+    /// let chart = LineChart::new(40, 20).series(&[std::f64::MAX, -std::f64::MAX]);
+    /// assert!(chart.frame().lines().next().unwrap().starts_with(" 5e307"));
+    /// ```
+    pub fn fit<I: IntoIterator<Item = f64>>(values: I) -> Scale {
+        match bounds(values) {
+            None => Scale::new(0.0, 1.0),
+            Some((min, max)) if min == max => Scale::new(min - 1.0, max + 1.0),
+            Some((min, max)) if !(max - min).is_finite() => {
+                let limit = f64::MAX / 4.0;
+                Scale::new(min.max(-limit), max.min(limit))
+            },
+            Some((min, max)) => Scale::new(min, max),
+        }
+    }
+
+    /// Creates a logarithmic `Scale` just covering the finite, positive values given.
+    ///
+    /// If there are no such values, the scale covers one to ten; if they are all the same, the
+    /// scale extends a factor of ten either side of them.
+    pub fn fit_log<I: IntoIterator<Item = f64>>(values: I) -> Scale {
+        match bounds(values.into_iter().filter(|&v| v > 0.0)) {
+            None => Scale::log(1.0, 10.0),
+            Some((min, max)) if min == max => Scale::log(min / 10.0, max * 10.0),
+            Some((min, max)) => Scale::log(min, max),
+        }
+    }
+
+    /// Returns a copy of the scale widened to start and end on a tick, for at most `max_ticks`
+    /// ticks.
+    ///
+    /// Logarithmic scales are widened to whole powers of ten.
+    pub fn nice(&self, max_ticks: usize) -> Scale {
+        match self.kind {
+            ScaleKind::Linear | ScaleKind::Time => {
+                let step = self.tick_step(max_ticks);
+                let (min, max) = ((self.min / step).floor() * step, (self.max / step).ceil() * step);
+                // Widening a scale near the limits of `f64` could overflow.
+                if !(max - min).is_finite() {
+                    return *self;
+                }
+                Scale { min, max, kind: self.kind }
+            },
+            ScaleKind::Logarithmic => {
+                Scale::log(10.0f64.powf(self.min.log10().floor()),
+                           10.0f64.powf(self.max.log10().ceil()))
+            },
+        }
+    }
+
+    /// Returns where `value` falls along the scale, from zero at `min` to one at `max`.
+    ///
+    /// On a logarithmic scale, non-positive values give `NaN`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        let (value, min, max) = match self.kind {
+            ScaleKind::Linear | ScaleKind::Time => (value, self.min, self.max),
+            ScaleKind::Logarithmic => {
+                let log = |v: f64| if v > 0.0 { v.log10() } else { f64::NAN };
+                (log(value), log(self.min), log(self.max))
+            },
+        };
+        if max == min {
+            0.5
+        } else {
+            // Halving first gives the same result, but can't overflow for values far outside the
+            // scale.
+            (value * 0.5 - min * 0.5) / (max * 0.5 - min * 0.5)
+        }
+    }
+
+    /// Returns how many pixels along an axis of `length` pixels `value` falls, starting from zero.
+    pub fn position(&self, value: f64, length: usize) -> f64 {
+        self.normalize(value) * length.saturating_sub(1) as f64
+    }
+
+    /// Returns the value that falls `pixel` pixels along an axis of `length` pixels.
+    pub fn value_at(&self, pixel: f64, length: usize) -> f64 {
+        let t = if length > 1 { pixel / (length - 1) as f64 } else { 0.5 };
+        match self.kind {
+            ScaleKind::Linear | ScaleKind::Time => self.min + t * (self.max - self.min),
+            ScaleKind::Logarithmic => self.min * (self.max / self.min).powf(t),
+        }
+    }
+
+    /// Returns the spacing between ticks for at most `max_ticks` ticks along a linear scale,
+    /// rounded to one, two or five times a power of ten, or along a time scale, rounded to a
+    /// number of seconds, minutes, hours or days.
+    pub fn tick_step(&self, max_ticks: usize) -> f64 {
+        let range = (self.max - self.min).abs();
+        if self.kind == ScaleKind::Time {
+            return time::step(range, max_ticks);
+        }
+        let intervals = if max_ticks > 1 { max_ticks - 1 } else { 1 };
+        let raw = range / intervals as f64;
+        if raw <= 0.0 || !raw.is_finite() {
+            return 1.0;
+        }
+        let magnitude = 10.0f64.powf(raw.log10().floor());
+        let step = match raw / magnitude {
+            n if n <= 1.0 => 1.0,
+            n if n <= 2.0 => 2.0,
+            n if n <= 5.0 => 5.0,
+            _ => 10.0,
+        };
+        step * magnitude
+    }
+
+    /// Returns evenly spaced, round values within the scale to place ticks at, no more than
+    /// `max_ticks` of them.
+    ///
+    /// Logarithmic scales have ticks at powers of ten, or at one, two and five times powers of ten
+    /// if they cover too few of them.
+    pub fn ticks(&self, max_ticks: usize) -> Vec<f64> {
+        if max_ticks == 0 {
+            return vec![];
+        }
+        if self.kind == ScaleKind::Logarithmic {
+            return self.log_ticks(max_ticks);
+        }
+        let step = self.tick_step(max_ticks);
+        let (low, high) = (self.min.min(self.max), self.min.max(self.max));
+        let mut ticks = vec![];
+        let mut i = (low / step).ceil();
+        while i * step <= high + step * 1e-9 && ticks.len() < max_ticks {
+            ticks.push(i * step);
+            i += 1.0;
+        }
+        ticks
+    }
+
+    fn log_ticks(&self, max_ticks: usize) -> Vec<f64> {
+        let (low, high) = (self.min.min(self.max), self.min.max(self.max));
+        if low <= 0.0 || low.is_nan() || !high.is_finite() {
+            return vec![];
+        }
+        let in_range = |v: f64| v >= low * (1.0 - 1e-9) && v <= high * (1.0 + 1e-9);
+        let (first, last) = (low.log10().floor() as i32, high.log10().ceil() as i32);
+        let powers = (first..last + 1).map(|e| 10.0f64.powi(e)).filter(|&v| in_range(v))
+                                      .collect::<Vec<_>>();
+        if powers.len() < 2 {
+            let multiples = (first..last + 1).flat_map(|e| {
+                [1.0, 2.0, 5.0].iter().map(move |m| m * 10.0f64.powi(e))
+            }).filter(|&v| in_range(v)).collect::<Vec<_>>();
+            if multiples.len() <= max_ticks {
+                return multiples;
+            }
+        }
+        // Thin out the powers of ten evenly if there are too many of them.
+        let every = powers.len().div_ceil(max_ticks).max(1);
+        powers.into_iter().step_by(every).collect()
+    }
+
+    /// Returns the ticks for at most `max_ticks` ticks along the scale, paired with their labels.
+    pub fn tick_labels(&self, max_ticks: usize) -> Vec<(f64, String)> {
+        let ticks = self.ticks(max_ticks);
+        match self.kind {
+            ScaleKind::Linear => {
+                let step = self.tick_step(max_ticks);
+                ticks.into_iter().map(|t| (t, format_tick(t, step))).collect()
+            },
+            ScaleKind::Logarithmic => {
+                ticks.into_iter().map(|t| (t, format_log_tick(t))).collect()
+            },
+            ScaleKind::Time => {
+                let step = self.tick_step(max_ticks);
+                ticks.into_iter().map(|t| (t, time::format(t, step))).collect()
+            },
+        }
+    }
+}
+
+/// Formats a tick value using just enough decimal places to tell apart ticks `step` apart,
+/// switching to exponent notation for values too large to write out in full.
+pub fn format_tick(value: f64, step: f64) -> String {
+    if value.abs() >= 1e15 {
+        return format!("{:e}", value);
+    }
+    let decimals = if step > 0.0 && step < 1.0 {
+        (-step.log10().floor()) as usize
+    } else {
+        0
+    };
+    // Avoid printing `-0` for values that are only negative due to rounding error.
+    let value = if value.abs() < step * 1e-9 { 0.0 } else { value };
+    format!("{:.*}", decimals, value)
+}
+
+/// Returns the smallest and largest of the finite values given, if there are any.
+fn bounds<I: IntoIterator<Item = f64>>(values: I) -> Option<(f64, f64)> {
+    values.into_iter().filter(|v| v.is_finite()).fold(None, |bounds, v| match bounds {
+        None => Some((v, v)),
+        Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+    })
+}
+
+/// Formats a tick value on a logarithmic scale, switching to exponent notation for very large or
+/// small values.
+fn format_log_tick(value: f64) -> String {
+    if (1e-4..1e6).contains(&value) {
+        let decimals = cmp::max(0, -(value.log10().floor() as i32)) as usize;
+        format!("{:.*}", decimals, value)
+    } else {
+        format!("{:e}", value)
+    }
+}
+
+/// How one axis of a chart is configured: its kind of scale and, optionally, a fixed range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AxisSpec {
+    pub range: Option<(f64, f64)>,
+    pub kind: ScaleKind,
+}
+
+impl AxisSpec {
+    pub fn new() -> AxisSpec {
+        AxisSpec { range: None, kind: ScaleKind::Linear }
+    }
+
+    /// Returns the range set for this axis, or else the smallest and largest of the `values` it can
+    /// show, if there are any.
+    pub fn extent<I: IntoIterator<Item = f64>>(&self, values: I) -> Option<(f64, f64)> {
+        let log = self.kind == ScaleKind::Logarithmic;
+        self.range.or_else(|| bounds(values.into_iter().filter(|&v| !log || v > 0.0)))
+    }
+
+    /// Returns the scale for this axis, fitting it to `values` if no range was given and widening
+    /// it to whole ticks if `nice` is set.
+    pub fn scale<I: IntoIterator<Item = f64>>(&self, values: I, nice: Option<usize>) -> Scale {
+        match (self.range, self.kind) {
+            (Some((min, max)), ScaleKind::Linear) => Scale::new(min, max),
+            (Some((min, max)), ScaleKind::Logarithmic) => Scale::log(min, max),
+            (Some((min, max)), ScaleKind::Time) => Scale::time(min, max),
+            (None, kind) => {
+                let fit = match kind {
+                    ScaleKind::Linear => Scale::fit(values),
+                    ScaleKind::Logarithmic => Scale::fit_log(values),
+                    ScaleKind::Time => Scale { kind, ..Scale::fit(values) },
+                };
+                match nice {
+                    Some(max_ticks) => fit.nice(max_ticks),
+                    None => fit,
+                }
+            },
+        }
+    }
+}
+
+/// The region of a canvas that a chart plots its data in, along with the scales of its axes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlotArea {
+    /// The pixel column of the left edge of the area.
+    pub left: usize,
+    /// The pixel row of the top edge of the area.
+    pub top: usize,
+    /// The width of the area in pixels.
+    pub width: usize,
+    /// The height of the area in pixels.
+    pub height: usize,
+    pub x_scale: Scale,
+    pub y_scale: Scale,
+}
+
+impl PlotArea {
+    /// Returns the canvas position of a data point, before rounding to a whole pixel.
+    ///
+    /// Larger y values are placed higher up the canvas.
+    pub fn project(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.left as f64 + self.x_scale.position(x, self.width),
+         self.top as f64 + (self.height - 1) as f64 - self.y_scale.position(y, self.height))
+    }
+
+    /// Returns the pixel a data point falls on, or `None` if it falls outside the area.
+    pub fn pixel(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        let (px, py) = self.project(x, y);
+        let (px, py) = (px.round(), py.round());
+        if px >= self.left as f64 && px < (self.left + self.width) as f64 &&
+           py >= self.top as f64 && py < (self.top + self.height) as f64 {
+            Some((px as usize, py as usize))
+        } else {
+            None
+        }
+    }
+}
+
+/// Draws labelled axes for a chart `width` by `height` pixels onto the `Canvas`, returning the area
+/// left for plotting data, or `None` if the chart is too small to plot anything.
+///
+/// The y axis is labelled on the left and the x axis along the bottom row of characters.
+pub fn draw_axes(canvas: &mut Canvas, width: usize, height: usize, x_scale: Scale, y_scale: Scale)
+                 -> Option<PlotArea> {
+    draw_chart_axes(canvas, (0, 0), width, height, x_scale, y_scale, None).map(|(area, _)| area)
+}
+
+/// Like `draw_axes`, but also draws a secondary y axis, labelled on the right, for overlaying series
+/// of a different magnitude.
+///
+/// Returns the plot area along with the same area scaled to the secondary axis.
+pub fn draw_dual_axes(canvas: &mut Canvas, width: usize, height: usize, x_scale: Scale,
+                      y_scale: Scale, secondary_scale: Scale) -> Option<(PlotArea, PlotArea)> {
+    draw_chart_axes(canvas, (0, 0), width, height, x_scale, y_scale, Some(secondary_scale))
+        .map(|(area, secondary)| (area, secondary.unwrap()))
+}
+
+/// Draws the axes of a chart `width` by `height` pixels whose top left corner is at pixel
+/// `(left, top)`, which should be the corner of a character.
+pub fn draw_chart_axes(canvas: &mut Canvas, (left, top): (usize, usize), width: usize,
+                       height: usize, x_scale: Scale, y_scale: Scale,
+                       secondary_scale: Option<Scale>) -> Option<(PlotArea, Option<PlotArea>)> {
+    let (cols, rows) = (width / 2, height / 4);
+    if rows < 2 || cols < 2 {
+        return None;
+    }
+    let max_y_ticks = max_y_ticks(height);
+    let label_width = |labels: &[(f64, String)]| {
+        labels.iter().map(|l| l.1.chars().count()).max().unwrap_or(0) + 1
+    };
+    let y_labels = y_scale.tick_labels(max_y_ticks);
+    let label_cols = label_width(&y_labels);
+    let secondary_labels = secondary_scale.map(|s| s.tick_labels(max_y_ticks));
+    let right_cols = secondary_labels.as_ref().map_or(0, |l| label_width(l));
+
+    let axis_x = cmp::min(label_cols, cols - 1) * 2;
+    let axis_y = (rows - 1) * 4 - 1;
+    // Without a secondary axis, the plot extends to the right edge of the chart.
+    let right = match secondary_scale {
+        Some(_) => (cols.saturating_sub(right_cols) * 2).saturating_sub(1),
+        None => width,
+    };
+    if right < axis_x + 3 || axis_y < 2 {
+        return None;
+    }
+    let area = PlotArea {
+        left: left + axis_x + 1,
+        top,
+        width: right - axis_x - 1,
+        height: axis_y,
+        x_scale,
+        y_scale,
+    };
+
+    let (axis_x, axis_y, right) = (left + axis_x, top + axis_y, left + right);
+    canvas.line(axis_x, top, axis_x, axis_y);
+    canvas.line(axis_x, axis_y, right - 1, axis_y);
+    for &(tick, ref label) in y_labels.iter() {
+        let y = area.project(x_scale.min, tick).1.round() as usize;
+        canvas.set(axis_x - 1, y);
+        let start = label_cols - 1 - label.chars().count();
+        canvas.text(left + start * 2, y, label);
+    }
+
+    let secondary = secondary_scale.map(|scale| PlotArea { y_scale: scale, ..area });
+    if let (Some(secondary), Some(labels)) = (secondary, secondary_labels) {
+        canvas.line(right, top, right, axis_y);
+        for &(tick, ref label) in labels.iter() {
+            let y = secondary.project(x_scale.min, tick).1.round() as usize;
+            canvas.set(right + 1, y);
+            canvas.text(right + 3, y, label);
+        }
+    }
+
+    let x_labels = |max_ticks| x_scale.tick_labels(max_ticks);
+    let max_x_label = label_width(&x_labels(2));
+    let x_labels = x_labels(cmp::max(2, (area.width / 2) / (max_x_label + 1)));
+    let mut next_free_col = 0;
+    for &(tick, ref label) in x_labels.iter() {
+        let x = area.project(tick, y_scale.min).0.round();
+        if !x.is_finite() {
+            continue;
+        }
+        let len = label.chars().count();
+        let col = (x.max(0.0) as usize).saturating_sub(left) / 2;
+        let start = cmp::min(col.saturating_sub(len / 2), cols.saturating_sub(len));
+        if start >= next_free_col {
+            canvas.text(left + start * 2, top + rows * 4 - 1, label);
+            next_free_col = start + len + 1;
+        }
+    }
+    Some((area, secondary))
+}
+
+/// The most ticks to put on the y axis of a chart `height` pixels high.
+pub fn max_y_ticks(height: usize) -> usize {
+    cmp::max(2, (height / 4).div_ceil(2))
+}
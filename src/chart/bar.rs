@@ -0,0 +1,260 @@
+use std::cmp;
+
+use block::Color;
+use braille::Canvas;
+use dither;
+use super::{Chart, ChartLayout, LegendEntry, PlotArea, Scale, max_y_ticks};
+use super::area::{FILL_COLORS, rows};
+use super::axis::AxisSpec;
+
+/// The dot densities used to fill successive series of a bar chart, so that series can be told
+/// apart without colour.
+static BAR_DENSITIES: [f64; 4] = [1.0, 0.25, 0.625, 0.4375];
+
+/// How a `BarChart` with more than one series arranges their bars.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BarMode {
+    /// The series’ bars for each category stand side by side.
+    Grouped,
+    /// Each series’ bar stands on top of the bars of the series added before it.
+    Stacked,
+}
+
+/// A bar chart of one or more series of values, one value per category, with the categories named
+/// along the x axis.
+///
+/// Each series is given a colour of its own, unless it was added with one, and filled with dots
+/// at a different density, so that series can be told apart without colour too. Since colours
+/// apply to whole Braille characters, bars of different series that share a character take the
+/// colour of the series added last. Negative values stand down from zero.
+///
+/// ```
+/// use drawille::chart::BarChart;
+///
+/// let chart = BarChart::new(80, 32).categories(&["cpu0", "cpu1", "cpu2"])
+///                                  .series(&[30.0, 45.0, 12.0])
+///                                  .series(&[10.0, 15.0, 4.0])
+///                                  .series(&[5.0, 2.0, 20.0])
+///                                  .stacked()
+///                                  .labels(&["user", "system", "iowait"]);
+/// println!("{}", chart.frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BarChart {
+    width: usize,
+    height: usize,
+    categories: Vec<String>,
+    series: Vec<BarSeries>,
+    mode: BarMode,
+    y_axis: AxisSpec,
+    layout: ChartLayout,
+    labels: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct BarSeries {
+    values: Vec<f64>,
+    color: Option<Color>,
+}
+
+impl BarChart {
+    /// Creates a new, empty `BarChart` with the given width and height in pixels, including its
+    /// axes and their labels. Bars are grouped until `stacked` is called.
+    pub fn new(width: usize, height: usize) -> BarChart {
+        BarChart {
+            width,
+            height,
+            categories: vec![],
+            series: vec![],
+            mode: BarMode::Grouped,
+            y_axis: AxisSpec::new(),
+            layout: ChartLayout::new(width, height),
+            labels: vec![],
+        }
+    }
+
+    /// Names the categories along the x axis, and returns the chart for use again.
+    ///
+    /// Every series gives one value for each category, in the same order. Names too long for the
+    /// space under their bars are cut short.
+    pub fn categories<S: AsRef<str>>(mut self, categories: &[S]) -> BarChart {
+        self.categories = categories.iter().map(|c| c.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Adds a series of values, one for each category, and returns the chart for use again.
+    pub fn series(mut self, values: &[f64]) -> BarChart {
+        self.series.push(BarSeries { values: values.to_vec(), color: None });
+        self
+    }
+
+    /// Adds a series of values drawn in the given colour, and returns the chart for use again.
+    pub fn colored_series(mut self, values: &[f64], color: Color) -> BarChart {
+        self.series.push(BarSeries { values: values.to_vec(), color: Some(color) });
+        self
+    }
+
+    /// Stacks each series’ bars on top of those of the series added before it, and returns the
+    /// chart for use again.
+    pub fn stacked(self) -> BarChart {
+        self.mode(BarMode::Stacked)
+    }
+
+    /// Sets how the bars of different series are arranged, and returns the chart for use again.
+    pub fn mode(mut self, mode: BarMode) -> BarChart {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the range of the y axis instead of fitting it to the data, and returns the chart for
+    /// use again.
+    pub fn y_range(mut self, min: f64, max: f64) -> BarChart {
+        self.y_axis.range = Some((min, max));
+        self
+    }
+
+    /// Sets the title shown above the chart, and returns the chart for use again.
+    pub fn title<S: Into<String>>(mut self, title: S) -> BarChart {
+        self.layout = self.layout.title(title);
+        self
+    }
+
+    /// Sets the title of the y axis, and returns the chart for use again.
+    pub fn y_title<S: Into<String>>(mut self, title: S) -> BarChart {
+        self.layout = self.layout.y_title(title);
+        self
+    }
+
+    /// Names the series in the order they were added, listing them in a legend, and returns the
+    /// chart for use again.
+    pub fn labels<S: AsRef<str>>(mut self, labels: &[S]) -> BarChart {
+        self.labels = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Returns the number of categories: those named, or the most values in any series if that’s
+    /// more.
+    fn category_count(&self) -> usize {
+        self.series.iter().map(|s| s.values.len()).fold(self.categories.len(), cmp::max)
+    }
+
+    /// Returns the colour of the series with the given index.
+    fn color(&self, index: usize) -> Color {
+        self.series[index].color.unwrap_or(FILL_COLORS[index % FILL_COLORS.len()])
+    }
+
+    /// Returns the bottom and top of each series’ bar in a category, with non-finite values
+    /// counting as zero.
+    fn bars(&self, category: usize) -> Vec<(f64, f64)> {
+        let (mut above, mut below) = (0.0, 0.0);
+        self.series.iter().map(|series| {
+            let value = series.values.get(category).cloned().filter(|v| v.is_finite())
+                                                   .unwrap_or(0.0);
+            match self.mode {
+                BarMode::Grouped => (0.0, value),
+                BarMode::Stacked if value >= 0.0 => {
+                    above += value;
+                    (above - value, above)
+                },
+                BarMode::Stacked => {
+                    below += value;
+                    (below - value, below)
+                },
+            }
+        }).collect()
+    }
+
+    /// Draws the chart onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let legend = self.labels.iter().enumerate().filter(|&(i, _)| i < self.series.len())
+            .map(|(i, label)| LegendEntry::colored(label.clone(), self.color(i)))
+            .collect();
+        let layout = self.layout.clone().legend(legend);
+        let count = self.category_count();
+        let ends = (0..count).flat_map(|c| self.bars(c)).flat_map(|(a, b)| vec![a, b]);
+        let y_scale = self.y_axis.scale(ends.chain(Some(0.0)),
+                                        Some(max_y_ticks(layout.axes_height())));
+        let x_scale = Scale::new(-0.5, count as f64 - 0.5);
+        let area = match layout.draw(canvas, x_scale, y_scale) {
+            Some(area) => area,
+            None => return,
+        };
+        if count == 0 {
+            return;
+        }
+
+        // The numbered x axis ticks give way to the names of the categories.
+        let label_row = (area.top + area.height) / 4 + 1;
+        canvas.clear_region(0, label_row * 4, area.left + area.width, 4);
+        for category in 0..count {
+            let (start, end) = slot(&area, category, count);
+            if let Some(name) = self.categories.get(category) {
+                let cols = (end / 2).saturating_sub(start.div_ceil(2));
+                let name = name.chars().take(cols).collect::<String>();
+                let col = start.div_ceil(2) + (cols - name.chars().count()) / 2;
+                canvas.text(col * 2, label_row * 4, name);
+            }
+
+            // A gap either side of the bars of each category.
+            let gap = (end - start) / 8;
+            let (start, end) = (start + gap, cmp::max(end - gap, start + gap + 1));
+            for (i, (bottom, top)) in self.bars(category).into_iter().enumerate() {
+                let (left, right) = match self.mode {
+                    BarMode::Grouped => {
+                        let n = self.series.len();
+                        let width = end - start;
+                        (start + width * i / n, cmp::max(start + width * (i + 1) / n,
+                                                         start + width * i / n + 1))
+                    },
+                    BarMode::Stacked => (start, end),
+                };
+                if bottom != top {
+                    self.fill_bar(canvas, &area, i, (left, right), (bottom, top));
+                }
+            }
+        }
+    }
+
+    /// Fills the bar of a series between pixel columns `left` and `right`, not including `right`,
+    /// and between the values `bottom` and `top`.
+    fn fill_bar(&self, canvas: &mut Canvas, area: &PlotArea, series: usize,
+                (left, right): (usize, usize), (bottom, top): (f64, f64)) {
+        let (top_px, bottom_px) = match rows(area, 0.0, bottom.min(top), bottom.max(top)) {
+            Some(rows) => rows,
+            None => return,
+        };
+        let (color, density) = (self.color(series), BAR_DENSITIES[series % BAR_DENSITIES.len()]);
+        for x in left..cmp::min(right, area.left + area.width) {
+            for y in top_px..bottom_px + 1 {
+                // Outlined, so that the sparsest fills still show where the bar ends.
+                let edge = x == left || x + 1 == right || y == top_px || y == bottom_px;
+                if edge || dither::is_set(x, y, density) {
+                    canvas.set_colored(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Draws the chart to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+/// Returns the pixel columns taken up by a category, from the first up to but not including the
+/// last.
+fn slot(area: &PlotArea, category: usize, count: usize) -> (usize, usize) {
+    (area.left + area.width * category / count, area.left + area.width * (category + 1) / count)
+}
+
+impl Chart for BarChart {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        BarChart::draw(self, canvas);
+    }
+}
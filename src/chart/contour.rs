@@ -0,0 +1,116 @@
+use braille::Canvas;
+use super::{Scale, draw_axes};
+
+/// Traces where a grid of samples crosses `level`, using marching squares, and returns the
+/// crossing as line segments.
+///
+/// `values` is a grid of rows of samples, all the same length, with the first row at the top. The
+/// ends of each segment are given as `(column, row)` positions within the grid, interpolated
+/// between the samples either side of the crossing. Cells with a sample that isn’t finite are
+/// skipped.
+///
+/// ```
+/// use drawille::chart::contour_lines;
+///
+/// let values = vec![vec![0.0, 0.0], vec![2.0, 2.0]];
+/// assert_eq!(contour_lines(&values, 1.0), vec![[(0.0, 0.5), (1.0, 0.5)]]);
+/// ```
+pub fn contour_lines(values: &[Vec<f64>], level: f64) -> Vec<[(f64, f64); 2]> {
+    let mut segments = vec![];
+    for (r, pair) in values.windows(2).enumerate() {
+        let (upper, lower) = (&pair[0], &pair[1]);
+        let cols = upper.len().min(lower.len());
+        for c in 0..cols.saturating_sub(1) {
+            let (tl, tr, br, bl) = (upper[c], upper[c + 1], lower[c + 1], lower[c]);
+            if !(tl.is_finite() && tr.is_finite() && br.is_finite() && bl.is_finite()) {
+                continue;
+            }
+            let (x, y) = (c as f64, r as f64);
+            let cross = |a: f64, b: f64| if a == b { 0.5 } else { (level - a) / (b - a) };
+            let top = (x + cross(tl, tr), y);
+            let right = (x + 1.0, y + cross(tr, br));
+            let bottom = (x + cross(bl, br), y + 1.0);
+            let left = (x, y + cross(tl, bl));
+
+            let above = |v: f64| (v > level) as usize;
+            let case = above(tl) << 3 | above(tr) << 2 | above(br) << 1 | above(bl);
+            // Saddles are resolved by whether the centre of the cell is above the level.
+            let centre_above = (tl + tr + br + bl) / 4.0 > level;
+            match case {
+                1 | 14 => segments.push([left, bottom]),
+                2 | 13 => segments.push([bottom, right]),
+                3 | 12 => segments.push([left, right]),
+                4 | 11 => segments.push([top, right]),
+                6 | 9 => segments.push([top, bottom]),
+                7 | 8 => segments.push([left, top]),
+                5 if centre_above => segments.extend(vec![[left, top], [bottom, right]]),
+                5 => segments.extend(vec![[left, bottom], [top, right]]),
+                10 if centre_above => segments.extend(vec![[top, right], [left, bottom]]),
+                10 => segments.extend(vec![[left, top], [bottom, right]]),
+                _ => {},
+            }
+        }
+    }
+    segments
+}
+
+/// Draws the contours of a grid of samples at each of `levels` onto the `Canvas`, stretching the
+/// grid over `width` by `height` pixels with its top left corner at the origin.
+///
+/// See `contour_lines` for how the grid is laid out.
+pub fn draw_contours(canvas: &mut Canvas, values: &[Vec<f64>], levels: &[f64], width: usize,
+                     height: usize) {
+    let cols = values.iter().map(|row| row.len()).min().unwrap_or(0);
+    let rows = values.len();
+    if cols < 2 || rows < 2 || width == 0 || height == 0 {
+        return;
+    }
+    let x_step = (width - 1) as f64 / (cols - 1) as f64;
+    let y_step = (height - 1) as f64 / (rows - 1) as f64;
+    draw_segments(canvas, values, levels, (0, 0), (x_step, y_step));
+}
+
+/// Plots the contours of `f(x, y)` over `x_range` by `y_range` at each of `levels`, with labelled
+/// axes, as a chart `size.0` by `size.1` pixels, and returns it as a `String`.
+///
+/// `f` is sampled once per pixel of the plot. Plotting the single level zero draws the implicit
+/// curve `f(x, y) = 0`.
+///
+/// ```
+/// use drawille::chart::plot_contours;
+///
+/// // A circle and the folium of Descartes.
+/// println!("{}", plot_contours(|x, y| x * x + y * y - 4.0, (-3.0, 3.0), (-3.0, 3.0), &[0.0],
+///                              (60, 40)));
+/// println!("{}", plot_contours(|x, y| x.powi(3) + y.powi(3) - 3.0 * x * y, (-3.0, 3.0),
+///                              (-3.0, 3.0), &[0.0], (60, 40)));
+/// ```
+pub fn plot_contours<F: Fn(f64, f64) -> f64>(f: F, x_range: (f64, f64), y_range: (f64, f64),
+                                             levels: &[f64], size: (usize, usize)) -> String {
+    let mut canvas = Canvas::new(size.0, size.1);
+    let x_scale = Scale::new(x_range.0, x_range.1);
+    let y_scale = Scale::new(y_range.0, y_range.1);
+    if let Some(area) = draw_axes(&mut canvas, size.0, size.1, x_scale, y_scale) {
+        let values = (0..area.height).map(|py| {
+            let y = y_scale.value_at((area.height - 1 - py) as f64, area.height);
+            (0..area.width).map(|px| f(x_scale.value_at(px as f64, area.width), y)).collect()
+        }).collect::<Vec<Vec<f64>>>();
+        draw_segments(&mut canvas, &values, levels, (area.left, area.top), (1.0, 1.0));
+    }
+    canvas.frame()
+}
+
+/// Draws the contours of a grid of samples, placing sample `(column, row)` at pixel
+/// `(left + column * x_step, top + row * y_step)`.
+fn draw_segments(canvas: &mut Canvas, values: &[Vec<f64>], levels: &[f64],
+                 (left, top): (usize, usize), (x_step, y_step): (f64, f64)) {
+    let pixel = |(x, y): (f64, f64)| {
+        ((left as f64 + x * x_step).round() as usize, (top as f64 + y * y_step).round() as usize)
+    };
+    for &level in levels.iter() {
+        for segment in contour_lines(values, level) {
+            let ((x1, y1), (x2, y2)) = (pixel(segment[0]), pixel(segment[1]));
+            canvas.line(x1, y1, x2, y2);
+        }
+    }
+}
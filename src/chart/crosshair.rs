@@ -0,0 +1,89 @@
+use braille::{Canvas, LineStyle, Marker};
+use super::PlotArea;
+
+/// What a crosshair found at a point of a chart: the data values under it, and the points of each
+/// series nearest it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crosshair {
+    /// The x value under the crosshair.
+    pub x: f64,
+    /// The y value under the crosshair.
+    pub y: f64,
+    /// For each series, in the order given, the index and `(x, y)` value of its point nearest the
+    /// crosshair, or `None` if it has no points that can be shown.
+    pub nearest: Vec<Option<(usize, (f64, f64))>>,
+}
+
+/// Draws a crosshair over the plot area at pixel `(x, y)`—dotted lines across the whole area
+/// through that pixel, and a square around the point of each series nearest it—and returns what
+/// it found, or `None` if the pixel is outside the area.
+///
+/// The nearest point of a series is the one closest across, on the canvas, with the one closest
+/// up or down breaking ties, which suits reading off values from series plotted against time.
+/// Together with `input::Mouse::pixel` this gives charts the mouse can be hovered over.
+///
+/// ```
+/// use drawille::braille::Canvas;
+/// use drawille::chart::{self, Scale};
+///
+/// let mut canvas = Canvas::new(80, 40);
+/// let area = chart::draw_axes(&mut canvas, 80, 40, Scale::new(0.0, 10.0), Scale::new(0.0, 100.0))
+///     .unwrap();
+/// let squares = (0..11).map(|x| (x as f64, (x * x) as f64)).collect::<Vec<_>>();
+/// let (px, py) = area.pixel(3.2, 50.0).unwrap();
+/// let found = chart::crosshair(&mut canvas, &area, (px, py), &[&squares]).unwrap();
+/// assert_eq!(found.nearest, vec![Some((3, (3.0, 9.0)))]);
+/// ```
+pub fn crosshair(canvas: &mut Canvas, area: &PlotArea, (x, y): (usize, usize),
+                 series: &[&[(f64, f64)]]) -> Option<Crosshair> {
+    let (right, bottom) = (area.left + area.width, area.top + area.height);
+    if x < area.left || x >= right || y < area.top || y >= bottom {
+        return None;
+    }
+    let style = LineStyle::Dotted;
+    for py in (area.top..bottom).filter(|&py| style.draws(py - area.top)) {
+        canvas.set(x, py);
+    }
+    for px in (area.left..right).filter(|&px| style.draws(px - area.left)) {
+        canvas.set(px, y);
+    }
+
+    let nearest = series.iter().map(|series| mark_nearest(canvas, area, series, (x, y))).collect();
+    let column = (x - area.left) as f64;
+    let row = (area.height - 1 - (y - area.top)) as f64;
+    Some(Crosshair {
+        x: area.x_scale.value_at(column, area.width),
+        y: area.y_scale.value_at(row, area.height),
+        nearest,
+    })
+}
+
+/// Finds the point of a series nearest a crosshair at pixel `(x, y)` and draws a square around it,
+/// returning its index and value.
+pub fn mark_nearest(canvas: &mut Canvas, area: &PlotArea, series: &[(f64, f64)],
+                    (x, y): (usize, usize)) -> Option<(usize, (f64, f64))> {
+    let found = nearest(area, series, (x as f64, y as f64));
+    if let Some((_, (vx, vy))) = found {
+        if let Some((px, py)) = area.pixel(vx, vy) {
+            canvas.draw_marker(px, py, Marker::Square(1));
+        }
+    }
+    found
+}
+
+/// Returns the index and value of the point of a series nearest a canvas position.
+fn nearest(area: &PlotArea, series: &[(f64, f64)], (x, y): (f64, f64))
+           -> Option<(usize, (f64, f64))> {
+    let mut best: Option<(f64, f64, usize)> = None;
+    for (i, &(vx, vy)) in series.iter().enumerate() {
+        let (px, py) = area.project(vx, vy);
+        if !px.is_finite() || !py.is_finite() {
+            continue;
+        }
+        let (across, down) = ((px - x).abs(), (py - y).abs());
+        if best.is_none_or(|(a, d, _)| across < a || (across == a && down < d)) {
+            best = Some((across, down, i));
+        }
+    }
+    best.map(|(_, _, i)| (i, series[i]))
+}
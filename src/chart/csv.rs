@@ -0,0 +1,216 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::Path;
+
+use super::{LineChart, Scatter};
+
+/// The size, in pixels, of the charts drawn by `from_csv` and `scatter_from_csv`.
+const CSV_CHART_SIZE: (usize, usize) = (120, 48);
+
+/// A column of a CSV file, chosen either by its position or by the name in its header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The column at the given position, counting from zero.
+    Index(usize),
+    /// The column with the given name in the first row.
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Column {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Column {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Column {
+        Column::Name(name)
+    }
+}
+
+/// An error from reading a chart’s data out of a CSV file.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The file couldn’t be read.
+    Io(io::Error),
+    /// The file has no column with the given name.
+    MissingColumn(String),
+    /// The field in the given column on the given line isn’t a number.
+    Parse { line: usize, column: usize },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvError::Io(ref err) => write!(f, "couldn’t read CSV file: {}", err),
+            CsvError::MissingColumn(ref name) => write!(f, "no CSV column named {:?}", name),
+            CsvError::Parse { line, column } => {
+                write!(f, "column {} on line {} isn’t a number", column, line)
+            },
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            CsvError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> CsvError {
+        CsvError::Io(err)
+    }
+}
+
+/// The `(x, y)` points read from two columns of a CSV file, along with the columns' names if it
+/// has a header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvSeries {
+    pub points: Vec<(f64, f64)>,
+    pub x_name: Option<String>,
+    pub y_name: Option<String>,
+}
+
+impl CsvSeries {
+    /// Reads the points in columns `x` and `y` from CSV text.
+    ///
+    /// The first row is taken to be a header if either column is chosen by name, or if its
+    /// fields in the chosen columns aren’t numbers. Blank lines are skipped, and empty fields are
+    /// read as `NaN`, which charts leave out. Fields may be quoted, with doubled quotes standing
+    /// for a quote within them.
+    ///
+    /// ```
+    /// use drawille::chart::CsvSeries;
+    ///
+    /// let series = CsvSeries::parse("t,\"temp, °C\"\n0,21.5\n1,22\n", 0, "temp, °C").unwrap();
+    /// assert_eq!(series.points, vec![(0.0, 21.5), (1.0, 22.0)]);
+    /// assert_eq!(series.x_name, Some("t".to_string()));
+    /// ```
+    pub fn parse<X, Y>(source: &str, x: X, y: Y) -> Result<CsvSeries, CsvError>
+        where X: Into<Column>, Y: Into<Column> {
+        let (x, y) = (x.into(), y.into());
+        let mut rows = source.lines().enumerate().filter(|l| !l.1.trim().is_empty())
+                                     .map(|(i, line)| (i + 1, split_fields(line)));
+        let (first_line, first) = match rows.next() {
+            Some(row) => row,
+            None => return Ok(CsvSeries { points: vec![], x_name: None, y_name: None }),
+        };
+
+        let by_name = |column: &Column| match *column {
+            Column::Index(_) => false,
+            Column::Name(_) => true,
+        };
+        let numeric = |column: &Column| match *column {
+            Column::Index(i) => first.get(i).is_none_or(|f| parse_field(f).is_some()),
+            Column::Name(_) => false,
+        };
+        let has_header = by_name(&x) || by_name(&y) || !(numeric(&x) && numeric(&y));
+        let index = |column: &Column| match *column {
+            Column::Index(i) => Ok(i),
+            Column::Name(ref name) if has_header => {
+                first.iter().position(|f| f == name)
+                     .ok_or_else(|| CsvError::MissingColumn(name.clone()))
+            },
+            Column::Name(ref name) => Err(CsvError::MissingColumn(name.clone())),
+        };
+        let (xi, yi) = (index(&x)?, index(&y)?);
+
+        let mut series = CsvSeries { points: vec![], x_name: None, y_name: None };
+        if has_header {
+            series.x_name = first.get(xi).cloned();
+            series.y_name = first.get(yi).cloned();
+        }
+        let data = if has_header { None } else { Some((first_line, first.clone())) };
+        for (line, fields) in data.into_iter().chain(rows) {
+            let field = |i: usize| {
+                let field = fields.get(i).map_or("", |f| &f[..]);
+                parse_field(field).ok_or(CsvError::Parse { line, column: i })
+            };
+            series.points.push((field(xi)?, field(yi)?));
+        }
+        Ok(series)
+    }
+
+    /// Reads the points in columns `x` and `y` from the CSV file at `path`, as `parse` does.
+    pub fn read<P: AsRef<Path>, X, Y>(path: P, x: X, y: Y) -> Result<CsvSeries, CsvError>
+        where X: Into<Column>, Y: Into<Column> {
+        CsvSeries::parse(&fs::read_to_string(path)?, x, y)
+    }
+}
+
+/// Reads columns `x` and `y` of the CSV file at `path`, and returns them drawn as a line chart,
+/// with the axes titled by the columns' names if the file has a header.
+///
+/// ```no_run
+/// println!("{}", drawille::chart::from_csv("data.csv", 0, 1).unwrap());
+/// ```
+pub fn from_csv<P: AsRef<Path>, X, Y>(path: P, x: X, y: Y) -> Result<String, CsvError>
+    where X: Into<Column>, Y: Into<Column> {
+    let series = CsvSeries::read(path, x, y)?;
+    let mut chart = LineChart::new(CSV_CHART_SIZE.0, CSV_CHART_SIZE.1).series_xy(&series.points);
+    if let Some(name) = series.x_name {
+        chart = chart.x_title(name);
+    }
+    if let Some(name) = series.y_name {
+        chart = chart.y_title(name);
+    }
+    Ok(chart.frame())
+}
+
+/// Like `from_csv`, but draws the points as a scatter plot.
+pub fn scatter_from_csv<P: AsRef<Path>, X, Y>(path: P, x: X, y: Y) -> Result<String, CsvError>
+    where X: Into<Column>, Y: Into<Column> {
+    let series = CsvSeries::read(path, x, y)?;
+    let mut plot = Scatter::new(CSV_CHART_SIZE.0, CSV_CHART_SIZE.1).points(&series.points);
+    if let Some(name) = series.x_name {
+        plot = plot.x_title(name);
+    }
+    if let Some(name) = series.y_name {
+        plot = plot.y_title(name);
+    }
+    Ok(plot.frame())
+}
+
+/// Parses a field as a number, reading an empty field as `NaN`.
+fn parse_field(field: &str) -> Option<f64> {
+    let field = field.trim();
+    if field.is_empty() {
+        Some(f64::NAN)
+    } else {
+        field.parse().ok()
+    }
+}
+
+/// Splits a line of CSV into its fields, unquoting any quoted fields.
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
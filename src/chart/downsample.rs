@@ -0,0 +1,159 @@
+use std::cmp;
+
+/// How a `LineChart` thins out series with more points than its plot has columns of pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Downsampling {
+    /// Every point is drawn.
+    None,
+    /// Points are picked with `lttb`, one per column.
+    Lttb,
+    /// The lowest and highest points in each column are kept, with `min_max`.
+    MinMax,
+}
+
+/// Picks `threshold` of the points of a series, sorted by x, that keep its shape when drawn, using
+/// the Largest-Triangle-Three-Buckets algorithm.
+///
+/// The first and last points are always kept. The points between are split into
+/// `threshold - 2` buckets of about the same number of points, and from each the point making the
+/// largest triangle with the point picked before it and the average of the next bucket is picked,
+/// which tends to keep peaks and troughs. Non-finite points are left out; a series with no more
+/// than `threshold` finite points is returned as it is.
+///
+/// ```
+/// use drawille::chart;
+///
+/// let wave = (0..10_000).map(|i| (i as f64, (i as f64 / 500.0).sin())).collect::<Vec<_>>();
+/// let thinned = chart::lttb(&wave, 200);
+/// assert_eq!(thinned.len(), 200);
+/// assert_eq!((thinned[0], thinned[199]), (wave[0], wave[9_999]));
+/// ```
+pub fn lttb(points: &[(f64, f64)], threshold: usize) -> Vec<(f64, f64)> {
+    let points = finite(points);
+    if points.len() <= threshold {
+        return points;
+    }
+    if threshold < 3 {
+        // Too few points are allowed to pick any from buckets, so just the ends are kept.
+        return [points[0], points[points.len() - 1]][..threshold].to_vec();
+    }
+    let buckets = threshold - 2;
+    let size = (points.len() - 2) as f64 / buckets as f64;
+    let bucket = |i: usize| {
+        let start = 1 + (i as f64 * size) as usize;
+        let end = 1 + ((i + 1) as f64 * size) as usize;
+        start..cmp::min(end, points.len() - 1)
+    };
+
+    let mut picked = Vec::with_capacity(threshold);
+    picked.push(points[0]);
+    let mut last = points[0];
+    for i in 0..buckets {
+        // The average of the next bucket, or the last point after the last bucket.
+        let next = if i + 1 < buckets {
+            let range = bucket(i + 1);
+            let n = range.len().max(1) as f64;
+            let sum = points[range].iter().fold((0.0, 0.0), |(x, y), p| (x + p.0, y + p.1));
+            (sum.0 / n, sum.1 / n)
+        } else {
+            points[points.len() - 1]
+        };
+        let best = points[bucket(i)].iter().cloned().max_by(|&a, &b| {
+            let area = |p: (f64, f64)| {
+                ((last.0 - next.0) * (p.1 - last.1) - (last.0 - p.0) * (next.1 - last.1)).abs()
+            };
+            area(a).partial_cmp(&area(b)).unwrap_or(cmp::Ordering::Equal)
+        });
+        if let Some(best) = best {
+            picked.push(best);
+            last = best;
+        }
+    }
+    picked.push(points[points.len() - 1]);
+    picked
+}
+
+/// Thins out a series, sorted by x, to at most two points in each of `columns` equal slices of its
+/// x range: the lowest and the highest, in the order they came.
+///
+/// Unlike `lttb`, this keeps every extreme, so a spike that fills one column of pixels still
+/// shows, which suits monitoring data. Non-finite points are left out.
+///
+/// ```
+/// use drawille::chart;
+///
+/// let mut samples = (0..1_000).map(|i| (i as f64, 1.0)).collect::<Vec<_>>();
+/// samples[503].1 = 50.0;
+/// let thinned = chart::min_max(&samples, 10);
+/// assert!(thinned.len() <= 20);
+/// assert!(thinned.contains(&(503.0, 50.0)));
+/// ```
+pub fn min_max(points: &[(f64, f64)], columns: usize) -> Vec<(f64, f64)> {
+    let points = finite(points);
+    if columns == 0 || points.len() <= columns * 2 {
+        return points;
+    }
+    let (first, last) = (points[0].0, points[points.len() - 1].0);
+    let span = last - first;
+    let column = |x: f64| {
+        if span > 0.0 {
+            cmp::min(((x - first) / span * columns as f64) as usize, columns - 1)
+        } else {
+            0
+        }
+    };
+
+    let mut kept = Vec::with_capacity(columns * 2);
+    let mut i = 0;
+    while i < points.len() {
+        let c = column(points[i].0);
+        let (mut low, mut high) = (i, i);
+        let mut j = i + 1;
+        while j < points.len() && column(points[j].0) == c {
+            if points[j].1 < points[low].1 {
+                low = j;
+            }
+            if points[j].1 > points[high].1 {
+                high = j;
+            }
+            j += 1;
+        }
+        kept.push(points[cmp::min(low, high)]);
+        if low != high {
+            kept.push(points[cmp::max(low, high)]);
+        }
+        i = j;
+    }
+    kept
+}
+
+/// Returns the points of a series thinned out for a plot `columns` pixels wide.
+///
+/// Gaps in the series, where points aren't finite, are kept: each run of finite points between
+/// them is thinned out separately, for its share of the columns, with a `NaN` point between runs.
+pub fn downsample(points: &[(f64, f64)], columns: usize, how: Downsampling) -> Vec<(f64, f64)> {
+    if how == Downsampling::None {
+        return points.to_vec();
+    }
+    let runs = points.split(|p| !p.0.is_finite() || !p.1.is_finite())
+                     .filter(|run| !run.is_empty()).collect::<Vec<_>>();
+    let span = |run: &[(f64, f64)]| (run[run.len() - 1].0 - run[0].0).abs();
+    let total = runs.iter().map(|run| span(run)).sum::<f64>();
+    let mut thinned = vec![];
+    for (i, run) in runs.iter().enumerate() {
+        if i > 0 {
+            thinned.push((f64::NAN, f64::NAN));
+        }
+        let share = if total > 0.0 { span(run) / total } else { 1.0 / runs.len() as f64 };
+        let columns = cmp::max(2, (columns as f64 * share).ceil() as usize);
+        thinned.extend(match how {
+            Downsampling::Lttb => lttb(run, columns),
+            _ => min_max(run, columns),
+        });
+    }
+    thinned
+}
+
+fn finite(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    points.iter().cloned().filter(|p| p.0.is_finite() && p.1.is_finite()).collect()
+}
@@ -0,0 +1,70 @@
+use braille::Canvas;
+use super::PlotArea;
+
+/// How far the uncertainty of a data point extends below and above its y value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ErrorBar {
+    pub below: f64,
+    pub above: f64,
+}
+
+impl ErrorBar {
+    /// Creates a new `ErrorBar` extending `error` either side of its point.
+    pub fn symmetric(error: f64) -> ErrorBar {
+        ErrorBar { below: error, above: error }
+    }
+
+    /// Creates a new `ErrorBar` extending `below` under its point and `above` over it.
+    pub fn asymmetric(below: f64, above: f64) -> ErrorBar {
+        ErrorBar { below, above }
+    }
+
+    /// Returns the lowest and highest y values the bar covers for a point at `y`.
+    pub fn range(&self, y: f64) -> (f64, f64) {
+        (y - self.below.abs(), y + self.above.abs())
+    }
+}
+
+/// Draws an error bar for each point that has one onto the plot area: a vertical line spanning
+/// its uncertainty, with caps `cap` pixels either side of the line at each end, or no caps if
+/// `cap` is zero.
+///
+/// Both ends of a bar are rounded to whole pixels before anything is drawn, so that each cap lies
+/// exactly across the end of its line. Bars are cut short at the edges of the area, and a cap is
+/// left off an end that was cut short, since the bar goes on beyond the area there.
+///
+/// ```
+/// use drawille::braille::Canvas;
+/// use drawille::chart::{self, ErrorBar, Scale};
+///
+/// let mut canvas = Canvas::new(80, 40);
+/// let area = chart::draw_axes(&mut canvas, 80, 40, Scale::new(0.0, 4.0), Scale::new(0.0, 10.0))
+///     .unwrap();
+/// let points = [(1.0, 4.0), (2.0, 6.0), (3.0, 5.0)];
+/// let errors = [ErrorBar::symmetric(1.0), ErrorBar::asymmetric(0.5, 2.0),
+///               ErrorBar::symmetric(3.0)];
+/// chart::draw_error_bars(&mut canvas, &area, &points, &errors, 1);
+/// ```
+pub fn draw_error_bars(canvas: &mut Canvas, area: &PlotArea, points: &[(f64, f64)],
+                       errors: &[ErrorBar], cap: usize) {
+    let (top, bottom) = (area.top as f64, (area.top + area.height) as f64 - 1.0);
+    let (left, right) = (area.left, area.left + area.width);
+    for (&(x, y), error) in points.iter().zip(errors.iter()) {
+        let (low, high) = error.range(y);
+        let (px, high) = area.project(x, high);
+        let (_, low) = area.project(x, low);
+        let (px, high, low) = (px.round(), high.round(), low.round());
+        if !px.is_finite() || !high.is_finite() || !low.is_finite() ||
+           px < left as f64 || px >= right as f64 || high > bottom || low < top {
+            continue;
+        }
+        let px = px as usize;
+        let (start, end) = (high.max(top) as usize, low.min(bottom) as usize);
+        canvas.line(px, start, px, end);
+        for (end, cut) in [(start, high < top), (end, low > bottom)] {
+            if cap > 0 && !cut {
+                canvas.line(px.saturating_sub(cap).max(left), end, (px + cap).min(right - 1), end);
+            }
+        }
+    }
+}
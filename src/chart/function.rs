@@ -0,0 +1,51 @@
+use super::{LineChart, Scale};
+
+/// Plots `y = f(x)` over `x_range` as a line chart `size.0` by `size.1` pixels, and returns it as
+/// a `String`.
+///
+/// `f` is first sampled a couple of times per pixel column, then sampled again between any
+/// neighbouring samples where the curve bends by more than half a pixel, so narrow spikes and
+/// sharp corners aren’t missed.
+///
+/// ```
+/// use drawille::chart::plot_fn;
+///
+/// println!("{}", plot_fn(|x| x.sin() / x, (-20.0, 20.0), (80, 40)));
+/// ```
+pub fn plot_fn<F: Fn(f64) -> f64>(f: F, x_range: (f64, f64), size: (usize, usize)) -> String {
+    let (start, end) = x_range;
+    let initial = (size.0 * 2).max(2);
+    let mut samples = (0..initial + 1).map(|i| {
+        let x = start + (end - start) * i as f64 / initial as f64;
+        (x, f(x))
+    }).collect::<Vec<_>>();
+
+    let scale = Scale::fit(samples.iter().map(|p| p.1));
+    // Half a pixel, in units of y.
+    let tolerance = (scale.max - scale.min) / size.1.max(1) as f64 / 2.0;
+    let mut refined = vec![samples[0]];
+    for pair in samples.windows(2) {
+        refine(&f, pair[0], pair[1], tolerance, MAX_REFINEMENT_DEPTH, &mut refined);
+    }
+    samples = refined;
+
+    LineChart::new(size.0, size.1).x_range(start, end).series_xy(&samples).frame()
+}
+
+/// How many times an interval between samples may be halved.
+const MAX_REFINEMENT_DEPTH: usize = 8;
+
+/// Pushes the samples needed to draw `f` from `a` to `b`, excluding `a` itself.
+fn refine<F: Fn(f64) -> f64>(f: &F, a: (f64, f64), b: (f64, f64), tolerance: f64, depth: usize,
+                             samples: &mut Vec<(f64, f64)>) {
+    let mid_x = (a.0 + b.0) / 2.0;
+    let mid = (mid_x, f(mid_x));
+    let linear = (a.1 + b.1) / 2.0;
+    let bends = !(mid.1 - linear).abs().le(&tolerance);
+    if depth > 0 && bends {
+        refine(f, a, mid, tolerance, depth - 1, samples);
+        refine(f, mid, b, tolerance, depth - 1, samples);
+    } else {
+        samples.push(b);
+    }
+}
@@ -0,0 +1,109 @@
+use std::f64::consts::PI;
+
+use braille::Canvas;
+use geometry;
+use super::{Chart, format_tick};
+
+/// The angle the dial of a gauge sweeps through, in degrees, from its lowest value at the bottom
+/// left round to its highest at the bottom right.
+const SWEEP: f64 = 270.0;
+
+/// A circular gauge like a speedometer, with a needle pointing at a value on a dial.
+///
+/// The dial is solid up to the value and dotted beyond it, with the value written beneath the
+/// needle.
+///
+/// ```
+/// use drawille::chart::Gauge;
+///
+/// println!("{}", Gauge::new(40, 40).range(0.0, 120.0).value(75.0).frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gauge {
+    width: usize,
+    height: usize,
+    range: (f64, f64),
+    value: f64,
+}
+
+impl Gauge {
+    /// Creates a new `Gauge` with the given width and height in pixels, showing zero on a dial
+    /// from 0 to 1.
+    pub fn new(width: usize, height: usize) -> Gauge {
+        Gauge { width, height, range: (0.0, 1.0), value: 0.0 }
+    }
+
+    /// Sets the values at the start and end of the dial, and returns the gauge for use again.
+    pub fn range(mut self, min: f64, max: f64) -> Gauge {
+        self.range = (min, max);
+        self
+    }
+
+    /// Sets the value the needle points at, and returns the gauge for use again.
+    ///
+    /// Values outside the range leave the needle at the end of the dial.
+    pub fn value(mut self, value: f64) -> Gauge {
+        self.value = value;
+        self
+    }
+
+    /// Draws the gauge onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let (min, max) = self.range;
+        let t = if max != min { (self.value - min) / (max - min) } else { 0.0 };
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let radius = (self.width.min(self.height) as f64 - 1.0) / 2.0;
+        if radius < 2.0 {
+            return;
+        }
+        let centre = ((self.width as f64 - 1.0) / 2.0, radius);
+        let point = |t: f64, r: f64| {
+            let angle = (225.0 - SWEEP * t).to_radians();
+            (centre.0 + r * angle.cos(), centre.1 - r * angle.sin())
+        };
+        let mut plot = |x, y| canvas.set(x, y);
+
+        // The dial is drawn in short steps, solid up to the value and every other step after it.
+        let steps = ((radius * PI * SWEEP / 180.0 / 2.0).ceil() as usize).max(1);
+        for i in 0..steps {
+            let (a, b) = (i as f64 / steps as f64, (i + 1) as f64 / steps as f64);
+            if a < t {
+                geometry::segment(point(a, radius), point(b.min(t), radius), geometry::BOUNDS,
+                                  &mut plot);
+            }
+            if b > t && i % 2 == 0 {
+                geometry::segment(point(a.max(t), radius), point(b, radius), geometry::BOUNDS,
+                                  &mut plot);
+            }
+        }
+        // Ticks at the ends and every quarter of the dial.
+        for tick in (0..5).map(|i| i as f64 / 4.0) {
+            geometry::segment(point(tick, radius), point(tick, radius * 0.85), geometry::BOUNDS,
+                              &mut plot);
+        }
+        geometry::segment(centre, point(t, radius * 0.75), geometry::BOUNDS, &mut plot);
+
+        let label = format_tick(self.value, (max - min).abs() / 100.0);
+        // The label goes in the gap between the ends of the dial.
+        let y = (centre.1 + radius * 0.6) as usize;
+        let x = (self.width / 2).saturating_sub(label.chars().count());
+        canvas.text(x, y, label);
+    }
+
+    /// Draws the gauge to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+impl Chart for Gauge {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        Gauge::draw(self, canvas);
+    }
+}
@@ -0,0 +1,155 @@
+use std::cmp;
+
+use braille::Canvas;
+
+/// A chart that can be drawn in a pane of a `ChartGrid`.
+///
+/// Charts with an x axis that other charts can share also give the range of x values they show,
+/// and can be drawn with another range instead.
+pub trait Chart {
+    /// Returns the width and height of the chart in pixels.
+    fn size(&self) -> (usize, usize);
+
+    /// Draws the chart onto the `Canvas`, with its top left corner at the origin.
+    fn draw(&self, canvas: &mut Canvas);
+
+    /// Returns the range of the chart's x axis, as set or as fitted to its data before being
+    /// widened to whole ticks, or `None` if it has no x axis to share or no data to fit one to.
+    fn x_extent(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Draws the chart just as `draw` does, but with its x axis ranging from `min` to `max`.
+    ///
+    /// Charts without an x axis to share are drawn as usual.
+    fn draw_x_range(&self, canvas: &mut Canvas, _min: f64, _max: f64) {
+        self.draw(canvas);
+    }
+}
+
+/// Charts laid out in a grid of panes within one canvas, filled a row at a time, for comparing
+/// the same metrics of several hosts at a glance.
+///
+/// Each column of panes is as wide as its widest chart, and each row as tall as its tallest, with
+/// a gap between them. Optionally, the charts can share an x axis ranging over the data of all of
+/// them, so that their ticks line up and the same x value falls in the same place in each pane
+/// of a column.
+///
+/// ```
+/// use drawille::chart::{ChartGrid, LineChart};
+///
+/// let hosts = [("web-1", [0.2, 0.5, 0.4]), ("web-2", [0.3, 0.9, 0.8]),
+///              ("db-1", [0.6, 0.6, 0.7]), ("db-2", [0.1, 0.2, 0.1])];
+/// let mut grid = ChartGrid::new(2).share_x();
+/// for &(host, load) in hosts.iter() {
+///     grid = grid.chart(LineChart::new(60, 32).series(&load).title(host));
+/// }
+/// assert_eq!(grid.size(), (124, 68));
+/// println!("{}", grid.frame());
+/// ```
+pub struct ChartGrid {
+    columns: usize,
+    charts: Vec<Box<dyn Chart>>,
+    gap: (usize, usize),
+    share_x: bool,
+}
+
+impl ChartGrid {
+    /// Creates a new, empty `ChartGrid` with the given number of panes in each row, and a gap of
+    /// two columns and one row of characters between panes.
+    pub fn new(columns: usize) -> ChartGrid {
+        ChartGrid { columns: cmp::max(1, columns), charts: vec![], gap: (2, 1), share_x: false }
+    }
+
+    /// Adds a chart in the next pane, and returns the grid for use again.
+    pub fn chart<C: Chart + 'static>(mut self, chart: C) -> ChartGrid {
+        self.charts.push(Box::new(chart));
+        self
+    }
+
+    /// Sets the gap between panes, in columns and rows of characters, and returns the grid for use
+    /// again.
+    pub fn gap(mut self, columns: usize, rows: usize) -> ChartGrid {
+        self.gap = (columns, rows);
+        self
+    }
+
+    /// Gives every chart with an x axis the same x range, covering the ranges of all of them, and
+    /// returns the grid for use again.
+    pub fn share_x(mut self) -> ChartGrid {
+        self.share_x = true;
+        self
+    }
+
+    /// Returns the range covering the x extents of all the charts that have one.
+    fn x_extent(&self) -> Option<(f64, f64)> {
+        self.charts.iter().filter_map(|c| c.x_extent()).fold(None, |range, (a, b)| {
+            let (a, b) = (a.min(b), a.max(b));
+            match range {
+                None => Some((a, b)),
+                Some((min, max)) => Some((f64::min(min, a), f64::max(max, b))),
+            }
+        })
+    }
+
+    /// Returns the width of each column and the height of each row of panes, in characters.
+    fn tracks(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut widths = vec![0; cmp::min(self.columns, self.charts.len())];
+        let mut heights = vec![0; self.charts.len().div_ceil(self.columns)];
+        for (i, chart) in self.charts.iter().enumerate() {
+            let (width, height) = chart.size();
+            let (column, row) = (i % self.columns, i / self.columns);
+            widths[column] = cmp::max(widths[column], width.div_ceil(2));
+            heights[row] = cmp::max(heights[row], height.div_ceil(4));
+        }
+        (widths, heights)
+    }
+
+    /// Returns the width and height of the whole grid in pixels.
+    pub fn size(&self) -> (usize, usize) {
+        let (widths, heights) = self.tracks();
+        let span = |tracks: &[usize], gap: usize| {
+            tracks.iter().sum::<usize>() + tracks.len().saturating_sub(1) * gap
+        };
+        (span(&widths, self.gap.0) * 2, span(&heights, self.gap.1) * 4)
+    }
+
+    /// Draws the grid onto the `Canvas`, with its top left corner at the origin, in place of
+    /// anything already drawn under each pane.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let shared = if self.share_x { self.x_extent() } else { None };
+        let (widths, heights) = self.tracks();
+        let start = |tracks: &[usize], index: usize, gap: usize| {
+            tracks[..index].iter().sum::<usize>() + index * gap
+        };
+        for (i, chart) in self.charts.iter().enumerate() {
+            let (column, row) = (i % self.columns, i / self.columns);
+            let (width, height) = chart.size();
+            let mut pane = Canvas::new(width, height);
+            match shared {
+                Some((min, max)) => chart.draw_x_range(&mut pane, min, max),
+                None => chart.draw(&mut pane),
+            }
+            canvas.paste(&pane, start(&widths, column, self.gap.0),
+                         start(&heights, row, self.gap.1));
+        }
+    }
+
+    /// Draws the grid to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let (width, height) = self.size();
+        let mut canvas = Canvas::new(width, height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+impl Chart for ChartGrid {
+    fn size(&self) -> (usize, usize) {
+        ChartGrid::size(self)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        ChartGrid::draw(self, canvas);
+    }
+}
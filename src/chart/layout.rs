@@ -0,0 +1,299 @@
+use std::cmp;
+
+use block::Color;
+use braille::{Canvas, LineStyle, Marker};
+use super::{PlotArea, Scale};
+use super::axis::draw_chart_axes;
+
+/// The number of characters a legend marker takes up, not counting the space after it. Six pixels
+/// are enough to tell each `LineStyle` apart.
+const MARKER_COLS: usize = 3;
+
+/// One entry of a chart legend: a label next to a marker showing how its series is drawn.
+///
+/// The marker is a short stroke in the series’ line style, with the point marker of the series
+/// in the middle of it if it has one, so that series can be told apart without colour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LegendEntry {
+    pub label: String,
+    /// The colour of the marker, or `None` for the terminal’s default colour.
+    pub color: Option<Color>,
+    /// The style of the stroke, or `None` for a series drawn without lines.
+    pub style: Option<LineStyle>,
+    /// The marker drawn at each point of the series, if any.
+    pub marker: Option<Marker>,
+}
+
+impl LegendEntry {
+    /// Creates a new `LegendEntry` with the given label and an uncoloured, solid stroke.
+    pub fn new<S: Into<String>>(label: S) -> LegendEntry {
+        LegendEntry {
+            label: label.into(),
+            color: None,
+            style: Some(LineStyle::Solid),
+            marker: None,
+        }
+    }
+
+    /// Creates a new `LegendEntry` with the given label and a solid stroke of the given colour.
+    pub fn colored<S: Into<String>>(label: S, color: Color) -> LegendEntry {
+        LegendEntry { color: Some(color), ..LegendEntry::new(label) }
+    }
+
+    /// Sets the style of the stroke, or `None` to leave it out, and returns the entry for use
+    /// again.
+    pub fn style(mut self, style: Option<LineStyle>) -> LegendEntry {
+        self.style = style;
+        self
+    }
+
+    /// Sets the marker drawn in the middle of the stroke, and returns the entry for use again.
+    pub fn marker(mut self, marker: Marker) -> LegendEntry {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Draws the entry’s marker in the `MARKER_COLS` characters from character `(col, row)`.
+    fn draw_marker(&self, canvas: &mut Canvas, col: usize, row: usize) {
+        let (left, y) = (col * 2, row * 4 + 1);
+        let mut pixels = vec![];
+        if let Some(style) = self.style {
+            pixels.extend((0..MARKER_COLS * 2).filter(|&i| style.draws(i)).map(|i| (left + i, y)));
+        }
+        match self.marker {
+            Some(marker) => {
+                let center = (left + MARKER_COLS) as isize;
+                pixels.extend(marker.brush().offsets().iter().map(|&(dx, dy)| {
+                    ((center + dx) as usize, (y as isize + dy) as usize)
+                }));
+            },
+            None if self.style.is_none() => pixels.push((left + MARKER_COLS, y)),
+            None => {},
+        }
+        for (x, y) in pixels {
+            match self.color {
+                Some(color) => canvas.set_colored(x, y, color),
+                None => canvas.set(x, y),
+            }
+        }
+    }
+}
+
+/// A corner of a chart’s plot area.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Draws a legend listing `entries` one per row in a corner of the plot area, over whatever has
+/// been plotted there, and returns the number of entries that fit.
+///
+/// The characters under the legend are cleared first so that it can be read over the data; draw
+/// it after the series. Entries whose labels are too long are cut short to fit the plot area.
+///
+/// ```
+/// use drawille::braille::{Canvas, LineStyle, Marker};
+/// use drawille::chart::{self, Corner, LegendEntry, Scale};
+///
+/// let mut canvas = Canvas::new(80, 40);
+/// let area = chart::draw_axes(&mut canvas, 80, 40, Scale::new(0.0, 1.0), Scale::new(0.0, 1.0))
+///     .unwrap();
+/// let entries = [LegendEntry::new("measured").style(Some(LineStyle::Dotted)),
+///                LegendEntry::new("model").marker(Marker::Plus(1))];
+/// assert_eq!(chart::draw_legend(&mut canvas, &area, &entries, Corner::TopRight), 2);
+/// ```
+pub fn draw_legend(canvas: &mut Canvas, area: &PlotArea, entries: &[LegendEntry], corner: Corner)
+                   -> usize {
+    // Only the characters wholly inside the plot area.
+    let (left, top) = (area.left.div_ceil(2), area.top.div_ceil(4));
+    let (right, bottom) = ((area.left + area.width) / 2, (area.top + area.height) / 4);
+    if right <= left + MARKER_COLS + 1 || bottom <= top {
+        return 0;
+    }
+    let rows = cmp::min(entries.len(), bottom - top);
+    let label_cols = right - left - MARKER_COLS - 1;
+    let labels = entries[..rows].iter().map(|e| truncate(&e.label, label_cols)).collect::<Vec<_>>();
+    let cols = MARKER_COLS + 1 + labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let col = match corner {
+        Corner::TopLeft | Corner::BottomLeft => left,
+        Corner::TopRight | Corner::BottomRight => right - cols,
+    };
+    let row = match corner {
+        Corner::TopLeft | Corner::TopRight => top,
+        Corner::BottomLeft | Corner::BottomRight => bottom - rows,
+    };
+    for r in row..row + rows {
+        for c in col..col + cols {
+            canvas.set_cell(c, r, 0);
+        }
+    }
+    for (i, (entry, label)) in entries.iter().zip(labels).enumerate() {
+        entry.draw_marker(canvas, col, row + i);
+        canvas.text((col + MARKER_COLS + 1) * 2, (row + i) * 4, label);
+    }
+    rows
+}
+
+/// The arrangement of the text around a chart: its title, axis titles and legend.
+///
+/// A `ChartLayout` reserves whole rows and columns of characters around the axes for each piece of
+/// text it is given, so that none of it overlaps the plot. The title is centred on the top row,
+/// the y axis title sits above the axis, the x axis title is centred below the tick labels, and the
+/// legend lists its entries down the right-hand side. A legend too wide to leave room for the plot
+/// is left out.
+///
+/// ```
+/// use drawille::braille::Canvas;
+/// use drawille::chart::{ChartLayout, LegendEntry, Scale};
+/// use drawille::block::Color;
+///
+/// let layout = ChartLayout::new(80, 40).title("Throughput").x_title("time (s)")
+///                                      .y_title("req/s")
+///                                      .legend(vec![LegendEntry::colored("api", Color::Green),
+///                                                   LegendEntry::colored("db", Color::Red)]);
+/// let mut canvas = Canvas::new(80, 40);
+/// let area = layout.draw(&mut canvas, Scale::new(0.0, 60.0), Scale::new(0.0, 500.0)).unwrap();
+/// assert!(area.left > 0 && area.top > 0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartLayout {
+    width: usize,
+    height: usize,
+    title: Option<String>,
+    x_title: Option<String>,
+    y_title: Option<String>,
+    legend: Vec<LegendEntry>,
+}
+
+impl ChartLayout {
+    /// Creates a new `ChartLayout`, with no text besides the tick labels, for a chart with the
+    /// given width and height in pixels.
+    pub fn new(width: usize, height: usize) -> ChartLayout {
+        ChartLayout {
+            width,
+            height,
+            title: None,
+            x_title: None,
+            y_title: None,
+            legend: vec![],
+        }
+    }
+
+    /// Sets the title of the chart, and returns the layout for use again.
+    pub fn title<S: Into<String>>(mut self, title: S) -> ChartLayout {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the title of the x axis, and returns the layout for use again.
+    pub fn x_title<S: Into<String>>(mut self, title: S) -> ChartLayout {
+        self.x_title = Some(title.into());
+        self
+    }
+
+    /// Sets the title of the y axis, and returns the layout for use again.
+    pub fn y_title<S: Into<String>>(mut self, title: S) -> ChartLayout {
+        self.y_title = Some(title.into());
+        self
+    }
+
+    /// Sets the entries of the legend, and returns the layout for use again.
+    pub fn legend(mut self, entries: Vec<LegendEntry>) -> ChartLayout {
+        self.legend = entries;
+        self
+    }
+
+    /// Returns the height in pixels left for the axes and their tick labels once the titles have
+    /// been placed.
+    ///
+    /// This is the height to pick the number of y axis ticks for with `max_y_ticks`.
+    pub fn axes_height(&self) -> usize {
+        let (top, bottom) = self.margin_rows();
+        (self.height / 4).saturating_sub(top + bottom) * 4
+    }
+
+    fn margin_rows(&self) -> (usize, usize) {
+        let top = self.title.is_some() as usize + self.y_title.is_some() as usize;
+        (top, self.x_title.is_some() as usize)
+    }
+
+    fn legend_cols(&self) -> usize {
+        let cols = self.width / 2;
+        let labels = self.legend.iter().map(|e| e.label.chars().count()).max();
+        match labels {
+            // A space either side of the marker, and one before the edge of the plot.
+            Some(len) if (MARKER_COLS + len + 2) * 2 <= cols => MARKER_COLS + len + 2,
+            _ => 0,
+        }
+    }
+
+    /// Draws the text and axes of the chart onto the `Canvas`, with its top left corner at the
+    /// origin, returning the area left for plotting data, or `None` if the chart is too small to
+    /// plot anything.
+    pub fn draw(&self, canvas: &mut Canvas, x_scale: Scale, y_scale: Scale) -> Option<PlotArea> {
+        self.draw_layout(canvas, x_scale, y_scale, None).map(|(area, _)| area)
+    }
+
+    /// Like `draw`, but also draws a secondary y axis, labelled on the right, for overlaying series
+    /// of a different magnitude.
+    ///
+    /// Returns the plot area along with the same area scaled to the secondary axis.
+    pub fn draw_dual(&self, canvas: &mut Canvas, x_scale: Scale, y_scale: Scale,
+                     secondary_scale: Scale) -> Option<(PlotArea, PlotArea)> {
+        self.draw_layout(canvas, x_scale, y_scale, Some(secondary_scale))
+            .map(|(area, secondary)| (area, secondary.unwrap()))
+    }
+
+    fn draw_layout(&self, canvas: &mut Canvas, x_scale: Scale, y_scale: Scale,
+                   secondary_scale: Option<Scale>) -> Option<(PlotArea, Option<PlotArea>)> {
+        let cols = self.width / 2;
+        let top = self.margin_rows().0;
+        let legend_cols = self.legend_cols();
+        let axes_cols = cols - legend_cols;
+        let (area, secondary) = draw_chart_axes(canvas, (0, top * 4), axes_cols * 2,
+                                                self.axes_height(), x_scale, y_scale,
+                                                secondary_scale)?;
+
+        let mut row = 0;
+        if let Some(ref title) = self.title {
+            let title = truncate(title, cols);
+            let start = (cols - title.chars().count()) / 2;
+            canvas.text(start * 2, 0, title);
+            row += 1;
+        }
+        if let Some(ref title) = self.y_title {
+            canvas.text(0, row * 4, truncate(title, axes_cols));
+        }
+        if let Some(ref title) = self.x_title {
+            let (plot_left, plot_cols) = (area.left / 2, area.width / 2);
+            let title = truncate(title, axes_cols);
+            let len = title.chars().count();
+            let start = cmp::min(plot_left + plot_cols.saturating_sub(len) / 2, axes_cols - len);
+            canvas.text(start * 2, (top + self.axes_height() / 4) * 4, title);
+        }
+        if legend_cols > 0 {
+            self.draw_legend(canvas, axes_cols + 1, top);
+        }
+        Some((area, secondary))
+    }
+
+    /// Lists as many legend entries as fit beside the axes one per row, starting at character
+    /// `(col, row)`.
+    fn draw_legend(&self, canvas: &mut Canvas, col: usize, row: usize) {
+        for (i, entry) in self.legend.iter().take(self.axes_height() / 4).enumerate() {
+            entry.draw_marker(canvas, col, row + i);
+            canvas.text((col + MARKER_COLS + 1) * 2, (row + i) * 4, &entry.label);
+        }
+    }
+}
+
+/// Returns at most the first `cols` characters of `s`.
+fn truncate(s: &str, cols: usize) -> &str {
+    match s.char_indices().nth(cols) {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
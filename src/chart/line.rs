@@ -0,0 +1,483 @@
+use braille::{Canvas, LineStyle};
+use geometry;
+use super::{Annotation, Chart, ChartLayout, Corner, Crosshair, ErrorBar, LegendEntry, PlotArea,
+            ScaleKind, crosshair, draw_annotations, draw_error_bars, draw_legend, max_y_ticks};
+use super::axis::AxisSpec;
+use super::crosshair::mark_nearest;
+use super::downsample::{Downsampling, downsample};
+
+/// A line chart of one or more data series, with labelled axes.
+///
+/// Axes are scaled to fit the data unless given explicit ranges. Series may also be plotted against
+/// a secondary y axis on the right of the chart, so that series of very different magnitudes can
+/// be overlaid.
+///
+/// ```
+/// use drawille::chart::LineChart;
+///
+/// let latency = [12.0, 15.0, 90.0, 240.0, 31.0];
+/// let queries = [1200.0, 1500.0, 900.0, 400.0, 1300.0];
+/// let chart = LineChart::new(80, 32).series(&latency).log_y()
+///                                   .secondary_series(&queries);
+/// println!("{}", chart.frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineChart {
+    width: usize,
+    height: usize,
+    series: Vec<Vec<(f64, f64)>>,
+    secondary_series: Vec<Vec<(f64, f64)>>,
+    x_axis: AxisSpec,
+    y_axis: AxisSpec,
+    layout: ChartLayout,
+    labels: Vec<String>,
+    styles: Vec<LineStyle>,
+    legend_corner: Option<Corner>,
+    /// The error bars of each series on the primary axis, empty for those without.
+    errors: Vec<Vec<ErrorBar>>,
+    error_caps: usize,
+    downsampling: Downsampling,
+    annotations: Vec<Annotation>,
+    secondary_axis: AxisSpec,
+}
+
+impl LineChart {
+    /// Creates a new, empty `LineChart` with the given width and height in pixels, including its
+    /// axes and their labels.
+    pub fn new(width: usize, height: usize) -> LineChart {
+        LineChart {
+            width,
+            height,
+            series: vec![],
+            secondary_series: vec![],
+            x_axis: AxisSpec::new(),
+            y_axis: AxisSpec::new(),
+            layout: ChartLayout::new(width, height),
+            labels: vec![],
+            styles: vec![],
+            legend_corner: None,
+            errors: vec![],
+            error_caps: 1,
+            downsampling: Downsampling::None,
+            annotations: vec![],
+            secondary_axis: AxisSpec::new(),
+        }
+    }
+
+    /// Adds a series of values, plotted against their indices, and returns the chart for use
+    /// again.
+    ///
+    /// Values that aren't finite leave a gap in the line.
+    pub fn series(mut self, values: &[f64]) -> LineChart {
+        self.series.push(indexed(values));
+        self
+    }
+
+    /// Adds a series of values that may be missing, plotted against their indices, and returns
+    /// the chart for use again.
+    ///
+    /// The line is broken where values are missing, just as it is for values that aren't finite,
+    /// rather than joining the values either side.
+    ///
+    /// ```
+    /// use drawille::chart::LineChart;
+    ///
+    /// let samples = [Some(1.0), Some(2.0), None, None, Some(2.0), Some(1.0)];
+    /// let gapped = LineChart::new(40, 16).series_opt(&samples).frame();
+    /// let nan = LineChart::new(40, 16).series(&[1.0, 2.0, f64::NAN, f64::NAN, 2.0, 1.0]).frame();
+    /// assert_eq!(gapped, nan);
+    /// ```
+    pub fn series_opt(self, values: &[Option<f64>]) -> LineChart {
+        self.series(&values.iter().map(|v| v.unwrap_or(f64::NAN)).collect::<Vec<_>>())
+    }
+
+    /// Adds a series of `(x, y)` points, and returns the chart for use again.
+    pub fn series_xy(mut self, points: &[(f64, f64)]) -> LineChart {
+        self.series.push(points.to_vec());
+        self
+    }
+
+    /// Adds a series of values, plotted against their indices on the secondary y axis, and
+    /// returns the chart for use again.
+    pub fn secondary_series(mut self, values: &[f64]) -> LineChart {
+        self.secondary_series.push(indexed(values));
+        self
+    }
+
+    /// Adds a series of `(x, y)` points plotted on the secondary y axis, and returns the chart for
+    /// use again.
+    pub fn secondary_series_xy(mut self, points: &[(f64, f64)]) -> LineChart {
+        self.secondary_series.push(points.to_vec());
+        self
+    }
+
+    /// Gives each point of the series added last error bars, in order, and returns the chart for
+    /// use again.
+    ///
+    /// Only series on the primary axis have error bars. The y axis is fitted to the error bars as
+    /// well as the points.
+    ///
+    /// ```
+    /// use drawille::chart::{ErrorBar, LineChart};
+    ///
+    /// let errors = [0.5, 0.2, 0.8, 0.4].iter().map(|&e| ErrorBar::symmetric(e))
+    ///                                   .collect::<Vec<_>>();
+    /// let chart = LineChart::new(80, 40).series(&[2.0, 3.0, 2.5, 4.0]).error_bars(&errors)
+    ///                                   .error_caps(2);
+    /// println!("{}", chart.frame());
+    /// ```
+    pub fn error_bars(mut self, errors: &[ErrorBar]) -> LineChart {
+        if !self.series.is_empty() {
+            self.errors.resize(self.series.len(), vec![]);
+            *self.errors.last_mut().unwrap() = errors.to_vec();
+        }
+        self
+    }
+
+    /// Sets how many pixels the caps of error bars reach either side of the bars, or zero for no
+    /// caps, and returns the chart for use again.
+    pub fn error_caps(mut self, cap: usize) -> LineChart {
+        self.error_caps = cap;
+        self
+    }
+
+    /// Sets how series with more points than the plot has columns of pixels are thinned out
+    /// before they're drawn, and returns the chart for use again.
+    ///
+    /// Drawing a line to every point of a series of millions takes far longer than drawing a few
+    /// hundred picked to keep its shape. Series are sorted by x for this to work. Error bars and
+    /// crosshairs still use every point.
+    ///
+    /// ```
+    /// use drawille::chart::{Downsampling, LineChart};
+    ///
+    /// let noise = (0..1_000_000u64).map(|i| (i * 7919 % 1000) as f64).collect::<Vec<_>>();
+    /// let chart = LineChart::new(200, 40).series(&noise).downsample(Downsampling::MinMax);
+    /// println!("{}", chart.frame());
+    /// ```
+    pub fn downsample(mut self, downsampling: Downsampling) -> LineChart {
+        self.downsampling = downsampling;
+        self
+    }
+
+    /// Adds an annotation, drawn over the series in the order annotations were added, and returns
+    /// the chart for use again.
+    ///
+    /// Annotations are placed by the primary y axis. The axes aren't fitted to them, so that an
+    /// annotation outside the data's range isn't drawn unless the axis range is set to include it.
+    ///
+    /// ```
+    /// use drawille::chart::{Annotation, LineChart};
+    ///
+    /// let availability = [99.95, 99.97, 99.82, 99.91, 99.99];
+    /// let chart = LineChart::new(80, 32).series(&availability).y_range(99.7, 100.0)
+    ///                                   .annotate(Annotation::threshold(99.9, "SLO 99.9"))
+    ///                                   .annotate(Annotation::label(2.0, 99.82, "incident"));
+    /// println!("{}", chart.frame());
+    /// ```
+    pub fn annotate(mut self, annotation: Annotation) -> LineChart {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Sets the range of the x axis instead of fitting it to the data, and returns the chart for
+    /// use again.
+    pub fn x_range(mut self, min: f64, max: f64) -> LineChart {
+        self.x_axis.range = Some((min, max));
+        self
+    }
+
+    /// Sets the range of the y axis instead of fitting it to the data, and returns the chart for
+    /// use again.
+    ///
+    /// Lines to values outside the range are cut short at the edge of the plot.
+    ///
+    /// ```
+    /// use drawille::chart::LineChart;
+    ///
+    /// let chart = LineChart::new(40, 16).y_range(0.0, 3.0);
+    /// let inside = chart.clone().series(&[1.0, 2.0, 2.0]).frame();
+    /// let outside = chart.series(&[1.0, 2.0, -1000.0]).frame();
+    /// let (inside, outside) = (inside.lines().collect::<Vec<_>>(),
+    ///                          outside.lines().collect::<Vec<_>>());
+    /// assert_eq!(outside.len(), inside.len());
+    /// assert_eq!(outside.last(), inside.last());
+    /// assert_eq!(outside[0].chars().next(), inside[0].chars().next());
+    /// ```
+    pub fn y_range(mut self, min: f64, max: f64) -> LineChart {
+        self.y_axis.range = Some((min, max));
+        self
+    }
+
+    /// Sets the range of the secondary y axis instead of fitting it to the data, and returns the
+    /// chart for use again.
+    pub fn secondary_y_range(mut self, min: f64, max: f64) -> LineChart {
+        self.secondary_axis.range = Some((min, max));
+        self
+    }
+
+    /// Gives the x axis a logarithmic scale, and returns the chart for use again.
+    pub fn log_x(mut self) -> LineChart {
+        self.x_axis.kind = ScaleKind::Logarithmic;
+        self
+    }
+
+    /// Gives the x axis a time scale, for x values that are times in seconds since the Unix epoch
+    /// as given by `chart::timestamp`, and returns the chart for use again.
+    pub fn time_x(mut self) -> LineChart {
+        self.x_axis.kind = ScaleKind::Time;
+        self
+    }
+
+    /// Gives the y axis a logarithmic scale, and returns the chart for use again.
+    pub fn log_y(mut self) -> LineChart {
+        self.y_axis.kind = ScaleKind::Logarithmic;
+        self
+    }
+
+    /// Gives the secondary y axis a logarithmic scale, and returns the chart for use again.
+    pub fn log_secondary_y(mut self) -> LineChart {
+        self.secondary_axis.kind = ScaleKind::Logarithmic;
+        self
+    }
+
+    /// Sets the title shown above the chart, and returns the chart for use again.
+    pub fn title<S: Into<String>>(mut self, title: S) -> LineChart {
+        self.layout = self.layout.title(title);
+        self
+    }
+
+    /// Sets the title of the x axis, and returns the chart for use again.
+    pub fn x_title<S: Into<String>>(mut self, title: S) -> LineChart {
+        self.layout = self.layout.x_title(title);
+        self
+    }
+
+    /// Sets the title of the y axis, and returns the chart for use again.
+    pub fn y_title<S: Into<String>>(mut self, title: S) -> LineChart {
+        self.layout = self.layout.y_title(title);
+        self
+    }
+
+    /// Names the series in the order they were added, listing them in a legend, and returns the
+    /// chart for use again.
+    pub fn labels<S: AsRef<str>>(mut self, labels: &[S]) -> LineChart {
+        self.labels = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Sets the style each series is drawn in, in the order they were added with those on the
+    /// secondary axis last, and returns the chart for use again.
+    ///
+    /// Series without a style are drawn solid. The legend shows each series’ style, so that
+    /// series can be told apart without colour.
+    ///
+    /// ```
+    /// use drawille::braille::LineStyle;
+    /// use drawille::chart::{Corner, LineChart};
+    ///
+    /// let chart = LineChart::new(80, 40).series(&[1.0, 3.0, 2.0]).series(&[2.0, 1.0, 3.0])
+    ///                                   .styles(&[LineStyle::Solid, LineStyle::Dashed])
+    ///                                   .labels(&["actual", "forecast"])
+    ///                                   .legend_corner(Corner::TopLeft);
+    /// println!("{}", chart.frame());
+    /// ```
+    pub fn styles(mut self, styles: &[LineStyle]) -> LineChart {
+        self.styles = styles.to_vec();
+        self
+    }
+
+    /// Lists the legend in a corner of the plot, over the data, rather than beside it, and
+    /// returns the chart for use again.
+    pub fn legend_corner(mut self, corner: Corner) -> LineChart {
+        self.legend_corner = Some(corner);
+        self
+    }
+
+    /// Draws the chart onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        self.draw_areas(canvas);
+    }
+
+    /// Draws the chart onto the `Canvas` just as `draw` does, along with a crosshair at pixel
+    /// `(x, y)` as `chart::crosshair` draws one, and returns what the crosshair found, or `None`
+    /// if the pixel is outside the plot.
+    ///
+    /// The points found are for the series on the primary axis, in the order they were added,
+    /// followed by those on the secondary axis.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    /// use drawille::chart::LineChart;
+    ///
+    /// let chart = LineChart::new(80, 40).series(&[3.0, 1.0, 4.0, 1.0, 5.0]);
+    /// let mut canvas = Canvas::new(80, 40);
+    /// let found = chart.draw_crosshair(&mut canvas, 70, 10).unwrap();
+    /// assert_eq!(found.nearest, vec![Some((4, (4.0, 5.0)))]);
+    /// ```
+    pub fn draw_crosshair(&self, canvas: &mut Canvas, x: usize, y: usize) -> Option<Crosshair> {
+        let (area, secondary) = self.draw_areas(canvas)?;
+        let series = self.series.iter().map(|s| &s[..]).collect::<Vec<_>>();
+        let mut found = crosshair(canvas, &area, (x, y), &series)?;
+        if let Some(secondary) = secondary {
+            for series in self.secondary_series.iter() {
+                found.nearest.push(mark_nearest(canvas, &secondary, series, (x, y)));
+            }
+        }
+        Some(found)
+    }
+
+    /// Draws the chart onto the `Canvas`, returning its plot area and, if it has series on the
+    /// secondary axis, the plot area scaled to that axis.
+    fn draw_areas(&self, canvas: &mut Canvas) -> Option<(PlotArea, Option<PlotArea>)> {
+        let points = |series: &[Vec<(f64, f64)>]| series.iter().flat_map(|s| s.iter())
+                                                                .cloned().collect::<Vec<_>>();
+        let (primary, secondary) = (points(&self.series), points(&self.secondary_series));
+        let legend = self.labels.iter().enumerate().map(|(i, label)| {
+            LegendEntry::new(label.clone()).style(Some(self.style(i)))
+        }).collect::<Vec<_>>();
+        let layout = match self.legend_corner {
+            None => self.layout.clone().legend(legend.clone()),
+            Some(_) => self.layout.clone(),
+        };
+        let max_y_ticks = max_y_ticks(layout.axes_height());
+        let x_scale = self.x_axis.scale(primary.iter().chain(secondary.iter()).map(|p| p.0), None);
+        let error_ranges = self.series.iter().zip(self.errors.iter()).flat_map(|(series, errors)| {
+            series.iter().zip(errors.iter()).flat_map(|(p, e)| {
+                let (low, high) = e.range(p.1);
+                vec![low, high]
+            })
+        });
+        let y_scale = self.y_axis.scale(primary.iter().map(|p| p.1).chain(error_ranges),
+                                        Some(max_y_ticks));
+
+        if self.secondary_series.is_empty() {
+            let area = layout.draw(canvas, x_scale, y_scale)?;
+            for (i, series) in self.series.iter().enumerate() {
+                self.draw_series(canvas, &area, series, self.style(i));
+            }
+            self.draw_error_bars(canvas, &area);
+            draw_annotations(canvas, &area, &self.annotations);
+            if let Some(corner) = self.legend_corner {
+                draw_legend(canvas, &area, &legend, corner);
+            }
+            return Some((area, None));
+        }
+
+        let secondary_scale = self.secondary_axis.scale(secondary.iter().map(|p| p.1),
+                                                        Some(max_y_ticks));
+        let (area, secondary_area) = layout.draw_dual(canvas, x_scale, y_scale, secondary_scale)?;
+        for (i, series) in self.series.iter().enumerate() {
+            self.draw_series(canvas, &area, series, self.style(i));
+        }
+        for (i, series) in self.secondary_series.iter().enumerate() {
+            self.draw_series(canvas, &secondary_area, series,
+                             self.style(self.series.len() + i));
+        }
+        self.draw_error_bars(canvas, &area);
+        draw_annotations(canvas, &area, &self.annotations);
+        if let Some(corner) = self.legend_corner {
+            draw_legend(canvas, &area, &legend, corner);
+        }
+        Some((area, Some(secondary_area)))
+    }
+
+    /// Draws the chart to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+
+    fn draw_series(&self, canvas: &mut Canvas, area: &PlotArea, series: &[(f64, f64)],
+                   style: LineStyle) {
+        match self.downsampling {
+            Downsampling::None => draw_series(canvas, area, series, style),
+            how => draw_series(canvas, area, &downsample(series, area.width, how), style),
+        }
+    }
+
+    fn draw_error_bars(&self, canvas: &mut Canvas, area: &PlotArea) {
+        for (series, errors) in self.series.iter().zip(self.errors.iter()) {
+            draw_error_bars(canvas, area, series, errors, self.error_caps);
+        }
+    }
+
+    /// Returns the style of the series with the given index, counting those on the secondary axis
+    /// after the rest.
+    fn style(&self, index: usize) -> LineStyle {
+        self.styles.get(index).cloned().unwrap_or(LineStyle::Solid)
+    }
+}
+
+fn indexed(values: &[f64]) -> Vec<(f64, f64)> {
+    values.iter().enumerate().map(|(i, &y)| (i as f64, y)).collect()
+}
+
+/// Draws lines joining each point of a series that can be shown in the plot area, in the given
+/// style, which carries on from one line to the next, leaving gaps at points that can't be shown.
+///
+/// Lines are cut short at the edges of the plot area, so points outside its scales are joined to
+/// the rest without drawing over the axes.
+fn draw_series(canvas: &mut Canvas, area: &PlotArea, series: &[(f64, f64)], style: LineStyle) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let rect = (area.left as f64, area.top as f64, (area.left + area.width - 1) as f64,
+                (area.top + area.height - 1) as f64);
+    let mut last = None;
+    // The last pixel set or skipped, and how many have been since the start of the line.
+    let mut end = None;
+    let mut drawn = 0;
+    for &(x, y) in series.iter() {
+        let point = area.project(x, y);
+        if !point.0.is_finite() || !point.1.is_finite() {
+            // A gap in the data, which the line isn't drawn across.
+            last = None;
+            continue;
+        }
+        if let Some(from) = last {
+            if let Some((a, b)) = geometry::clip(from, point, rect) {
+                let (x1, y1) = (a.0.round() as usize, a.1.round() as usize);
+                let (x2, y2) = (b.0.round() as usize, b.1.round() as usize);
+                geometry::line_pixels(x1, y1, x2, y2, |px, py| {
+                    // A line starting where the last ended has its first pixel counted already.
+                    if end != Some((px, py)) {
+                        if style.draws(drawn) {
+                            canvas.set(px, py);
+                        }
+                        drawn += 1;
+                    }
+                    end = Some((px, py));
+                });
+            }
+        } else {
+            end = area.pixel(x, y);
+            drawn = 0;
+            if let Some((px, py)) = end {
+                canvas.set(px, py);
+                drawn = 1;
+            }
+        }
+        last = Some(point);
+    }
+}
+
+impl Chart for LineChart {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        LineChart::draw(self, canvas);
+    }
+
+    fn x_extent(&self) -> Option<(f64, f64)> {
+        self.x_axis.extent(self.series.iter().chain(self.secondary_series.iter())
+                                     .flat_map(|s| s.iter().map(|p| p.0)))
+    }
+
+    fn draw_x_range(&self, canvas: &mut Canvas, min: f64, max: f64) {
+        self.clone().x_range(min, max).draw(canvas);
+    }
+}
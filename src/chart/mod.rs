@@ -0,0 +1,65 @@
+//! Charts drawn with Braille characters
+//!
+//! This module provides ready-made charts that scale data onto a `braille::Canvas` and label their
+//! axes with text.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::chart::LineChart;
+//!
+//! let squares = (0..20).map(|x| (x * x) as f64).collect::<Vec<_>>();
+//! let chart = LineChart::new(60, 32).series(&squares);
+//! println!("{}", chart.frame());
+//! ```
+
+pub use self::annotation::{Annotation, draw_annotations};
+pub use self::area::{AreaChart, fill_between};
+pub use self::audio::{draw_spectrum, draw_waveform, spectrum, waveform};
+pub use self::bar::{BarChart, BarMode};
+pub use self::axis::{PlotArea, Scale, ScaleKind, draw_axes, draw_dual_axes, format_tick,
+                     max_y_ticks};
+pub use self::contour::{contour_lines, draw_contours, plot_contours};
+#[cfg(feature = "csv")]
+pub use self::csv::{Column, CsvError, CsvSeries, from_csv, scatter_from_csv};
+pub use self::crosshair::{Crosshair, crosshair};
+pub use self::downsample::{Downsampling, lttb, min_max};
+pub use self::error_bar::{ErrorBar, draw_error_bars};
+pub use self::function::plot_fn;
+pub use self::gauge::Gauge;
+pub use self::grid::{Chart, ChartGrid};
+pub use self::layout::{ChartLayout, Corner, LegendEntry, draw_legend};
+pub use self::line::LineChart;
+pub use self::polar::{PolarChart, RadarChart};
+pub use self::progress::progress_bar;
+pub use self::quiver::{FieldGrid, quiver};
+pub use self::scatter::Scatter;
+pub use self::sparkline::sparkline;
+pub use self::spectrogram::Spectrogram;
+pub use self::streaming::StreamingPlot;
+pub use self::time::timestamp;
+
+mod annotation;
+mod area;
+mod audio;
+mod bar;
+mod axis;
+mod contour;
+mod crosshair;
+mod downsample;
+mod error_bar;
+#[cfg(feature = "csv")]
+mod csv;
+mod function;
+mod gauge;
+mod grid;
+mod layout;
+mod line;
+mod polar;
+mod progress;
+mod quiver;
+mod scatter;
+mod sparkline;
+mod spectrogram;
+mod streaming;
+mod time;
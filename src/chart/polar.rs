@@ -0,0 +1,335 @@
+use std::f64;
+
+use braille::Canvas;
+use super::{Chart, Scale};
+
+/// The most rings to draw for the radial axis of a polar chart.
+const MAX_RINGS: usize = 4;
+
+/// A polar chart of one or more series of `(angle, radius)` points, such as an antenna pattern.
+///
+/// Angles are in degrees, measured anticlockwise from the right of the chart. The radial axis is
+/// scaled from zero to fit the data unless given an explicit range, and is marked with labelled
+/// rings; radii outside the range are clamped to it.
+///
+/// ```
+/// use drawille::chart::PolarChart;
+///
+/// let pattern = (0..=72).map(|i| {
+///     let angle = i as f64 * 5.0;
+///     (angle, angle.to_radians().cos().abs().powi(2))
+/// }).collect::<Vec<_>>();
+/// println!("{}", PolarChart::new(60, 40).series(&pattern).frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolarChart {
+    width: usize,
+    height: usize,
+    series: Vec<Vec<(f64, f64)>>,
+    range: Option<(f64, f64)>,
+}
+
+impl PolarChart {
+    /// Creates a new, empty `PolarChart` with the given width and height in pixels, including its
+    /// ring labels.
+    pub fn new(width: usize, height: usize) -> PolarChart {
+        PolarChart { width, height, series: vec![], range: None }
+    }
+
+    /// Adds a series of `(angle, radius)` points, joined in order, and returns the chart for use
+    /// again.
+    pub fn series(mut self, points: &[(f64, f64)]) -> PolarChart {
+        self.series.push(points.to_vec());
+        self
+    }
+
+    /// Sets the radii shown at the centre and at the edge of the chart instead of fitting them to
+    /// the data, and returns the chart for use again.
+    pub fn range(mut self, min: f64, max: f64) -> PolarChart {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Draws the chart onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let scale = match self.range {
+            Some((min, max)) => Scale::new(min, max),
+            None => {
+                let radii = self.series.iter().flat_map(|s| s.iter().map(|p| p.1));
+                Scale::fit(radii.chain(Some(0.0))).nice(MAX_RINGS + 1)
+            },
+        };
+        let polar = match PolarArea::new(self.width, self.height, 0, 1) {
+            Some(polar) => polar,
+            None => return,
+        };
+
+        for angle in (0..4).map(|i| i as f64 * 90.0) {
+            polar.dotted_spoke(canvas, angle);
+        }
+        for (tick, label) in scale.tick_labels(MAX_RINGS + 1) {
+            let t = scale.normalize(tick);
+            if t <= 0.0 {
+                continue;
+            }
+            polar.ring(canvas, t, t < 1.0 - 1e-9);
+            let (x, y) = polar.point(t, 90.0);
+            canvas.text(x as usize + 2, y as usize, label);
+        }
+
+        for series in self.series.iter() {
+            let points = series.iter().map(|&(angle, r)| (scale.normalize(r), angle));
+            polar.polyline(canvas, points, false);
+        }
+    }
+
+    /// Draws the chart to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+/// A radar chart comparing one or more series of values along several labelled spokes.
+///
+/// The first spoke points straight up and the rest follow clockwise. Every spoke shares the same
+/// scale, from zero to fit the data unless given an explicit range, and each series is drawn as a
+/// closed outline joining its values on successive spokes.
+///
+/// ```
+/// use drawille::chart::RadarChart;
+///
+/// let chart = RadarChart::new(60, 40, &["speed", "power", "range", "cost", "weight"])
+///     .series(&[4.0, 3.0, 5.0, 2.0, 3.5])
+///     .series(&[2.0, 5.0, 3.0, 4.0, 1.0]);
+/// println!("{}", chart.frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadarChart {
+    width: usize,
+    height: usize,
+    spokes: Vec<String>,
+    series: Vec<Vec<f64>>,
+    range: Option<(f64, f64)>,
+}
+
+impl RadarChart {
+    /// Creates a new, empty `RadarChart` with the given width and height in pixels, including its
+    /// spoke labels, and one spoke for each label.
+    pub fn new<S: AsRef<str>>(width: usize, height: usize, spokes: &[S]) -> RadarChart {
+        RadarChart {
+            width,
+            height,
+            spokes: spokes.iter().map(|s| s.as_ref().to_string()).collect(),
+            series: vec![],
+            range: None,
+        }
+    }
+
+    /// Adds a series with one value for each spoke, and returns the chart for use again.
+    ///
+    /// Missing values are drawn at the centre of the chart, and extra values are ignored.
+    pub fn series(mut self, values: &[f64]) -> RadarChart {
+        self.series.push(values.to_vec());
+        self
+    }
+
+    /// Sets the values shown at the centre and at the ends of the spokes instead of fitting them
+    /// to the data, and returns the chart for use again.
+    pub fn range(mut self, min: f64, max: f64) -> RadarChart {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Draws the chart onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let n = self.spokes.len();
+        if n == 0 {
+            return;
+        }
+        let scale = match self.range {
+            Some((min, max)) => Scale::new(min, max),
+            None => {
+                let values = self.series.iter().flat_map(|s| s.iter().cloned());
+                Scale::fit(values.chain(Some(0.0)))
+            },
+        };
+        let label_cols = self.spokes.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+        let polar = match PolarArea::new(self.width, self.height, label_cols + 1, 2) {
+            Some(polar) => polar,
+            None => return,
+        };
+        // Spokes go clockwise from the top of the chart.
+        let spoke_angle = |i: usize| 90.0 - i as f64 * 360.0 / n as f64;
+
+        for (i, label) in self.spokes.iter().enumerate() {
+            let angle = spoke_angle(i);
+            polar.line(canvas, (0.0, angle), (1.0, angle));
+            polar.label(canvas, angle, label);
+        }
+        let rim = (0..n).map(|i| (1.0, spoke_angle(i)));
+        polar.dotted_polyline(canvas, rim);
+
+        for series in self.series.iter() {
+            let points = (0..n).map(|i| {
+                let value = series.get(i).cloned().unwrap_or(f64::NAN);
+                let t = scale.normalize(value);
+                (if t.is_finite() { t } else { 0.0 }, spoke_angle(i))
+            });
+            polar.polyline(canvas, points, true);
+        }
+    }
+
+    /// Draws the chart to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+/// The circular region of a canvas that a polar or radar chart is drawn in.
+struct PolarArea {
+    cx: f64,
+    cy: f64,
+    radius: f64,
+}
+
+impl PolarArea {
+    /// Centres the largest circle that fits in a chart of the given size, leaving `label_cols`
+    /// character columns and `label_rows` character rows free on every side for labels.
+    fn new(width: usize, height: usize, label_cols: usize, label_rows: usize)
+           -> Option<PolarArea> {
+        let (cols, rows) = (width / 2, height / 4);
+        if cols <= label_cols * 2 || rows <= label_rows * 2 {
+            return None;
+        }
+        let inner_width = (cols - label_cols * 2) * 2;
+        let inner_height = (rows - label_rows * 2) * 4;
+        let radius = ((inner_width.min(inner_height) - 1) / 2) as f64;
+        if radius < 2.0 {
+            return None;
+        }
+        Some(PolarArea {
+            cx: (label_cols * 2 + inner_width / 2) as f64,
+            cy: (label_rows * 4 + inner_height / 2) as f64,
+            radius,
+        })
+    }
+
+    /// Returns the pixel at `t` of the way from the centre to the edge, at `angle` degrees
+    /// anticlockwise from the right. `t` is clamped to the area.
+    fn point(&self, t: f64, angle: f64) -> (f64, f64) {
+        let r = t.clamp(0.0, 1.0) * self.radius;
+        let angle = angle.to_radians();
+        ((self.cx + r * angle.cos()).round(), (self.cy - r * angle.sin()).round())
+    }
+
+    fn line(&self, canvas: &mut Canvas, from: (f64, f64), to: (f64, f64)) {
+        let (x1, y1) = self.point(from.0, from.1);
+        let (x2, y2) = self.point(to.0, to.1);
+        canvas.line(x1 as usize, y1 as usize, x2 as usize, y2 as usize);
+    }
+
+    /// Joins successive `(t, angle)` points, leaving a gap at any that aren't finite, and
+    /// optionally joins the last point back to the first if there are no gaps.
+    fn polyline<I: Iterator<Item = (f64, f64)>>(&self, canvas: &mut Canvas, points: I,
+                                                closed: bool) {
+        let points = points.collect::<Vec<_>>();
+        let runs = points.split(|p| !p.0.is_finite() || !p.1.is_finite())
+                         .filter(|run| !run.is_empty()).collect::<Vec<_>>();
+        for run in runs.iter() {
+            for pair in run.windows(2) {
+                self.line(canvas, pair[0], pair[1]);
+            }
+            if run.len() == 1 {
+                let (x, y) = self.point(run[0].0, run[0].1);
+                canvas.set(x as usize, y as usize);
+            }
+        }
+        if let (true, [run]) = (closed, &runs[..]) {
+            if run.len() == points.len() && run.len() > 1 {
+                self.line(canvas, run[run.len() - 1], run[0]);
+            }
+        }
+    }
+
+    /// Joins successive `(t, angle)` points and the last back to the first with dotted lines.
+    fn dotted_polyline<I: Iterator<Item = (f64, f64)>>(&self, canvas: &mut Canvas, points: I) {
+        let points = points.map(|(t, angle)| self.point(t, angle)).collect::<Vec<_>>();
+        for (i, &(x1, y1)) in points.iter().enumerate() {
+            let (x2, y2) = points[(i + 1) % points.len()];
+            let steps = (x2 - x1).abs().max((y2 - y1).abs()).max(1.0) as usize;
+            for step in (0..steps).step_by(2) {
+                let s = step as f64 / steps as f64;
+                canvas.set((x1 + (x2 - x1) * s).round() as usize,
+                           (y1 + (y2 - y1) * s).round() as usize);
+            }
+        }
+    }
+
+    fn dotted_spoke(&self, canvas: &mut Canvas, angle: f64) {
+        for step in (0..self.radius as usize).step_by(2) {
+            let (x, y) = self.point(step as f64 / self.radius, angle);
+            canvas.set(x as usize, y as usize);
+        }
+    }
+
+    /// Draws a circle `t` of the way from the centre to the edge, optionally dotted.
+    fn ring(&self, canvas: &mut Canvas, t: f64, dotted: bool) {
+        // Enough steps to leave no gaps between neighbouring pixels.
+        let steps = ((t * self.radius * 8.0).ceil() as usize).max(8);
+        let mut last = None;
+        for step in 0..steps {
+            let (x, y) = self.point(t, step as f64 * 360.0 / steps as f64);
+            let pixel = (x as usize, y as usize);
+            if last == Some(pixel) {
+                continue;
+            }
+            last = Some(pixel);
+            if !dotted || (pixel.0 + pixel.1).is_multiple_of(3) {
+                canvas.set(pixel.0, pixel.1);
+            }
+        }
+    }
+
+    /// Writes a label just beyond the end of the spoke at `angle`, placed to the spoke's side so
+    /// it reads away from the chart.
+    fn label(&self, canvas: &mut Canvas, angle: f64, label: &str) {
+        let (x, y) = self.point(1.0, angle);
+        let (dx, dy) = (angle.to_radians().cos(), -angle.to_radians().sin());
+        let len = label.chars().count() as f64;
+        // Move one character beyond the rim, then line the label up against that point.
+        let col = (x / 2.0 + dx * 1.5).round();
+        let row = (y / 4.0 + dy * 1.5).round();
+        let start = if dx > 0.3 {
+            col
+        } else if dx < -0.3 {
+            col - len + 1.0
+        } else {
+            col - (len / 2.0).floor()
+        };
+        canvas.text(start.max(0.0) as usize * 2, row.max(0.0) as usize * 4, label);
+    }
+}
+
+impl Chart for PolarChart {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        PolarChart::draw(self, canvas);
+    }
+}
+
+impl Chart for RadarChart {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        RadarChart::draw(self, canvas);
+    }
+}
@@ -0,0 +1,28 @@
+use std::char;
+
+/// The dots of a Braille character in the order a progress bar fills them: down the left column,
+/// then down the right.
+static FILL_ORDER: [u32; 8] = [0x01, 0x02, 0x04, 0x40, 0x08, 0x10, 0x20, 0x80];
+
+/// Draws a progress bar `width` characters wide, filled to `fraction` of its length.
+///
+/// Each character fills a dot at a time, so the bar moves in eight steps per character rather than
+/// one. `fraction` is clamped to between 0 and 1.
+///
+/// ```
+/// use drawille::chart::progress_bar;
+///
+/// assert_eq!(progress_bar(0.55, 4), "⣿⣿⠃ ");
+/// ```
+pub fn progress_bar(fraction: f64, width: usize) -> String {
+    let fraction = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+    let dots = (fraction * (width * FILL_ORDER.len()) as f64).round() as usize;
+    (0..width).map(|i| {
+        let filled = dots.saturating_sub(i * FILL_ORDER.len()).min(FILL_ORDER.len());
+        if filled == 0 {
+            return ' ';
+        }
+        let mask = FILL_ORDER[..filled].iter().fold(0, |mask, &dot| mask | dot);
+        char::from_u32(0x2800 + mask).unwrap()
+    }).collect()
+}
@@ -0,0 +1,119 @@
+use braille::Canvas;
+use super::{PlotArea, Scale, draw_axes};
+
+/// The region of the plane a vector field is sampled over, and how densely.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FieldGrid {
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    size: (usize, usize),
+    spacing: usize,
+}
+
+impl FieldGrid {
+    /// Creates a new `FieldGrid` covering `x_range` by `y_range`, for a plot `size.0` by `size.1`
+    /// pixels including its axes, with an arrow every 8 pixels.
+    pub fn new(x_range: (f64, f64), y_range: (f64, f64), size: (usize, usize)) -> FieldGrid {
+        FieldGrid { x_range, y_range, size, spacing: 8 }
+    }
+
+    /// Sets the distance in pixels between neighbouring arrows, and returns the grid for use
+    /// again.
+    pub fn spacing(mut self, spacing: usize) -> FieldGrid {
+        self.spacing = spacing.max(2);
+        self
+    }
+}
+
+/// Plots the vector field `f` as arrows on a grid, with labelled axes, and returns it as a
+/// `String`.
+///
+/// Each arrow is centred on the point it was sampled at and points along `f(x, y)`. Arrows are
+/// scaled in proportion to the size of the vectors so that the longest is `scale` times the grid
+/// spacing; keep `scale` at or below one to stop neighbouring arrows overlapping. Points where `f`
+/// is zero, or not finite, are marked with a dot.
+///
+/// ```
+/// use drawille::chart::{FieldGrid, quiver};
+///
+/// // The phase portrait of a damped pendulum.
+/// let grid = FieldGrid::new((-4.0, 4.0), (-3.0, 3.0), (80, 48));
+/// println!("{}", quiver(|x, y| (y, -x.sin() - 0.3 * y), grid, 0.9));
+/// ```
+pub fn quiver<F: Fn(f64, f64) -> (f64, f64)>(f: F, grid: FieldGrid, scale: f64) -> String {
+    let (width, height) = grid.size;
+    let mut canvas = Canvas::new(width, height);
+    let x_scale = Scale::new(grid.x_range.0, grid.x_range.1);
+    let y_scale = Scale::new(grid.y_range.0, grid.y_range.1);
+    let area = match draw_axes(&mut canvas, width, height, x_scale, y_scale) {
+        Some(area) => area,
+        None => return canvas.frame(),
+    };
+
+    // Pixels per unit along each axis, so arrows point the right way on screen.
+    let x_density = area.width.saturating_sub(1) as f64 / (x_scale.max - x_scale.min);
+    let y_density = area.height.saturating_sub(1) as f64 / (y_scale.max - y_scale.min);
+    let spacing = grid.spacing;
+    let mut arrows = vec![];
+    for py in (area.top + spacing / 2..area.top + area.height).step_by(spacing) {
+        for px in (area.left + spacing / 2..area.left + area.width).step_by(spacing) {
+            let x = x_scale.value_at((px - area.left) as f64, area.width);
+            let y = y_scale.value_at((area.top + area.height - 1 - py) as f64, area.height);
+            let (dx, dy) = f(x, y);
+            arrows.push(((px as f64, py as f64), (dx * x_density, -dy * y_density)));
+        }
+    }
+
+    let length = |v: (f64, f64)| (v.0 * v.0 + v.1 * v.1).sqrt();
+    let longest = arrows.iter().map(|a| length(a.1)).filter(|l| l.is_finite())
+                        .fold(0.0, f64::max);
+    for &(centre, v) in arrows.iter() {
+        let l = length(v);
+        if !(l > 0.0 && l.is_finite()) {
+            set_clamped(&mut canvas, &area, centre);
+            continue;
+        }
+        let k = scale * spacing as f64 / longest / 2.0;
+        let (half_x, half_y) = (v.0 * k, v.1 * k);
+        let tail = (centre.0 - half_x, centre.1 - half_y);
+        let tip = (centre.0 + half_x, centre.1 + half_y);
+        draw_arrow(&mut canvas, &area, tail, tip);
+    }
+    canvas.frame()
+}
+
+/// Draws a line from `tail` to `tip` with a small arrowhead at `tip`, kept within the plot area.
+fn draw_arrow(canvas: &mut Canvas, area: &PlotArea, tail: (f64, f64), tip: (f64, f64)) {
+    line_clamped(canvas, area, tail, tip);
+    let (dx, dy) = (tip.0 - tail.0, tip.1 - tail.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < 2.0 {
+        return;
+    }
+    let head = (length / 3.0).clamp(1.5, 3.0);
+    let (ux, uy) = (dx / length, dy / length);
+    // Barbs swept back 30 degrees either side of the shaft.
+    let (cos, sin) = (0.866, 0.5);
+    for &side in [-1.0, 1.0].iter() {
+        let bx = -(ux * cos - uy * sin * side);
+        let by = -(uy * cos + ux * sin * side);
+        line_clamped(canvas, area, tip, (tip.0 + bx * head, tip.1 + by * head));
+    }
+}
+
+fn clamp_to_area(area: &PlotArea, (x, y): (f64, f64)) -> (usize, usize) {
+    let x = x.round().clamp(area.left as f64, (area.left + area.width - 1) as f64);
+    let y = y.round().clamp(area.top as f64, (area.top + area.height - 1) as f64);
+    (x as usize, y as usize)
+}
+
+fn set_clamped(canvas: &mut Canvas, area: &PlotArea, p: (f64, f64)) {
+    let (x, y) = clamp_to_area(area, p);
+    canvas.set(x, y);
+}
+
+fn line_clamped(canvas: &mut Canvas, area: &PlotArea, a: (f64, f64), b: (f64, f64)) {
+    let (x1, y1) = clamp_to_area(area, a);
+    let (x2, y2) = clamp_to_area(area, b);
+    canvas.line(x1, y1, x2, y2);
+}
@@ -0,0 +1,211 @@
+use block::Color;
+use braille::Canvas;
+use super::{Annotation, Chart, ChartLayout, ErrorBar, LegendEntry, ScaleKind, draw_annotations,
+            draw_error_bars, max_y_ticks};
+use super::axis::AxisSpec;
+
+/// A scatter plot of one or more series of points, with labelled axes.
+///
+/// Axes are scaled to fit the data unless given explicit ranges, and points falling outside the
+/// ranges aren’t drawn. Each series may be given a colour; since colours apply to whole Braille
+/// characters, points of differently coloured series that share a character take the colour of the
+/// series added last.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scatter {
+    width: usize,
+    height: usize,
+    series: Vec<PointSeries>,
+    x_axis: AxisSpec,
+    y_axis: AxisSpec,
+    layout: ChartLayout,
+    labels: Vec<String>,
+    error_caps: usize,
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct PointSeries {
+    points: Vec<(f64, f64)>,
+    color: Option<Color>,
+    errors: Vec<ErrorBar>,
+}
+
+impl PointSeries {
+    fn new(points: &[(f64, f64)]) -> PointSeries {
+        PointSeries { points: points.to_vec(), color: None, errors: vec![] }
+    }
+}
+
+impl Scatter {
+    /// Creates a new, empty `Scatter` plot with the given width and height in pixels, including
+    /// its axes and their labels.
+    pub fn new(width: usize, height: usize) -> Scatter {
+        Scatter {
+            width,
+            height,
+            series: vec![],
+            x_axis: AxisSpec::new(),
+            y_axis: AxisSpec::new(),
+            layout: ChartLayout::new(width, height),
+            labels: vec![],
+            error_caps: 1,
+            annotations: vec![],
+        }
+    }
+
+    /// Adds a series of `(x, y)` points, and returns the plot for use again.
+    pub fn points(mut self, points: &[(f64, f64)]) -> Scatter {
+        self.series.push(PointSeries::new(points));
+        self
+    }
+
+    /// Adds a series of `(x, y)` points drawn in the given colour, and returns the plot for use
+    /// again.
+    pub fn colored_points(mut self, points: &[(f64, f64)], color: Color) -> Scatter {
+        self.series.push(PointSeries { color: Some(color), ..PointSeries::new(points) });
+        self
+    }
+
+    /// Gives each point of the series added last error bars, in order, and returns the plot for
+    /// use again.
+    ///
+    /// The y axis is fitted to the error bars as well as the points.
+    pub fn error_bars(mut self, errors: &[ErrorBar]) -> Scatter {
+        if let Some(series) = self.series.last_mut() {
+            series.errors = errors.to_vec();
+        }
+        self
+    }
+
+    /// Sets how many pixels the caps of error bars reach either side of the bars, or zero for no
+    /// caps, and returns the plot for use again.
+    pub fn error_caps(mut self, cap: usize) -> Scatter {
+        self.error_caps = cap;
+        self
+    }
+
+    /// Adds an annotation, drawn over the points in the order annotations were added, and returns
+    /// the plot for use again. The axes aren't fitted to annotations.
+    pub fn annotate(mut self, annotation: Annotation) -> Scatter {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Sets the range of the x axis instead of fitting it to the data, and returns the plot for
+    /// use again.
+    pub fn x_range(mut self, min: f64, max: f64) -> Scatter {
+        self.x_axis.range = Some((min, max));
+        self
+    }
+
+    /// Sets the range of the y axis instead of fitting it to the data, and returns the plot for
+    /// use again.
+    pub fn y_range(mut self, min: f64, max: f64) -> Scatter {
+        self.y_axis.range = Some((min, max));
+        self
+    }
+
+    /// Gives the x axis a logarithmic scale, and returns the plot for use again.
+    pub fn log_x(mut self) -> Scatter {
+        self.x_axis.kind = ScaleKind::Logarithmic;
+        self
+    }
+
+    /// Gives the x axis a time scale, for x values that are times in seconds since the Unix epoch
+    /// as given by `chart::timestamp`, and returns the plot for use again.
+    pub fn time_x(mut self) -> Scatter {
+        self.x_axis.kind = ScaleKind::Time;
+        self
+    }
+
+    /// Gives the y axis a logarithmic scale, and returns the plot for use again.
+    pub fn log_y(mut self) -> Scatter {
+        self.y_axis.kind = ScaleKind::Logarithmic;
+        self
+    }
+
+    /// Sets the title shown above the plot, and returns the plot for use again.
+    pub fn title<S: Into<String>>(mut self, title: S) -> Scatter {
+        self.layout = self.layout.title(title);
+        self
+    }
+
+    /// Sets the title of the x axis, and returns the plot for use again.
+    pub fn x_title<S: Into<String>>(mut self, title: S) -> Scatter {
+        self.layout = self.layout.x_title(title);
+        self
+    }
+
+    /// Sets the title of the y axis, and returns the plot for use again.
+    pub fn y_title<S: Into<String>>(mut self, title: S) -> Scatter {
+        self.layout = self.layout.y_title(title);
+        self
+    }
+
+    /// Names the series in the order they were added, listing them in a legend, and returns the
+    /// plot for use again.
+    pub fn labels<S: AsRef<str>>(mut self, labels: &[S]) -> Scatter {
+        self.labels = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Draws the plot onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let points = || self.series.iter().flat_map(|s| s.points.iter());
+        let legend = self.labels.iter().zip(self.series.iter()).map(|(label, series)| {
+            LegendEntry { color: series.color, ..LegendEntry::new(label.clone()).style(None) }
+        }).collect();
+        let layout = self.layout.clone().legend(legend);
+        let x_scale = self.x_axis.scale(points().map(|p| p.0), None);
+        let error_ranges = self.series.iter().flat_map(|s| {
+            s.points.iter().zip(s.errors.iter()).flat_map(|(p, e)| {
+                let (low, high) = e.range(p.1);
+                vec![low, high]
+            })
+        });
+        let y_scale = self.y_axis.scale(points().map(|p| p.1).chain(error_ranges),
+                                        Some(max_y_ticks(layout.axes_height())));
+        let area = match layout.draw(canvas, x_scale, y_scale) {
+            Some(area) => area,
+            None => return,
+        };
+
+        for series in self.series.iter() {
+            draw_error_bars(canvas, &area, &series.points, &series.errors, self.error_caps);
+            for &(x, y) in series.points.iter() {
+                if let Some((x, y)) = area.pixel(x, y) {
+                    match series.color {
+                        Some(color) => canvas.set_colored(x, y, color),
+                        None => canvas.set(x, y),
+                    }
+                }
+            }
+        }
+        draw_annotations(canvas, &area, &self.annotations);
+    }
+
+    /// Draws the plot to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+impl Chart for Scatter {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        Scatter::draw(self, canvas);
+    }
+
+    fn x_extent(&self) -> Option<(f64, f64)> {
+        self.x_axis.extent(self.series.iter().flat_map(|s| s.points.iter().map(|p| p.0)))
+    }
+
+    fn draw_x_range(&self, canvas: &mut Canvas, min: f64, max: f64) {
+        self.clone().x_range(min, max).draw(canvas);
+    }
+}
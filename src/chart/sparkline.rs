@@ -0,0 +1,40 @@
+use braille::Canvas;
+use super::Scale;
+
+/// Draws `values` as a one-line sparkline `width` characters wide.
+///
+/// Each character shows two columns of bars up to four pixels high, scaled so that the smallest
+/// value gets one pixel and the largest gets four. When there are more values than columns, each
+/// column shows the average of the values falling in it; when there are fewer, values are
+/// stretched across several columns. Columns with no finite values are left blank.
+///
+/// ```
+/// use drawille::chart::sparkline;
+///
+/// assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0, std::f64::NAN, 4.0], 3), "⣠⣾⢸");
+/// ```
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    let columns = width * 2;
+    let mut canvas = Canvas::new(columns, 4);
+    let scale = Scale::fit(values.iter().cloned());
+    for column in 0..columns {
+        let (start, end) = (column * values.len() / columns, (column + 1) * values.len() / columns);
+        let bucket = if end > start {
+            &values[start..end]
+        } else if start < values.len() {
+            &values[start..start + 1]
+        } else {
+            &[]
+        };
+        let finite = bucket.iter().filter(|v| v.is_finite()).collect::<Vec<_>>();
+        if finite.is_empty() {
+            continue;
+        }
+        let mean = finite.iter().cloned().sum::<f64>() / finite.len() as f64;
+        let height = 1 + (scale.normalize(mean) * 3.0).round() as usize;
+        for y in 4 - height..4 {
+            canvas.set(column, y);
+        }
+    }
+    canvas.rows()[0].chars().take(width).collect()
+}
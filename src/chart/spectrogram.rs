@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+
+use block::{self, Color};
+use braille;
+use dither;
+use super::{Chart, Scale};
+
+/// A scrolling waterfall display, such as a spectrogram, showing one row of magnitudes per frame.
+///
+/// Each pushed row becomes one row of pixels at the top of the display, pushing older rows down
+/// and eventually off the bottom. Intensities can be drawn as dithered Braille dots or as colours
+/// on a `block::Canvas`.
+///
+/// ```
+/// use drawille::chart::Spectrogram;
+///
+/// let mut waterfall = Spectrogram::new(64, 16);
+/// for frame in 0..16 {
+///     let bins = (0..64).map(|bin| ((bin + frame) as f64 / 6.0).sin().abs()).collect::<Vec<_>>();
+///     waterfall.push(&bins);
+/// }
+/// println!("{}", waterfall.frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spectrogram {
+    width: usize,
+    height: usize,
+    rows: VecDeque<Vec<f64>>,
+    range: Option<Scale>,
+}
+
+impl Spectrogram {
+    /// Creates a new, empty `Spectrogram` with the given width and height in pixels.
+    ///
+    /// Intensities are scaled to fit the magnitudes currently shown unless given an explicit
+    /// range.
+    pub fn new(width: usize, height: usize) -> Spectrogram {
+        Spectrogram {
+            width,
+            height,
+            rows: VecDeque::with_capacity(height),
+            range: None,
+        }
+    }
+
+    /// Sets the magnitudes shown with no intensity and with full intensity instead of fitting them
+    /// to the data, and returns the spectrogram for use again.
+    pub fn range(mut self, min: f64, max: f64) -> Spectrogram {
+        self.range = Some(Scale::new(min, max));
+        self
+    }
+
+    /// Adds a new row of magnitudes at the top of the display, dropping the oldest row if the
+    /// display is full.
+    ///
+    /// The magnitudes are resampled to the width of the display, averaging neighbouring bins when
+    /// there are more bins than columns. Non-finite magnitudes are shown with no intensity.
+    pub fn push(&mut self, magnitudes: &[f64]) {
+        if self.height == 0 {
+            return;
+        }
+        if self.rows.len() == self.height {
+            self.rows.pop_back();
+        }
+        let n = magnitudes.len();
+        let row = (0..self.width).map(|x| {
+            let (start, end) = (x * n / self.width, (x + 1) * n / self.width);
+            let bins = if end > start {
+                &magnitudes[start..end]
+            } else if start < n {
+                &magnitudes[start..start + 1]
+            } else {
+                &[]
+            };
+            bins.iter().sum::<f64>() / bins.len() as f64
+        }).collect();
+        self.rows.push_front(row);
+    }
+
+    /// Removes every row from the display.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    /// Returns the intensity of every magnitude shown, from zero to one, newest row first.
+    fn intensities(&self) -> Vec<Vec<f64>> {
+        let scale = self.range.unwrap_or_else(|| {
+            Scale::fit(self.rows.iter().flat_map(|row| row.iter().cloned()))
+        });
+        self.rows.iter().map(|row| row.iter().map(|&m| {
+            let t = scale.normalize(m);
+            if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 }
+        }).collect()).collect()
+    }
+
+    /// Draws the display onto a Braille `Canvas` as dithered dots, with its top left corner at the
+    /// origin.
+    pub fn draw(&self, canvas: &mut braille::Canvas) {
+        for (y, row) in self.intensities().iter().enumerate() {
+            for (x, &t) in row.iter().enumerate() {
+                if dither::is_set(x, y, t) {
+                    canvas.set(x, y);
+                }
+            }
+        }
+    }
+
+    /// Draws the display onto a block `Canvas` in colour, from black for no intensity through blue,
+    /// magenta, red and yellow to white for full intensity.
+    pub fn draw_colored(&self, canvas: &mut block::Canvas) {
+        for (y, row) in self.intensities().iter().enumerate() {
+            for (x, &t) in row.iter().enumerate() {
+                let index = (t * (HEAT_COLORS.len() - 1) as f64).round() as usize;
+                canvas.set(x, y, HEAT_COLORS[index]);
+            }
+        }
+    }
+
+    /// Draws the display as dithered Braille dots to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = braille::Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+static HEAT_COLORS: [Color; 6] = [Color::Black, Color::Blue, Color::Magenta, Color::Red,
+                                  Color::Yellow, Color::White];
+
+impl Chart for Spectrogram {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut braille::Canvas) {
+        Spectrogram::draw(self, canvas);
+    }
+}
@@ -0,0 +1,197 @@
+use std::cmp;
+use std::collections::VecDeque;
+use std::io;
+
+use backend::RenderBackend;
+use braille::Canvas;
+use frame::Frame;
+use super::{Chart, Scale};
+
+/// A plot of the most recent samples of a stream, scrolling left as new samples arrive.
+///
+/// Each sample takes up one column of pixels, with the newest at the right edge. Samples are
+/// rasterized as they are pushed, so pushing a sample only works out the pixels of the new column
+/// unless the vertical scale changes. Every column moves along as the plot scrolls, so the plot is
+/// drawn afresh each time; to redraw a terminal, `render_changes` writes only the characters
+/// that differ from those it wrote the time before.
+///
+/// ```
+/// use drawille::chart::StreamingPlot;
+///
+/// let mut plot = StreamingPlot::new(40, 8).y_range(0.0, 100.0);
+/// for load in [12.0, 30.0, 45.0, 41.0, 80.0, 64.0].iter() {
+///     plot.push(*load);
+/// }
+/// println!("{}", plot.frame());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamingPlot {
+    width: usize,
+    height: usize,
+    samples: VecDeque<f64>,
+    /// The range of pixel rows drawn in each sample’s column.
+    columns: VecDeque<Option<(usize, usize)>>,
+    fixed_scale: Option<Scale>,
+    scale: Scale,
+    /// The frame last written by `render_changes`.
+    rendered: Option<Frame>,
+}
+
+impl StreamingPlot {
+    /// Creates a new, empty `StreamingPlot` with the given width and height in pixels, keeping
+    /// one sample per column.
+    ///
+    /// The vertical scale fits the samples currently shown unless given an explicit range.
+    pub fn new(width: usize, height: usize) -> StreamingPlot {
+        StreamingPlot {
+            width,
+            height,
+            samples: VecDeque::with_capacity(width),
+            columns: VecDeque::with_capacity(width),
+            fixed_scale: None,
+            scale: Scale::fit(None),
+            rendered: None,
+        }
+    }
+
+    /// Sets the vertical range of the plot instead of fitting it to the samples, and returns the
+    /// plot for use again.
+    pub fn y_range(mut self, min: f64, max: f64) -> StreamingPlot {
+        self.fixed_scale = Some(Scale::new(min, max));
+        self.rescale();
+        self
+    }
+
+    /// Adds a new sample at the right edge of the plot, dropping the oldest sample if the plot is
+    /// full.
+    ///
+    /// Non-finite samples leave a gap in the plot.
+    pub fn push(&mut self, value: f64) {
+        if self.width == 0 {
+            return;
+        }
+        if self.samples.len() == self.width {
+            self.samples.pop_front();
+            self.columns.pop_front();
+        }
+        self.samples.push_back(value);
+        self.columns.push_back(None);
+
+        let scale = self.fixed_scale.unwrap_or_else(|| Scale::fit(self.samples.iter().cloned()));
+        if scale != self.scale {
+            self.scale = scale;
+            self.rescale();
+        } else {
+            let last = self.samples.len() - 1;
+            self.columns[last] = self.column(last);
+            // The oldest column no longer joins on to the sample that was dropped.
+            self.columns[0] = self.column(0);
+        }
+    }
+
+    /// Returns the samples currently shown, oldest first.
+    pub fn samples(&self) -> &VecDeque<f64> {
+        &self.samples
+    }
+
+    /// Removes every sample from the plot.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.columns.clear();
+        self.rescale();
+    }
+
+    /// Draws the plot onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let offset = self.width - self.columns.len();
+        for (i, column) in self.columns.iter().enumerate() {
+            if let Some((top, bottom)) = *column {
+                for y in top..bottom + 1 {
+                    canvas.set(offset + i, y);
+                }
+            }
+        }
+    }
+
+    /// Draws the plot to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+
+    /// Draws the plot to a `Frame` and returns it.
+    pub fn to_frame(&self) -> Frame {
+        let mut canvas = Canvas::new(self.width, self.height);
+        self.draw(&mut canvas);
+        canvas.to_frame()
+    }
+
+    /// Writes the characters of the plot that changed since this was last called to a backend,
+    /// and then flushes it, or writes every character the first time.
+    ///
+    /// If writing fails, every character is written the next time, since there's no telling
+    /// what the backend shows.
+    ///
+    /// ```
+    /// use drawille::backend::CaptureBackend;
+    /// use drawille::chart::StreamingPlot;
+    ///
+    /// let mut plot = StreamingPlot::new(8, 4).y_range(0.0, 1.0);
+    /// plot.push(0.0);
+    /// let mut capture = CaptureBackend::new();
+    /// plot.render_changes(&mut capture).unwrap();
+    /// assert_eq!(capture.cells.len(), 4);
+    ///
+    /// plot.push(0.0);
+    /// let mut capture = CaptureBackend::new();
+    /// plot.render_changes(&mut capture).unwrap();
+    /// assert_eq!(capture.cells.len(), 1);
+    /// ```
+    pub fn render_changes<B: RenderBackend>(&mut self, backend: &mut B) -> io::Result<()> {
+        let frame = self.to_frame();
+        let result = match self.rendered.take() {
+            Some(previous) => frame.render_changes(&previous, backend),
+            None => frame.render(backend),
+        };
+        if result.is_ok() {
+            self.rendered = Some(frame);
+        }
+        result
+    }
+
+    fn rescale(&mut self) {
+        self.scale = self.fixed_scale.unwrap_or_else(|| Scale::fit(self.samples.iter().cloned()));
+        for i in 0..self.samples.len() {
+            self.columns[i] = self.column(i);
+        }
+    }
+
+    /// Works out which pixels to draw in the column of the sample at `index`, joining it to the
+    /// previous sample with a vertical line.
+    fn column(&self, index: usize) -> Option<(usize, usize)> {
+        let y = self.row(self.samples[index])?;
+        match index.checked_sub(1).and_then(|i| self.row(self.samples[i])) {
+            Some(previous) => Some((cmp::min(y, previous), cmp::max(y, previous))),
+            None => Some((y, y)),
+        }
+    }
+
+    fn row(&self, value: f64) -> Option<usize> {
+        if !value.is_finite() || self.height == 0 {
+            return None;
+        }
+        let max = (self.height - 1) as f64;
+        Some((max - self.scale.position(value, self.height)).round().max(0.0).min(max) as usize)
+    }
+}
+
+impl Chart for StreamingPlot {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        StreamingPlot::draw(self, canvas);
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+
+/// The spacings between ticks on a time axis, in seconds: round numbers of seconds, minutes,
+/// hours and days.
+static STEPS: [f64; 18] = [1.0, 2.0, 5.0, 10.0, 15.0, 30.0,
+                           MINUTE, 2.0 * MINUTE, 5.0 * MINUTE, 10.0 * MINUTE, 15.0 * MINUTE,
+                           30.0 * MINUTE, HOUR, 2.0 * HOUR, 3.0 * HOUR, 6.0 * HOUR, 12.0 * HOUR,
+                           DAY];
+
+/// Returns the number of seconds from the Unix epoch to `time`, negative for times before it, as
+/// plotted on a time axis.
+pub fn timestamp(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_secs_f64(),
+        Err(before) => -before.duration().as_secs_f64(),
+    }
+}
+
+/// Returns the spacing in seconds between ticks for at most `max_ticks` ticks across `range`
+/// seconds, picking the smallest round number of seconds, minutes, hours or days that fits.
+///
+/// Below a second, and above a day, the spacing is one, two or five times a power of ten
+/// seconds or days.
+pub fn step(range: f64, max_ticks: usize) -> f64 {
+    let intervals = if max_ticks > 1 { max_ticks - 1 } else { 1 };
+    let raw = range.abs() / intervals as f64;
+    if raw <= 0.0 || !raw.is_finite() {
+        return 1.0;
+    }
+    let decimal = |raw: f64| {
+        let magnitude = 10.0f64.powf(raw.log10().floor());
+        match raw / magnitude {
+            n if n <= 1.0 => magnitude,
+            n if n <= 2.0 => 2.0 * magnitude,
+            n if n <= 5.0 => 5.0 * magnitude,
+            _ => 10.0 * magnitude,
+        }
+    };
+    if raw < 1.0 {
+        return decimal(raw);
+    }
+    match STEPS.iter().find(|&&step| step >= raw) {
+        Some(&step) => step,
+        None => decimal(raw / DAY) * DAY,
+    }
+}
+
+/// Formats a tick on a time axis with ticks `step` seconds apart, in UTC.
+///
+/// Ticks less than a day apart show the time of day, to the second if they’re less than a minute
+/// apart and with fractions of a second if they’re less than a second apart, except for ticks at
+/// midnight, which show the date instead. Ticks a day or more apart show the month and day, and
+/// ticks a year or more apart just the year.
+pub fn format(time: f64, step: f64) -> String {
+    let days = (time / DAY).floor();
+    let (year, month, day) = civil_from_days(days as i64);
+    let seconds = time - days * DAY;
+    // Round to the precision shown, so that ticks just short of a boundary don't show the wrong
+    // side of it.
+    let seconds = if step < 1.0 { seconds } else { seconds.round() };
+    if step >= 365.0 * DAY {
+        return format!("{}", year);
+    }
+    if step >= DAY || seconds.abs() < step * 1e-9 {
+        return format!("{:02}-{:02}", month, day);
+    }
+    let (hours, minutes) = ((seconds / HOUR) as u32, (seconds % HOUR / MINUTE) as u32);
+    let secs = seconds % MINUTE;
+    if step >= MINUTE {
+        format!("{:02}:{:02}", hours, minutes)
+    } else if step >= 1.0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, secs as u32)
+    } else {
+        let decimals = (-step.log10().floor()) as usize;
+        let width = decimals + 3;
+        format!("{:02}:{:02}:{:0width$.decimals$}", hours, minutes, secs, width = width,
+                decimals = decimals)
+    }
+}
+
+/// Returns the year, month and day of the given number of days since the Unix epoch, in the
+/// proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Counted in 400-year eras starting on the 1st of March, so that leap days come last.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+                       - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+    let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
@@ -0,0 +1,109 @@
+//! Gradients for showing numbers as colours
+//!
+//! A `Colormap` maps a number from 0 to 1 onto a smooth gradient of RGB colours, such as for
+//! heatmaps or colouring points by a value, and onto the closest terminal `Color` for drawing on
+//! a canvas. The perceptually uniform gradients from matplotlib are built in, so nobody needs to
+//! reach for a rainbow of their own.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::block::Color;
+//! use drawille::colormap::Colormap;
+//! use drawille::palette::Rgb;
+//!
+//! let heat = Colormap::new(&[(0.0, Rgb(0, 0, 0)), (0.5, Rgb(255, 0, 0)),
+//!                            (1.0, Rgb(255, 255, 0))]);
+//! assert_eq!(heat.rgb(0.25), Rgb(128, 0, 0));
+//! assert_eq!(heat.color(1.0), Color::Yellow);
+//! assert_eq!(Colormap::grayscale().rgb(2.0), Rgb(255, 255, 255));
+//! ```
+
+use block::Color;
+use palette::{Palette, Rgb};
+
+/// A gradient through RGB colours at given positions from 0 to 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Colormap {
+    stops: Vec<(f64, Rgb)>,
+}
+
+impl Colormap {
+    /// Creates a new `Colormap` from colours at positions from 0 to 1, blending between them.
+    ///
+    /// The stops may be given in any order. Positions before the first stop take its colour, and
+    /// positions after the last take that; a colormap without any stops is black throughout.
+    pub fn new(stops: &[(f64, Rgb)]) -> Colormap {
+        let mut stops = stops.iter().cloned().filter(|s| s.0.is_finite()).collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Colormap { stops }
+    }
+
+    /// Returns the viridis colormap, from dark blue through green to yellow.
+    pub fn viridis() -> Colormap {
+        Colormap::evenly(&[Rgb(68, 1, 84), Rgb(71, 45, 123), Rgb(59, 82, 139), Rgb(44, 114, 142),
+                           Rgb(33, 145, 140), Rgb(40, 174, 128), Rgb(94, 201, 98),
+                           Rgb(173, 220, 48), Rgb(253, 231, 37)])
+    }
+
+    /// Returns the magma colormap, from black through purple and red to pale yellow.
+    pub fn magma() -> Colormap {
+        Colormap::evenly(&[Rgb(0, 0, 4), Rgb(28, 16, 68), Rgb(79, 18, 123), Rgb(129, 37, 129),
+                           Rgb(181, 54, 122), Rgb(229, 80, 100), Rgb(251, 135, 97),
+                           Rgb(254, 194, 135), Rgb(252, 253, 191)])
+    }
+
+    /// Returns the turbo colormap, a smoother rainbow from dark blue through green and yellow to
+    /// dark red.
+    pub fn turbo() -> Colormap {
+        Colormap::evenly(&[Rgb(48, 18, 59), Rgb(73, 88, 221), Rgb(47, 158, 245),
+                           Rgb(39, 215, 195), Rgb(78, 249, 131), Rgb(150, 250, 80),
+                           Rgb(223, 220, 50), Rgb(255, 163, 35), Rgb(244, 92, 23),
+                           Rgb(184, 32, 8), Rgb(122, 4, 3)])
+    }
+
+    /// Returns a colormap from black to white.
+    pub fn grayscale() -> Colormap {
+        Colormap::evenly(&[Rgb(0, 0, 0), Rgb(255, 255, 255)])
+    }
+
+    /// Creates a new `Colormap` with colours spread evenly from 0 to 1.
+    fn evenly(colors: &[Rgb]) -> Colormap {
+        let last = (colors.len() - 1) as f64;
+        Colormap::new(&colors.iter().enumerate().map(|(i, &c)| (i as f64 / last, c))
+                                                 .collect::<Vec<_>>())
+    }
+
+    /// Returns the colour at position `t` along the gradient, which is clamped to between 0 and 1.
+    ///
+    /// A position that isn’t a number is taken as 0.
+    pub fn rgb(&self, t: f64) -> Rgb {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let after = match self.stops.iter().position(|s| s.0 > t) {
+            Some(0) => return self.stops[0].1,
+            Some(i) => i,
+            None => return self.stops.last().map_or(Rgb(0, 0, 0), |s| s.1),
+        };
+        let ((t0, a), (t1, b)) = (self.stops[after - 1], self.stops[after]);
+        let f = (t - t0) / (t1 - t0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+        Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+    }
+
+    /// Returns the terminal colour closest to the colour at position `t`, with the default xterm
+    /// colours.
+    pub fn color(&self, t: f64) -> Color {
+        self.color_in(t, &Palette::xterm())
+    }
+
+    /// Returns the colour of `palette` closest to the colour at position `t`.
+    pub fn color_in(&self, t: f64, palette: &Palette) -> Color {
+        palette.map(self.rgb(t))
+    }
+}
+
+impl Default for Colormap {
+    fn default() -> Colormap {
+        Colormap::viridis()
+    }
+}
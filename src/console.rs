@@ -0,0 +1,82 @@
+//! Preparing the console for coloured output
+//!
+//! The canvases colour their output with ANSI escape codes, which terminals on Unix and most
+//! terminal emulators on Windows understand already. The classic Windows console (ConHost, as used
+//! by `cmd.exe`) only understands them once virtual terminal processing has been turned on, and
+//! prints them as garbage otherwise; `enable_ansi` turns it on.
+//!
+//! Colour can also be turned off altogether with the `NO_COLOR` environment variable, as
+//! described at <https://no-color.org>: while it’s set, canvases draw without colour codes, and
+//! `block::Canvas` draws with shading characters instead.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::block::{Canvas, Color};
+//! use drawille::console;
+//!
+//! let mut canvas = Canvas::new(10, 4);
+//! canvas.line(0, 0, 9, 3, Color::Green);
+//! if console::enable_ansi() {
+//!     println!("{}", canvas.frame());
+//! }
+//! ```
+
+use std::env;
+
+/// Makes sure the console that standard output goes to shows ANSI escape codes rather than
+/// printing them, returning whether it will.
+///
+/// On Windows this turns on virtual terminal processing, which is supported from Windows 10 on,
+/// and returns `false` if the console doesn’t support it. Output that isn’t going to a console,
+/// such as a pipe or a file, is left alone and counts as supporting them. Elsewhere this does
+/// nothing and returns `true`.
+pub fn enable_ansi() -> bool {
+    imp::enable_ansi()
+}
+
+/// Detects whether colour has been turned off, by setting the `NO_COLOR` environment variable to
+/// anything but an empty string.
+pub fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::os::raw::c_void;
+
+    type Handle = *mut c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+    }
+
+    pub fn enable_ansi() -> bool {
+        unsafe {
+            let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+            if handle.is_null() || handle as isize == -1 {
+                return true;
+            }
+            let mut mode = 0;
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                // Not a console, so nothing will interpret the escape codes anyway.
+                return true;
+            }
+            mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 ||
+                SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    pub fn enable_ansi() -> bool {
+        true
+    }
+}
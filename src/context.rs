@@ -0,0 +1,348 @@
+//! Stateful drawing onto a canvas
+//!
+//! A `DrawContext` wraps a `braille::Canvas` with the current graphics state—pen colour, line
+//! width, dash pattern, transform and clipping rectangle—which applies to everything drawn until
+//! it’s changed. As with the HTML canvas, `save` and `restore` keep a stack of states, so a piece
+//! of code can change the state freely and put it back afterwards. The transform can also be
+//! built up a step at a time with `translate`, `rotate` and `scale`, and kept on a stack of its
+//! own with `push` and `pop`.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::context::DrawContext;
+//! use drawille::transform::Transform;
+//!
+//! let mut canvas = Canvas::new(40, 20);
+//! {
+//!     let mut context = DrawContext::new(&mut canvas);
+//!     context.set_dash(&[2.0, 2.0]);
+//!     context.rect((0.0, 0.0), (39.0, 19.0));
+//!     context.save();
+//!     context.set_transform(Transform::rotation(45.0).then(&Transform::translation(20.0, 10.0)));
+//!     context.set_dash(&[]);
+//!     context.set_line_width(2.0);
+//!     context.line((-6.0, 0.0), (6.0, 0.0));
+//!     context.restore();
+//!     context.circle((20.0, 10.0), 8.0);
+//! }
+//! println!("{}", canvas.frame());
+//! ```
+
+use block::Color;
+use braille::Canvas;
+use geometry::{self, Rect};
+use path::Path;
+use transform::Transform;
+
+/// The shortest a dash pattern may add up to, in pixels; anything finer draws solid lines.
+pub const MIN_DASH_PATTERN: f64 = 0.5;
+
+/// The settings that apply to everything a `DrawContext` draws.
+#[derive(Clone, Debug, PartialEq)]
+struct State {
+    color: Option<Color>,
+    line_width: f64,
+    /// Alternating lengths of dashes and gaps, or empty for solid lines.
+    dash: Vec<f64>,
+    transform: Transform,
+    clip: Rect,
+}
+
+/// A canvas along with the graphics state used to draw on it.
+pub struct DrawContext<'a> {
+    canvas: &'a mut Canvas,
+    state: State,
+    saved: Vec<State>,
+    /// The transforms saved by `push`, separately from whole saved states.
+    transforms: Vec<Transform>,
+}
+
+impl<'a> DrawContext<'a> {
+    /// Creates a new `DrawContext` drawing onto a canvas, with uncoloured solid lines one pixel
+    /// wide, no transform and no clipping.
+    pub fn new(canvas: &'a mut Canvas) -> DrawContext<'a> {
+        DrawContext {
+            canvas,
+            state: State {
+                color: None,
+                line_width: 1.0,
+                dash: vec![],
+                transform: Transform::identity(),
+                clip: geometry::BOUNDS,
+            },
+            saved: vec![],
+            transforms: vec![],
+        }
+    }
+
+    /// Returns the canvas being drawn on.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        self.canvas
+    }
+
+    /// Pushes a copy of the current graphics state onto the stack.
+    pub fn save(&mut self) {
+        self.saved.push(self.state.clone());
+    }
+
+    /// Pops the graphics state last saved off the stack and makes it current.
+    ///
+    /// Does nothing if no state is saved.
+    pub fn restore(&mut self) {
+        if let Some(state) = self.saved.pop() {
+            self.state = state;
+        }
+    }
+
+    /// Sets the colour everything is drawn in, or `None` for no colour.
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.state.color = color;
+    }
+
+    /// Sets the width of lines, in pixels before the transform is applied.
+    pub fn set_line_width(&mut self, width: f64) {
+        self.state.line_width = width.max(0.0);
+    }
+
+    /// Sets the dash pattern of lines, as alternating lengths of dashes and gaps in pixels after
+    /// the transform is applied, or solid lines if `pattern` is empty.
+    ///
+    /// The pattern starts again at the start of each line, polyline and shape. A pattern adding
+    /// up to less than `MIN_DASH_PATTERN` draws solid lines, as it would be too fine to see.
+    ///
+    /// Lines are only followed inside the clipping rectangle, so a dashed line reaching far
+    /// outside it is no slower to draw than its visible part.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    /// use drawille::context::DrawContext;
+    ///
+    /// let draw = |to: f64| {
+    ///     let mut canvas = Canvas::new(16, 4);
+    ///     {
+    ///         let mut context = DrawContext::new(&mut canvas);
+    ///         context.clip((0.0, 0.0), (15.0, 3.0));
+    ///         context.set_dash(&[2.0, 2.0]);
+    ///         context.line((-7.0, 1.0), (to, 1.0));
+    ///     }
+    ///     canvas.frame()
+    /// };
+    /// assert_eq!(draw(1e12), draw(20.0));
+    /// ```
+    pub fn set_dash(&mut self, pattern: &[f64]) {
+        let valid = pattern.iter().all(|&l| l >= 0.0)
+            && pattern.iter().sum::<f64>() >= MIN_DASH_PATTERN;
+        self.state.dash = if valid { pattern.to_vec() } else { vec![] };
+        // An odd number of lengths is repeated, so that dashes and gaps alternate.
+        if self.state.dash.len() % 2 == 1 {
+            self.state.dash.extend_from_within(..);
+        }
+    }
+
+    /// Returns the transform applied to everything drawn.
+    pub fn transform(&self) -> Transform {
+        self.state.transform
+    }
+
+    /// Sets the transform applied to everything drawn.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.state.transform = transform;
+    }
+
+    /// Moves everything drawn from now on `dx` right and `dy` down, in the coordinates of the
+    /// current transform.
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        self.state.transform = Transform::translation(dx, dy).then(&self.state.transform);
+    }
+
+    /// Turns everything drawn from now on clockwise by `degrees` about the origin of the current
+    /// transform.
+    pub fn rotate(&mut self, degrees: f64) {
+        self.state.transform = Transform::rotation(degrees).then(&self.state.transform);
+    }
+
+    /// Stretches everything drawn from now on by `sx` across and `sy` down, away from the origin
+    /// of the current transform.
+    pub fn scale(&mut self, sx: f64, sy: f64) {
+        self.state.transform = Transform::scaling(sx, sy).then(&self.state.transform);
+    }
+
+    /// Pushes a copy of the current transform onto a stack of its own, leaving the rest of the
+    /// graphics state alone.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    /// use drawille::context::DrawContext;
+    ///
+    /// let mut canvas = Canvas::new(40, 40);
+    /// let mut context = DrawContext::new(&mut canvas);
+    /// for i in 0..3 {
+    ///     context.push();
+    ///     context.translate(10.0 + 10.0 * i as f64, 20.0);
+    ///     context.rotate(30.0 * i as f64);
+    ///     context.rect((-4.0, -4.0), (4.0, 4.0));
+    ///     context.pop();
+    /// }
+    /// ```
+    pub fn push(&mut self) {
+        self.transforms.push(self.state.transform);
+    }
+
+    /// Pops the transform last pushed off its stack and makes it current.
+    ///
+    /// Does nothing if no transform is pushed.
+    pub fn pop(&mut self) {
+        if let Some(transform) = self.transforms.pop() {
+            self.state.transform = transform;
+        }
+    }
+
+    /// Narrows the area drawn in to the part of the current clipping rectangle that’s also inside
+    /// the rectangle with corners `from` and `to`, in canvas pixels.
+    ///
+    /// The clipping rectangle can only be widened again with `restore`.
+    pub fn clip(&mut self, from: (f64, f64), to: (f64, f64)) {
+        let rect = (from.0.min(to.0), from.1.min(to.1), from.0.max(to.0), from.1.max(to.1));
+        self.state.clip = geometry::intersect(self.state.clip, rect);
+    }
+
+    /// Draws a point.
+    pub fn point(&mut self, p: (f64, f64)) {
+        self.stroke(&[p, p], false);
+    }
+
+    /// Draws a line from `a` to `b`.
+    pub fn line(&mut self, a: (f64, f64), b: (f64, f64)) {
+        self.stroke(&[a, b], true);
+    }
+
+    /// Draws lines joining a sequence of points.
+    pub fn polyline(&mut self, points: &[(f64, f64)]) {
+        self.stroke(points, true);
+    }
+
+    /// Draws the outline of a polygon with the given corners.
+    pub fn polygon(&mut self, points: &[(f64, f64)]) {
+        let closed = points.iter().chain(points.first()).cloned().collect::<Vec<_>>();
+        self.stroke(&closed, true);
+    }
+
+    /// Draws the outline of a rectangle with corners `from` and `to`.
+    pub fn rect(&mut self, from: (f64, f64), to: (f64, f64)) {
+        self.polygon(&[from, (to.0, from.1), to, (from.0, to.1)]);
+    }
+
+    /// Draws the outline of a circle.
+    pub fn circle(&mut self, center: (f64, f64), radius: f64) {
+        let points = geometry::circle_points(center, radius, self.state.transform.scale_factor());
+        self.stroke(&points, true);
+    }
+
+    /// Draws the outline of a path.
+    pub fn stroke_path(&mut self, path: &Path) {
+        for points in path.flatten() {
+            self.stroke(&points, true);
+        }
+    }
+
+    /// Fills the inside of a path, treating every subpath as closed.
+    pub fn fill_path(&mut self, path: &Path) {
+        let polygons = path.transformed(&self.state.transform).flatten();
+        let (canvas, color) = (&mut *self.canvas, self.state.color);
+        geometry::fill(&polygons, self.state.clip, |x, y| match color {
+            Some(color) => canvas.set_colored(x, y, color),
+            None => canvas.set(x, y),
+        });
+    }
+
+    /// Writes text onto the canvas from the character containing a point, which is transformed
+    /// and must be within the clipping rectangle.
+    pub fn text<S: AsRef<str>>(&mut self, p: (f64, f64), text: S) {
+        let (x, y) = self.state.transform.apply(p);
+        let (left, top, right, bottom) = self.state.clip;
+        if x >= left && y >= top && x <= right && y <= bottom {
+            self.canvas.text(x.round() as usize, y.round() as usize, text);
+        }
+    }
+
+    /// Draws lines joining a sequence of points with the current state, dashed if `dashed` is
+    /// true and there is a dash pattern.
+    fn stroke(&mut self, points: &[(f64, f64)], dashed: bool) {
+        let state = &self.state;
+        let canvas = &mut *self.canvas;
+        let points = points.iter().map(|&p| state.transform.apply(p)).collect::<Vec<_>>();
+        let radius = state.line_width * state.transform.scale_factor() / 2.0;
+        // A pen wider than a pixel stamps a disc of pixels around each pixel of the line.
+        let reach = radius.round().max(0.0) as isize;
+        let (left, top, right, bottom) = state.clip;
+        let mut plot = |x: usize, y: usize| {
+            for dy in -reach..reach + 1 {
+                for dx in -reach..reach + 1 {
+                    if (dx * dx + dy * dy) as f64 > radius * radius + 0.5 {
+                        continue;
+                    }
+                    let (px, py) = (x as f64 + dx as f64, y as f64 + dy as f64);
+                    if px < left || py < top || px > right || py > bottom {
+                        continue;
+                    }
+                    let (px, py) = (px as usize, py as usize);
+                    match state.color {
+                        Some(color) => canvas.set_colored(px, py, color),
+                        None => canvas.set(px, py),
+                    }
+                }
+            }
+        };
+
+        if state.dash.is_empty() || !dashed {
+            for pair in points.windows(2) {
+                geometry::segment(pair[0], pair[1], state.clip, &mut plot);
+            }
+            return;
+        }
+
+        // Walk along the part of each line inside the clipping rectangle, drawing only the parts
+        // that fall in dashes, and move through the pattern by the length of the parts outside.
+        let total = state.dash.iter().sum::<f64>();
+        let rect = geometry::intersect(state.clip, geometry::BOUNDS);
+        let (mut dash, mut left_in_dash) = (0, state.dash[0]);
+        let advance = |dash: &mut usize, left_in_dash: &mut f64, skipped: f64| {
+            let mut skipped = skipped % total;
+            while skipped >= *left_in_dash {
+                skipped -= *left_in_dash;
+                *dash = (*dash + 1) % state.dash.len();
+                *left_in_dash = state.dash[*dash];
+            }
+            *left_in_dash -= skipped;
+        };
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let length = ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+            let (start, end) = match geometry::clip(a, b, rect) {
+                Some((from, to)) => {
+                    let distance = |p: (f64, f64)| ((p.0 - a.0).powi(2) + (p.1 - a.1).powi(2)).sqrt();
+                    (distance(from), distance(to))
+                },
+                None => (length, length),
+            };
+            let at = |t: f64| (a.0 + (b.0 - a.0) * t / length, a.1 + (b.1 - a.1) * t / length);
+            advance(&mut dash, &mut left_in_dash, start);
+            let mut done = start;
+            while done < end {
+                let step = left_in_dash.min(end - done);
+                if dash % 2 == 0 {
+                    geometry::segment(at(done), at(done + step), state.clip, &mut plot);
+                }
+                done += step;
+                left_in_dash -= step;
+                if left_in_dash <= 0.0 {
+                    dash = (dash + 1) % state.dash.len();
+                    left_in_dash = state.dash[dash];
+                }
+            }
+            advance(&mut dash, &mut left_in_dash, length - end);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Ordered dithering
+//!
+//! This module provides the threshold map used to turn intensities into patterns of set and unset
+//! pixels, so that shades of grey can be approximated with monochrome Braille dots.
+
+static BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10],
+                                  [12, 4, 14, 6],
+                                  [3, 11, 1, 9],
+                                  [15, 7, 13, 5]];
+
+/// Returns the dithering threshold for the pixel at the given coordinates, strictly between zero
+/// and one.
+///
+/// Thresholds follow a 4×4 Bayer matrix, so setting each pixel whose intensity exceeds its threshold
+/// spreads the set pixels of a flat intensity evenly.
+pub fn threshold(x: usize, y: usize) -> f64 {
+    (BAYER_4X4[y % 4][x % 4] as f64 + 0.5) / 16.0
+}
+
+/// Returns whether the pixel at the given coordinates should be set to show `intensity`, from zero
+/// (never) to one (always).
+pub fn is_set(x: usize, y: usize, intensity: f64) -> bool {
+    intensity > threshold(x, y)
+}
@@ -0,0 +1,260 @@
+//! Ready-made fractal drawings
+//!
+//! This module provides a few classic recursive figures drawn with a `braille::Turtle`. Each
+//! function starts drawing from the turtle’s current position and heading, and leaves the turtle
+//! with the heading it started with.
+//!
+//! It also provides `EscapeTime`, which draws the Mandelbrot set and Julia sets straight onto a
+//! canvas, pixel by pixel, optionally spread over several threads.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Turtle;
+//! use drawille::fractals;
+//!
+//! let mut turtle = Turtle::new(0.0, 20.0);
+//! fractals::koch_snowflake(&mut turtle, 60.0, 3);
+//! println!("{}", turtle.frame());
+//! ```
+
+use std::thread;
+
+use block;
+use braille::{self, Turtle};
+use colormap::Colormap;
+
+/// Draws a Koch snowflake with sides of length `size`, subdivided `depth` times.
+///
+/// The first side is drawn along the turtle’s heading and the snowflake lies to its right.
+pub fn koch_snowflake(turtle: &mut Turtle, size: f32, depth: usize) {
+    for _ in 0..3 {
+        koch_curve(turtle, size, depth);
+        turtle.right(120.0);
+    }
+}
+
+/// Draws a single Koch curve of length `size`, subdivided `depth` times.
+pub fn koch_curve(turtle: &mut Turtle, size: f32, depth: usize) {
+    if depth == 0 {
+        turtle.forward(size);
+        return;
+    }
+    let size = size / 3.0;
+    koch_curve(turtle, size, depth - 1);
+    turtle.left(60.0);
+    koch_curve(turtle, size, depth - 1);
+    turtle.right(120.0);
+    koch_curve(turtle, size, depth - 1);
+    turtle.left(60.0);
+    koch_curve(turtle, size, depth - 1);
+}
+
+/// Draws a Sierpinski triangle with sides of length `size`, subdivided `depth` times.
+///
+/// The base of the triangle is drawn along the turtle’s heading and the triangle lies to its left.
+/// The turtle finishes where it started.
+pub fn sierpinski_triangle(turtle: &mut Turtle, size: f32, depth: usize) {
+    if depth == 0 {
+        for _ in 0..3 {
+            turtle.forward(size);
+            turtle.left(120.0);
+        }
+        return;
+    }
+    let half = size / 2.0;
+    sierpinski_triangle(turtle, half, depth - 1);
+    turtle.forward(half);
+    sierpinski_triangle(turtle, half, depth - 1);
+    turtle.back(half);
+    turtle.left(60.0);
+    turtle.forward(half);
+    turtle.right(60.0);
+    sierpinski_triangle(turtle, half, depth - 1);
+    turtle.left(60.0);
+    turtle.back(half);
+    turtle.right(60.0);
+}
+
+/// Draws a Heighway dragon curve spanning `size` steps, folded `depth` times.
+///
+/// The curve starts at the turtle’s position and ends `size` steps along its heading.
+pub fn dragon_curve(turtle: &mut Turtle, size: f32, depth: usize) {
+    dragon(turtle, size, depth, 1.0);
+}
+
+fn dragon(turtle: &mut Turtle, size: f32, depth: usize, turn: f32) {
+    if depth == 0 {
+        turtle.forward(size);
+        return;
+    }
+    let size = size / 2.0f32.sqrt();
+    turtle.right(45.0 * turn);
+    dragon(turtle, size, depth - 1, 1.0);
+    turtle.left(90.0 * turn);
+    dragon(turtle, size, depth - 1, -1.0);
+    turtle.right(45.0 * turn);
+}
+
+/// Draws a binary tree whose trunk is `size` steps long, branching `depth` times.
+///
+/// Each branch splits into two branches `angle` degrees either side of it, each 70% of its length.
+/// The trunk grows along the turtle’s heading, so turn the turtle to face upwards (`left(90.0)`)
+/// for an upright tree. The turtle finishes where it started.
+pub fn binary_tree(turtle: &mut Turtle, size: f32, depth: usize, angle: f32) {
+    if depth == 0 {
+        return;
+    }
+    turtle.forward(size);
+    turtle.left(angle);
+    binary_tree(turtle, size * 0.7, depth - 1, angle);
+    turtle.right(angle * 2.0);
+    binary_tree(turtle, size * 0.7, depth - 1, angle);
+    turtle.left(angle);
+    turtle.back(size);
+}
+
+/// The kinds of escape-time fractal an `EscapeTime` draws.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Fractal {
+    Mandelbrot,
+    /// The Julia set for the given constant.
+    Julia(f64, f64),
+}
+
+/// Draws an escape-time fractal—the Mandelbrot set or a Julia set—by iterating `z² + c` at each
+/// pixel until it escapes or gives up.
+///
+/// ```
+/// use drawille::braille::Canvas;
+/// use drawille::fractals::EscapeTime;
+///
+/// let mandelbrot = EscapeTime::mandelbrot().max_iterations(50);
+/// let mut canvas = Canvas::new(80, 40);
+/// mandelbrot.draw(&mut canvas, 80, 40);
+/// // The middle of the main bulb is in the set, and far out to the right isn’t.
+/// assert!(canvas.get(50, 20) && !canvas.get(79, 0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscapeTime {
+    fractal: Fractal,
+    center: (f64, f64),
+    span: f64,
+    max_iterations: usize,
+    threads: usize,
+}
+
+impl EscapeTime {
+    /// Creates a new `EscapeTime` drawing the whole Mandelbrot set.
+    pub fn mandelbrot() -> EscapeTime {
+        EscapeTime {
+            fractal: Fractal::Mandelbrot,
+            center: (-0.75, 0.0),
+            span: 3.5,
+            max_iterations: 100,
+            threads: 1,
+        }
+    }
+
+    /// Creates a new `EscapeTime` drawing the whole Julia set for the constant `re + im i`.
+    pub fn julia(re: f64, im: f64) -> EscapeTime {
+        EscapeTime { fractal: Fractal::Julia(re, im), center: (0.0, 0.0), span: 3.5,
+                     ..EscapeTime::mandelbrot() }
+    }
+
+    /// Sets the part of the complex plane drawn, as the point at the centre and how wide the part
+    /// drawn is, and returns the `EscapeTime` for use again.
+    ///
+    /// Pixels are square, so the height of the part drawn follows from the shape of the area it’s
+    /// drawn in. The imaginary axis points up.
+    pub fn view(mut self, re: f64, im: f64, span: f64) -> EscapeTime {
+        self.center = (re, im);
+        self.span = span;
+        self
+    }
+
+    /// Sets how many times `z² + c` is iterated before a point is taken to be in the set, and
+    /// returns the `EscapeTime` for use again.
+    ///
+    /// More iterations show finer detail when zoomed in, but take longer.
+    pub fn max_iterations(mut self, max_iterations: usize) -> EscapeTime {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets how many threads the work is shared among, and returns the `EscapeTime` for use
+    /// again.
+    pub fn threads(mut self, threads: usize) -> EscapeTime {
+        self.threads = threads;
+        self
+    }
+
+    /// Returns how many iterations each of the pixels of an area `width` by `height` pixels took
+    /// to escape, a row at a time from the top left, or `None` for those that never did.
+    pub fn iterations(&self, width: usize, height: usize) -> Vec<Option<usize>> {
+        let mut counts = vec![None; width * height];
+        if width == 0 || height == 0 {
+            return counts;
+        }
+        let rows_each = height.div_ceil(self.threads.clamp(1, height));
+        thread::scope(|scope| {
+            for (chunk, counts) in counts.chunks_mut(rows_each * width).enumerate() {
+                scope.spawn(move || {
+                    for (i, count) in counts.iter_mut().enumerate() {
+                        let (x, y) = (i % width, chunk * rows_each + i / width);
+                        *count = self.escape(self.point(x, y, width, height));
+                    }
+                });
+            }
+        });
+        counts
+    }
+
+    /// Sets each pixel of the canvas within `width` by `height` pixels that is in the set.
+    pub fn draw(&self, canvas: &mut braille::Canvas, width: usize, height: usize) {
+        for (i, count) in self.iterations(width, height).into_iter().enumerate() {
+            if count.is_none() {
+                canvas.set(i % width, i / width);
+            }
+        }
+    }
+
+    /// Colours each pixel of the canvas within `width` by `height` pixels by how quickly it
+    /// escaped, taking colours from `colormap` from 0 for the quickest to 1 for the slowest,
+    /// and sets the pixels in the set to black.
+    pub fn draw_colored(&self, canvas: &mut block::Canvas, width: usize, height: usize,
+                        colormap: &Colormap) {
+        let scale = self.max_iterations.max(1) as f64;
+        for (i, count) in self.iterations(width, height).into_iter().enumerate() {
+            let color = match count {
+                Some(count) => colormap.color(count as f64 / scale),
+                None => block::Color::Black,
+            };
+            canvas.set(i % width, i / width, color);
+        }
+    }
+
+    /// Returns the point of the complex plane at the centre of a pixel.
+    fn point(&self, x: usize, y: usize, width: usize, height: usize) -> (f64, f64) {
+        let step = self.span / width as f64;
+        (self.center.0 + (x as f64 + 0.5 - width as f64 / 2.0) * step,
+         self.center.1 - (y as f64 + 0.5 - height as f64 / 2.0) * step)
+    }
+
+    /// Returns how many iterations it took the point to escape, or `None` if it didn’t.
+    fn escape(&self, (re, im): (f64, f64)) -> Option<usize> {
+        let ((mut zr, mut zi), (cr, ci)) = match self.fractal {
+            Fractal::Mandelbrot => ((0.0, 0.0), (re, im)),
+            Fractal::Julia(cr, ci) => ((re, im), (cr, ci)),
+        };
+        for i in 0..self.max_iterations {
+            if zr * zr + zi * zi > 4.0 {
+                return Some(i);
+            }
+            let next = zr * zr - zi * zi + cr;
+            zi = 2.0 * zr * zi + ci;
+            zr = next;
+        }
+        None
+    }
+}
@@ -0,0 +1,352 @@
+//! Frames of characters with their colours and attributes
+//!
+//! A `Frame` is what a canvas looks like once it's drawn: a grid of characters, each with the
+//! colours and text attributes it's shown in, before any of it is turned into escape codes. Both
+//! `braille::Canvas` and `block::Canvas` produce one with `to_frame`, and a frame can then be
+//! written out as rows of ANSI escape codes, as plain text, or as HTML, converted to a ratatui
+//! `Buffer` with the `ratatui` feature, or rendered cell by cell to any of the backends in the
+//! `backend` module.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::block::Color;
+//! use drawille::braille::Canvas;
+//!
+//! let mut canvas = Canvas::new(4, 4);
+//! canvas.set_colored(0, 0, Color::Red);
+//! canvas.text(2, 0, "!");
+//! let frame = canvas.to_frame();
+//! assert_eq!(frame[(0, 0)].fg, Some(Color::Red));
+//! assert_eq!(frame.plain_rows(), vec!["⠁!"]);
+//! assert_eq!(frame.ansi_rows(), vec!["\x1b[31m⠁\x1b[0m!"]);
+//! ```
+
+#[cfg(feature = "ratatui")]
+use std::cmp;
+use std::io;
+use std::ops::{Index, IndexMut};
+
+use backend::{RenderBackend, StringBackend};
+use block::{Attributes, Color};
+use image;
+
+/// A character of a `Frame`, with the colours and attributes it's shown in.
+///
+/// Without colours, the terminal's own colours show.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub char: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub attributes: Attributes,
+}
+
+impl Cell {
+    /// Creates a new `Cell` showing `c` in the terminal's own colours.
+    pub fn new(c: char) -> Cell {
+        Cell { char: c, fg: None, bg: None, attributes: Attributes::empty() }
+    }
+
+    /// Detects whether the cell is shown in the terminal's own colours without any attributes.
+    pub fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.attributes.is_empty()
+    }
+
+    /// Returns the SGR escape code that shows the cell's colours and attributes, or an empty
+    /// string for a plain cell.
+    pub fn escape(&self) -> String {
+        let mut codes = vec![];
+        codes.extend(self.fg.map(|color| format!("3{}", color as u32)));
+        codes.extend(self.bg.map(|color| format!("4{}", color as u32)));
+        if codes.is_empty() {
+            self.attributes.to_string()
+        } else {
+            format!("{}\x1b[{}m", self.attributes, codes.join(";"))
+        }
+    }
+
+    /// Returns the CSS declarations that show the cell's colours and attributes.
+    fn style(&self) -> String {
+        let hex = |color: Color| {
+            let [r, g, b] = image::rgb(color);
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        };
+        let (fg, bg) = if self.attributes.contains(Attributes::REVERSE) {
+            (self.bg.or(Some(Color::Black)), self.fg.or(Some(Color::White)))
+        } else {
+            (self.fg, self.bg)
+        };
+        let mut style = vec![];
+        style.extend(fg.map(|color| format!("color:{}", hex(color))));
+        style.extend(bg.map(|color| format!("background-color:{}", hex(color))));
+        let css = [(Attributes::BOLD, "font-weight:bold"), (Attributes::DIM, "opacity:0.5"),
+                   (Attributes::ITALIC, "font-style:italic")];
+        for &(attribute, declaration) in css.iter() {
+            if self.attributes.contains(attribute) {
+                style.push(declaration.to_string());
+            }
+        }
+        let lines = [(Attributes::UNDERLINE, "underline"), (Attributes::BLINK, "blink")];
+        let lines = lines.iter().filter(|&&(attribute, _)| self.attributes.contains(attribute))
+                         .map(|&(_, line)| line).collect::<Vec<_>>();
+        if !lines.is_empty() {
+            style.push(format!("text-decoration:{}", lines.join(" ")));
+        }
+        style.join(";")
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Cell {
+        Cell::new(' ')
+    }
+}
+
+/// Converts a cell to a ratatui cell with the same character, colours and attributes.
+///
+/// White becomes ratatui’s `Gray`, which is the same ANSI colour; ratatui’s `White` is bright
+/// white.
+#[cfg(feature = "ratatui")]
+impl<'a> From<&'a Cell> for ::ratatui::buffer::Cell {
+    fn from(cell: &'a Cell) -> ::ratatui::buffer::Cell {
+        use ratatui::style::{Color as TuiColor, Modifier};
+
+        let color = |color: Option<Color>| match color {
+            None => TuiColor::Reset,
+            Some(Color::Black) => TuiColor::Black,
+            Some(Color::Red) => TuiColor::Red,
+            Some(Color::Green) => TuiColor::Green,
+            Some(Color::Yellow) => TuiColor::Yellow,
+            Some(Color::Blue) => TuiColor::Blue,
+            Some(Color::Magenta) => TuiColor::Magenta,
+            Some(Color::Cyan) => TuiColor::Cyan,
+            Some(Color::White) => TuiColor::Gray,
+        };
+        let mut tui = ::ratatui::buffer::Cell::from(cell.char);
+        tui.set_fg(color(cell.fg)).set_bg(color(cell.bg));
+        let modifiers = [(Attributes::BOLD, Modifier::BOLD), (Attributes::DIM, Modifier::DIM),
+                         (Attributes::ITALIC, Modifier::ITALIC),
+                         (Attributes::UNDERLINE, Modifier::UNDERLINED),
+                         (Attributes::BLINK, Modifier::SLOW_BLINK),
+                         (Attributes::REVERSE, Modifier::REVERSED)];
+        for &(attribute, modifier) in modifiers.iter() {
+            if cell.attributes.contains(attribute) {
+                tui.modifier.insert(modifier);
+            }
+        }
+        tui
+    }
+}
+
+/// A grid of characters with their colours and attributes, as drawn from a canvas.
+///
+/// A wide character, such as most CJK characters, takes up its own cell and the one after it,
+/// which holds `'\0'` and is skipped when the frame is written out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// Creates a new `Frame` of blank cells, `width` characters across and `height` down.
+    pub fn new(width: usize, height: usize) -> Frame {
+        Frame { width, height, cells: vec![Cell::default(); width * height] }
+    }
+
+    /// Returns the width of the frame in characters.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the frame in characters.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns every cell of the frame, a row at a time from the top, each from left to right.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Returns the cells of the row with the given index.
+    pub fn row(&self, row: usize) -> &[Cell] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Returns the cell at the given column and row, or `None` if it's outside the frame.
+    pub fn get(&self, column: usize, row: usize) -> Option<&Cell> {
+        if column < self.width && row < self.height {
+            Some(&self.cells[row * self.width + column])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the frame as a ratatui `Buffer` of the same size, for drawing from a ratatui widget.
+    ///
+    /// The second cell of each wide character is left blank, as ratatui expects. A frame wider or
+    /// higher than 65535 characters is cut short, since that's as big as a `Buffer` can be.
+    ///
+    /// ```
+    /// # extern crate drawille;
+    /// # extern crate ratatui;
+    /// use drawille::block::Color;
+    /// use drawille::frame::{Cell, Frame};
+    ///
+    /// # fn main() {
+    /// let mut frame = Frame::new(2, 1);
+    /// frame[(1, 0)] = Cell { fg: Some(Color::Red), ..Cell::new('x') };
+    /// let buffer = frame.to_buffer();
+    /// assert_eq!(buffer[(1, 0)].symbol(), "x");
+    /// assert_eq!(buffer[(1, 0)].fg, ratatui::style::Color::Red);
+    /// # }
+    /// ```
+    #[cfg(feature = "ratatui")]
+    pub fn to_buffer(&self) -> ::ratatui::buffer::Buffer {
+        use backend::BufferBackend;
+        use ratatui::buffer::Buffer;
+        use ratatui::layout::Rect;
+
+        let clamp = |n: usize| cmp::min(n, u16::MAX as usize) as u16;
+        let area = Rect::new(0, 0, clamp(self.width), clamp(self.height));
+        let mut buffer = Buffer::empty(area);
+        // Nothing rendered to a buffer can fail.
+        let _ = self.render(&mut BufferBackend::new(&mut buffer, area));
+        buffer
+    }
+
+    /// Writes every cell of the frame to a backend, a row at a time, and then flushes it.
+    pub fn render<B: RenderBackend>(&self, backend: &mut B) -> io::Result<()> {
+        for (i, cell) in self.cells.iter().enumerate().filter(|&(_, c)| c.char != '\0') {
+            backend.write_cell(i % self.width, i / self.width, cell)?;
+        }
+        backend.flush()
+    }
+
+    /// Writes the cells of the frame that differ from those of the `previous` frame to a
+    /// backend, and then flushes it, for redrawing a terminal without writing what's already
+    /// there.
+    ///
+    /// Every cell is written if the frames are of different sizes.
+    pub fn render_changes<B: RenderBackend>(&self, previous: &Frame, backend: &mut B)
+                                            -> io::Result<()> {
+        if (previous.width, previous.height) != (self.width, self.height) {
+            return self.render(backend);
+        }
+        let changed = self.cells.iter().zip(previous.cells.iter()).enumerate()
+                                .filter(|&(_, (cell, old))| cell != old && cell.char != '\0');
+        for (i, (cell, _)) in changed {
+            backend.write_cell(i % self.width, i / self.width, cell)?;
+        }
+        backend.flush()
+    }
+
+    /// Writes each row of the frame with ANSI escape codes for its colours and attributes, as
+    /// `StringBackend::ansi_rows` does.
+    pub fn ansi_rows(&self) -> Vec<String> {
+        self.rendered(StringBackend::ansi_rows)
+    }
+
+    /// Writes each row of the frame as plain text, without its colours and attributes.
+    pub fn plain_rows(&self) -> Vec<String> {
+        self.rendered(StringBackend::rows)
+    }
+
+    /// Renders the frame to a `StringBackend` and returns the rows `rows` gets from it, one for
+    /// each row of the frame.
+    fn rendered<F: Fn(&StringBackend) -> Vec<String>>(&self, rows: F) -> Vec<String> {
+        let mut backend = StringBackend::new();
+        // Nothing rendered to a string can fail.
+        let _ = self.render(&mut backend);
+        let mut rows = rows(&backend);
+        // A frame without columns writes no rows.
+        rows.resize(self.height, String::new());
+        rows
+    }
+
+    /// Writes the frame as ANSI rows joined by newlines, just as canvases' `frame` does.
+    pub fn to_ansi(&self) -> String {
+        self.ansi_rows().join("\n")
+    }
+
+    /// Writes the frame as an HTML `pre` element, with each run of characters of the same style
+    /// in a `span` styled to match.
+    ///
+    /// ```
+    /// use drawille::block::{Attributes, Color};
+    /// use drawille::frame::{Cell, Frame};
+    ///
+    /// let mut frame = Frame::new(3, 1);
+    /// frame[(0, 0)] = Cell::new('<');
+    /// frame[(1, 0)] = Cell { fg: Some(Color::Red), attributes: Attributes::BOLD,
+    ///                        ..Cell::new('a') };
+    /// assert_eq!(frame.to_html(),
+    ///            "<pre>&lt;<span style=\"color:#cd0000;font-weight:bold\">a</span> </pre>");
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<pre>");
+        for (i, row) in self.write_cells().iter().enumerate() {
+            if i > 0 {
+                html.push('\n');
+            }
+            let mut run: Option<(Cell, String)> = None;
+            for &cell in row.iter() {
+                let same = run.as_ref().is_some_and(|&(style, _)| {
+                    (style.fg, style.bg, style.attributes) == (cell.fg, cell.bg, cell.attributes)
+                });
+                if !same {
+                    html.push_str(&span(run.take()));
+                    run = Some((cell, String::new()));
+                }
+                if let Some((_, ref mut text)) = run {
+                    match cell.char {
+                        '&' => text.push_str("&amp;"),
+                        '<' => text.push_str("&lt;"),
+                        '>' => text.push_str("&gt;"),
+                        c => text.push(c),
+                    }
+                }
+            }
+            html.push_str(&span(run));
+        }
+        html.push_str("</pre>");
+        html
+    }
+
+    /// Returns the cells written out in each row, leaving out the second cell of each wide
+    /// character.
+    fn write_cells(&self) -> Vec<Vec<Cell>> {
+        (0..self.height).map(|y| self.row(y).iter().cloned().filter(|c| c.char != '\0').collect())
+                        .collect()
+    }
+}
+
+/// Returns a run of HTML-escaped text in the style of a cell, in a `span` unless it's plain.
+fn span(run: Option<(Cell, String)>) -> String {
+    match run {
+        Some((style, text)) => match style.style() {
+            ref css if css.is_empty() => text,
+            css => format!("<span style=\"{}\">{}</span>", css, text),
+        },
+        None => String::new(),
+    }
+}
+
+impl Index<(usize, usize)> for Frame {
+    type Output = Cell;
+
+    fn index(&self, (column, row): (usize, usize)) -> &Cell {
+        assert!(column < self.width && row < self.height, "cell outside the frame");
+        &self.cells[row * self.width + column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Frame {
+    fn index_mut(&mut self, (column, row): (usize, usize)) -> &mut Cell {
+        assert!(column < self.width && row < self.height, "cell outside the frame");
+        &mut self.cells[row * self.width + column]
+    }
+}
@@ -0,0 +1,209 @@
+//! Drawing latitude and longitude on a canvas
+//!
+//! This module projects points given in degrees of latitude and longitude onto a
+//! `braille::Canvas`, so that GPS tracks and simple maps can be drawn. A `MapView` fits a bounding
+//! box onto the canvas without distorting it, and `simplify` thins out dense tracks before they
+//! are drawn.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::geo::{self, Bounds, MapView, Projection};
+//!
+//! let track = [(51.5007, -0.1246), (51.5014, -0.1419), (51.5055, -0.0754), (51.5081, -0.0759)];
+//! let track = geo::simplify(&track, 1e-4);
+//! let view = MapView::new(Projection::WebMercator, Bounds::fit(&track), 60, 32);
+//! let mut canvas = Canvas::new(60, 32);
+//! view.draw_track(&mut canvas, &track);
+//! println!("{}", canvas.frame());
+//! ```
+
+use std::f64::consts::PI;
+
+use braille::Canvas;
+
+/// The furthest latitude from the equator that Web Mercator can show, in degrees.
+const MERCATOR_MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// A way of flattening the globe onto a canvas.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Projection {
+    /// Latitude and longitude are mapped straight onto the y and x axes.
+    Equirectangular,
+    /// The projection used by most web maps, which keeps shapes but stretches areas towards the
+    /// poles. Latitudes beyond about 85° are clamped.
+    WebMercator,
+}
+
+impl Projection {
+    /// Projects a point given in degrees, returning its position on a flat map with x increasing
+    /// eastwards and y increasing northwards, in radians along the equator.
+    pub fn project(&self, lat: f64, lon: f64) -> (f64, f64) {
+        let x = lon.to_radians();
+        match *self {
+            Projection::Equirectangular => (x, lat.to_radians()),
+            Projection::WebMercator => {
+                let lat = lat.clamp(-MERCATOR_MAX_LATITUDE, MERCATOR_MAX_LATITUDE).to_radians();
+                (x, (PI / 4.0 + lat / 2.0).tan().ln())
+            },
+        }
+    }
+}
+
+/// An area of the globe between two latitudes and two longitudes, in degrees.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bounds {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+}
+
+impl Bounds {
+    /// Creates a new `Bounds` with the given south-west and north-east corners.
+    pub fn new((min_lat, min_lon): (f64, f64), (max_lat, max_lon): (f64, f64)) -> Bounds {
+        Bounds { min_lat, min_lon, max_lat, max_lon }
+    }
+
+    /// Creates a `Bounds` just covering the finite `(lat, lon)` points given, or the whole globe if
+    /// there are none.
+    pub fn fit(points: &[(f64, f64)]) -> Bounds {
+        let finite = points.iter().filter(|p| p.0.is_finite() && p.1.is_finite());
+        finite.fold(None, |bounds: Option<Bounds>, &(lat, lon)| Some(match bounds {
+            None => Bounds::new((lat, lon), (lat, lon)),
+            Some(b) => Bounds::new((b.min_lat.min(lat), b.min_lon.min(lon)),
+                                   (b.max_lat.max(lat), b.max_lon.max(lon))),
+        })).unwrap_or_else(|| Bounds::new((-90.0, -180.0), (90.0, 180.0)))
+    }
+}
+
+/// A bounding box of the globe fitted onto an area of a canvas.
+///
+/// The box is scaled to fill as much of the area as it can without being stretched, and centred
+/// within it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MapView {
+    projection: Projection,
+    /// The projected position of the south-west corner of the box.
+    origin: (f64, f64),
+    /// Pixels per projected unit.
+    scale: f64,
+    /// The pixel the south-west corner of the box falls on.
+    offset: (f64, f64),
+    width: usize,
+    height: usize,
+}
+
+impl MapView {
+    /// Creates a new `MapView` fitting `bounds` onto the area of a canvas `width` by `height`
+    /// pixels, with its top left corner at the origin.
+    pub fn new(projection: Projection, bounds: Bounds, width: usize, height: usize) -> MapView {
+        let (x1, y1) = projection.project(bounds.min_lat, bounds.min_lon);
+        let (x2, y2) = projection.project(bounds.max_lat, bounds.max_lon);
+        let (span_x, span_y) = ((x2 - x1).abs(), (y2 - y1).abs());
+        let (w, h) = (width.saturating_sub(1) as f64, height.saturating_sub(1) as f64);
+        let scale = match (span_x > 0.0, span_y > 0.0) {
+            (true, true) => (w / span_x).min(h / span_y),
+            (true, false) => w / span_x,
+            (false, true) => h / span_y,
+            // A single point goes in the middle of the area.
+            (false, false) => 1.0,
+        };
+        MapView {
+            projection,
+            origin: (x1.min(x2), y1.min(y2)),
+            scale,
+            offset: ((w - span_x * scale) / 2.0, h - (h - span_y * scale) / 2.0),
+            width,
+            height,
+        }
+    }
+
+    /// Returns the canvas position of a point given in degrees, before rounding to a whole pixel.
+    pub fn position(&self, lat: f64, lon: f64) -> (f64, f64) {
+        let (x, y) = self.projection.project(lat, lon);
+        (self.offset.0 + (x - self.origin.0) * self.scale,
+         self.offset.1 - (y - self.origin.1) * self.scale)
+    }
+
+    /// Returns the pixel a point given in degrees falls on, or `None` if it falls outside the
+    /// area.
+    pub fn pixel(&self, lat: f64, lon: f64) -> Option<(usize, usize)> {
+        let (x, y) = self.position(lat, lon);
+        let (x, y) = (x.round(), y.round());
+        if x >= 0.0 && y >= 0.0 && x < self.width as f64 && y < self.height as f64 {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Draws lines joining a track of `(lat, lon)` points onto the `Canvas`.
+    ///
+    /// Segments with an end outside the area are left out.
+    pub fn draw_track(&self, canvas: &mut Canvas, track: &[(f64, f64)]) {
+        let mut last = None;
+        for &(lat, lon) in track.iter() {
+            let pixel = self.pixel(lat, lon);
+            match (last, pixel) {
+                (Some((x1, y1)), Some((x2, y2))) => canvas.line(x1, y1, x2, y2),
+                (None, Some((x, y))) => canvas.set(x, y),
+                _ => {},
+            }
+            last = pixel;
+        }
+    }
+}
+
+/// Simplifies a polyline with the Ramer–Douglas–Peucker algorithm, keeping only the points needed
+/// to stay within `tolerance` of the original line.
+///
+/// `tolerance` is in the same units as the points, so for `(lat, lon)` tracks it’s in degrees; the
+/// first and last points are always kept.
+///
+/// ```
+/// use drawille::geo::simplify;
+///
+/// let line = [(0.0, 0.0), (1.0, 0.01), (2.0, -0.01), (3.0, 0.0), (3.0, 5.0)];
+/// assert_eq!(simplify(&line, 0.1), vec![(0.0, 0.0), (3.0, 0.0), (3.0, 5.0)]);
+/// ```
+pub fn simplify(points: &[(f64, f64)], tolerance: f64) -> Vec<(f64, f64)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut spans = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = spans.pop() {
+        let (a, b) = (points[start], points[end]);
+        let mut furthest = (start, 0.0);
+        for (i, &p) in points.iter().enumerate().take(end).skip(start + 1) {
+            let distance = distance_to_segment(p, a, b);
+            if distance > furthest.1 {
+                furthest = (i, distance);
+            }
+        }
+        let (i, distance) = furthest;
+        if distance > tolerance {
+            keep[i] = true;
+            spans.push((start, i));
+            spans.push((i, end));
+        }
+    }
+    points.iter().zip(keep).filter(|p| p.1).map(|p| *p.0).collect()
+}
+
+/// Returns the distance from `p` to the closest point on the segment from `a` to `b`.
+fn distance_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length2 = dx * dx + dy * dy;
+    let t = if length2 > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (cx, cy) = (a.0 + t * dx, a.1 + t * dy);
+    ((p.0 - cx).powi(2) + (p.1 - cy).powi(2)).sqrt()
+}
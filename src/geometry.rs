@@ -0,0 +1,196 @@
+//! Rasterising shapes given in fractional coordinates, shared by the drawing APIs built on
+//! `braille::Canvas` and the shapes drawn on `block::Canvas`.
+
+use std::cmp;
+use std::f64::consts::PI;
+
+use braille::{DrawError, MAX_COORDINATE};
+
+/// The furthest from the origin anything is drawn, so that a stray point can’t make a canvas grow
+/// without bound.
+pub const LIMIT: f64 = 65_535.0;
+
+/// A rectangle as its left, top, right and bottom edges.
+pub type Rect = (f64, f64, f64, f64);
+
+/// The area everything is clipped to: the part of a canvas in reach of `LIMIT`.
+pub const BOUNDS: Rect = (0.0, 0.0, LIMIT, LIMIT);
+
+/// Checks that a pixel is within reach of the `try_` drawing methods.
+pub fn check_pixel(x: usize, y: usize) -> Result<(), DrawError> {
+    if x > MAX_COORDINATE || y > MAX_COORDINATE {
+        Err(DrawError::OutOfRange { x, y })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that text starting at a pixel fits within reach of the `try_` drawing methods, when
+/// each character is `width` pixels across, and has no control characters.
+pub fn check_text(x: usize, y: usize, width: usize, s: &str) -> Result<(), DrawError> {
+    if let Some(c) = s.chars().find(|c| c.is_control()) {
+        return Err(DrawError::InvalidText(c));
+    }
+    check_pixel(x, y)?;
+    check_pixel(x + s.chars().count().saturating_sub(1) * width, y)
+}
+
+/// Rounds a fractional coordinate to the nearest pixel, checking that it’s within reach of the
+/// `try_` drawing methods.
+pub fn checked_pixel(c: f64) -> Result<usize, DrawError> {
+    let rounded = c.round();
+    if (0.0..=MAX_COORDINATE as f64).contains(&rounded) {
+        Ok(rounded as usize)
+    } else {
+        Err(DrawError::InvalidCoordinate(c))
+    }
+}
+
+/// Clips the segment from `a` to `b` to a rectangle with the Liang–Barsky algorithm, returning
+/// the part inside, or `None` if none of it is.
+pub fn clip(a: (f64, f64), b: (f64, f64), rect: Rect) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    let edges = [(-dx, a.0 - rect.0), (dx, rect.2 - a.0), (-dy, a.1 - rect.1), (dy, rect.3 - a.1)];
+    for &(p, q) in edges.iter() {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 || t0.is_nan() || t1.is_nan() {
+        return None;
+    }
+    Some(((a.0 + t0 * dx, a.1 + t0 * dy), (a.0 + t1 * dx, a.1 + t1 * dy)))
+}
+
+/// Calls `plot` with each pixel on the line from `(x1, y1)` to `(x2, y2)`, stepping just as
+/// `Canvas::line` does.
+pub fn line_pixels<F: FnMut(usize, usize)>(x1: usize, y1: usize, x2: usize, y2: usize,
+                                           mut plot: F) {
+    let xdiff = cmp::max(x1, x2) - cmp::min(x1, x2);
+    let ydiff = cmp::max(y1, y2) - cmp::min(y1, y2);
+    let r = cmp::max(xdiff, ydiff);
+    for i in 0..r + 1 {
+        let step = |from: usize, to: usize, diff: usize| {
+            if diff == 0 {
+                from
+            } else if from <= to {
+                from + i * diff / r
+            } else {
+                from - i * diff / r
+            }
+        };
+        plot(step(x1, x2, xdiff), step(y1, y2, ydiff));
+    }
+}
+
+/// Calls `plot` with each pixel on the segment from `a` to `b`, clipped to `rect`.
+pub fn segment<F: FnMut(usize, usize)>(a: (f64, f64), b: (f64, f64), rect: Rect, plot: F) {
+    if let Some((a, b)) = clip(a, b, intersect(rect, BOUNDS)) {
+        let (x1, y1, x2, y2) = (a.0.round(), a.1.round(), b.0.round(), b.1.round());
+        line_pixels(x1 as usize, y1 as usize, x2 as usize, y2 as usize, plot);
+    }
+}
+
+/// Returns the overlap of two rectangles, which may be empty.
+pub fn intersect(a: Rect, b: Rect) -> Rect {
+    (a.0.max(b.0), a.1.max(b.1), a.2.min(b.2), a.3.min(b.3))
+}
+
+/// Returns points around a circle, enough for the segments joining them to look smooth when
+/// drawn `scale` times larger, ending with the first point again.
+pub fn circle_points(center: (f64, f64), radius: f64, scale: f64) -> Vec<(f64, f64)> {
+    ellipse_points(center, (radius, radius), scale)
+}
+
+/// Returns points around an ellipse with the given radii across and down, just as
+/// `circle_points` does for a circle.
+pub fn ellipse_points(center: (f64, f64), radii: (f64, f64), scale: f64) -> Vec<(f64, f64)> {
+    let circumference = 2.0 * PI * radii.0.abs().max(radii.1.abs()) * scale;
+    let segments = (circumference / 2.0).ceil().clamp(8.0, 1024.0) as usize;
+    (0..segments + 1).map(|i| {
+        let angle = 2.0 * PI * i as f64 / segments as f64;
+        (center.0 + radii.0 * angle.cos(), center.1 + radii.1 * angle.sin())
+    }).collect()
+}
+
+/// Returns the distance from `p` to the nearest point of the segment from `a` to `b`.
+pub fn distance_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (x, y) = (a.0 + t * dx - p.0, a.1 + t * dy - p.1);
+    (x * x + y * y).sqrt()
+}
+
+/// Detects whether `p` is inside a closed polygon, using the nonzero winding rule just as `fill`
+/// does.
+pub fn contains(polygon: &[(f64, f64)], p: (f64, f64)) -> bool {
+    let mut winding = 0;
+    for (&a, &b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        let crosses = (a.1 <= p.1) != (b.1 <= p.1);
+        if crosses && a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0) > p.0 {
+            winding += if a.1 < b.1 { 1 } else { -1 };
+        }
+    }
+    winding != 0
+}
+
+/// Calls `plot` with each pixel inside a shape made of closed polygons, clipped to `rect`.
+///
+/// A pixel is inside if its centre is, using the nonzero winding rule, so overlapping polygons
+/// going the same way fill their union and holes go the other way.
+pub fn fill<F: FnMut(usize, usize)>(polygons: &[Vec<(f64, f64)>], rect: Rect, mut plot: F) {
+    let edges = polygons.iter().flat_map(|polygon| {
+        polygon.iter().zip(polygon.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+    }).filter(|&(a, b)| a.1 != b.1).collect::<Vec<_>>();
+    let ys = || edges.iter().flat_map(|&(a, b)| vec![a.1, b.1]);
+    let (left, top, right, bottom) = intersect(rect, BOUNDS);
+    let first = ys().fold(f64::INFINITY, f64::min).floor().max(top.ceil());
+    let last = ys().fold(f64::NEG_INFINITY, f64::max).ceil().min(bottom.floor());
+    if edges.is_empty() || left > right || first > last {
+        return;
+    }
+
+    let mut crossings = vec![];
+    for y in first as usize..last as usize + 1 {
+        let centre = y as f64 + 0.5;
+        crossings.clear();
+        for &(a, b) in edges.iter() {
+            let (low, high, winding) = if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) };
+            if centre >= low.1 && centre < high.1 {
+                let x = low.0 + (centre - low.1) / (high.1 - low.1) * (high.0 - low.0);
+                crossings.push((x, winding));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(cmp::Ordering::Equal));
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            if winding == 0 {
+                continue;
+            }
+            // The pixels whose centres lie between the two crossings.
+            let start = (pair[0].0 - 0.5).ceil().max(left.ceil()).max(0.0);
+            let end = (pair[1].0 - 0.5).floor().min(right.floor());
+            if start <= end {
+                for x in start as usize..end as usize + 1 {
+                    plot(x, y);
+                }
+            }
+        }
+    }
+}
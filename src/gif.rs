@@ -0,0 +1,341 @@
+//! Recording canvases as animated GIFs
+//!
+//! A `Recorder` collects a sequence of frames, each drawn from a canvas, and writes them out as an
+//! animated GIF that loops forever. Pixels that a braille canvas leaves unset are transparent.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use drawille::braille::Canvas;
+//! use drawille::gif::Recorder;
+//!
+//! let mut recorder = Recorder::new(4);
+//! let mut canvas = Canvas::new(40, 40);
+//! for i in 0..40 {
+//!     canvas.line(0, i, 39, 39 - i);
+//!     recorder.push(&canvas, Duration::from_millis(50));
+//! }
+//! recorder.save("lines.gif").unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use block::Color;
+use image;
+
+/// The number of bits in each index of the colour table: one transparent entry and eight colours
+/// need four.
+const COLOR_BITS: u8 = 4;
+
+/// A single image to record, giving the colour of each pixel or `None` for transparency.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Option<Color>>,
+}
+
+impl Frame {
+    /// Creates a new `Frame` `width` by `height` pixels by calling `pixel` with the coordinates of
+    /// each pixel.
+    pub fn from_fn<F: Fn(usize, usize) -> Option<Color>>(width: usize, height: usize,
+                                                         pixel: F) -> Frame {
+        let pixels = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)))
+                                .map(|(x, y)| pixel(x, y))
+                                .collect();
+        Frame { width, height, pixels }
+    }
+}
+
+/// Collects frames and writes them as an animated GIF.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    scale: usize,
+    frames: Vec<(Frame, Duration)>,
+}
+
+impl Recorder {
+    /// Creates a new `Recorder` which draws each canvas pixel as a square `scale` pixels across.
+    pub fn new(scale: usize) -> Recorder {
+        Recorder {
+            scale: scale.max(1),
+            frames: vec![],
+        }
+    }
+
+    /// Adds a frame, such as a `&braille::Canvas` or `&block::Canvas`, to be shown for `delay`.
+    ///
+    /// GIFs time frames in hundredths of a second, so the delay is rounded to the nearest.
+    pub fn push<F: Into<Frame>>(&mut self, frame: F, delay: Duration) {
+        self.frames.push((frame.into(), delay));
+    }
+
+    /// Returns the number of frames recorded so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Detects whether no frames have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes the frames recorded so far as an animated GIF.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use drawille::braille::Canvas;
+    /// use drawille::gif::Recorder;
+    ///
+    /// let mut recorder = Recorder::new(2);
+    /// let mut canvas = Canvas::new(4, 8);
+    /// for i in 0..3 {
+    ///     canvas.set(i, i);
+    ///     recorder.push(&canvas, Duration::from_millis(100));
+    /// }
+    /// let mut gif = vec![];
+    /// recorder.write(&mut gif).unwrap();
+    ///
+    /// assert_eq!(&gif[..6], b"GIF89a");
+    /// assert_eq!(&gif[6..10], &[8, 0, 16, 0]);
+    /// // After the header and colour table come extensions, frames and the trailer.
+    /// let (mut pos, mut frames, mut looping) = (13 + 3 * 16, 0, false);
+    /// let skip_blocks = |mut pos: usize| {
+    ///     while gif[pos] != 0 {
+    ///         pos += gif[pos] as usize + 1;
+    ///     }
+    ///     pos + 1
+    /// };
+    /// while gif[pos] != 0x3b {
+    ///     if gif[pos] == 0x21 {
+    ///         looping |= &gif[pos + 2..pos + 14] == b"\x0bNETSCAPE2.0";
+    ///         pos = skip_blocks(pos + 2);
+    ///     } else {
+    ///         assert_eq!(gif[pos], 0x2c);
+    ///         frames += 1;
+    ///         pos = skip_blocks(pos + 11);
+    ///     }
+    /// }
+    /// assert_eq!((frames, looping, pos + 1), (3, true, gif.len()));
+    /// ```
+    ///
+    /// The pixels are compressed with LZW codes, starting the code table afresh whenever it fills
+    /// up:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use drawille::block::Color;
+    /// use drawille::gif::{Frame, Recorder};
+    ///
+    /// // Enough noise to fill the code table several times over.
+    /// let noise = |x: usize, y: usize| {
+    ///     let n = x as u64 * 73_856_093 ^ y as u64 * 19_349_663;
+    ///     (n.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 40) as usize % 9
+    /// };
+    /// let colors = [Color::Black, Color::Red, Color::Green, Color::Yellow, Color::Blue,
+    ///               Color::Magenta, Color::Cyan, Color::White];
+    /// let frame = Frame::from_fn(160, 120, |x, y| match noise(x, y) {
+    ///     0 => None,
+    ///     n => Some(colors[n - 1]),
+    /// });
+    /// let mut recorder = Recorder::new(1);
+    /// recorder.push(frame, Duration::from_millis(100));
+    /// let mut gif = vec![];
+    /// recorder.write(&mut gif).unwrap();
+    ///
+    /// // Gather the image data, after the header, colour table, extensions and image descriptor.
+    /// let mut pos = 13 + 3 * 16 + 19 + 8 + 10;
+    /// let min_size = gif[pos] as u32;
+    /// let mut data = vec![];
+    /// pos += 1;
+    /// while gif[pos] != 0 {
+    ///     data.extend_from_slice(&gif[pos + 1..pos + 1 + gif[pos] as usize]);
+    ///     pos += gif[pos] as usize + 1;
+    /// }
+    ///
+    /// let (clear, end) = (1 << min_size, (1 << min_size) + 1);
+    /// let reset = || (0..clear + 2).map(|i| vec![i as u8]).collect::<Vec<_>>();
+    /// let (mut table, mut size, mut previous) = (reset(), min_size + 1, None::<Vec<u8>>);
+    /// let (mut bit, mut clears, mut pixels) = (0, 0, vec![]);
+    /// loop {
+    ///     let code = (0..size).fold(0, |code, i| {
+    ///         let b = bit + i as usize;
+    ///         code | ((data[b / 8] as usize >> (b % 8)) & 1) << i
+    ///     });
+    ///     bit += size as usize;
+    ///     if code == clear {
+    ///         table = reset();
+    ///         size = min_size + 1;
+    ///         previous = None;
+    ///         clears += 1;
+    ///         continue;
+    ///     }
+    ///     if code == end {
+    ///         break;
+    ///     }
+    ///     let entry = match (table.get(code), &previous) {
+    ///         (Some(entry), _) => entry.clone(),
+    ///         (None, Some(p)) => [&p[..], &p[..1]].concat(),
+    ///         (None, None) => panic!("code {} before the table has it", code),
+    ///     };
+    ///     pixels.extend_from_slice(&entry);
+    ///     if let Some(p) = previous {
+    ///         if table.len() < 4096 {
+    ///             table.push([&p[..], &entry[..1]].concat());
+    ///             if table.len() == 1 << size && size < 12 {
+    ///                 size += 1;
+    ///             }
+    ///         }
+    ///     }
+    ///     previous = Some(entry);
+    /// }
+    ///
+    /// assert!(clears > 1);
+    /// let expected = (0..120).flat_map(|y| (0..160).map(move |x| noise(x, y) as u8));
+    /// assert!(pixels.into_iter().eq(expected));
+    /// ```
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let width = self.frames.iter().map(|f| f.0.width).max().unwrap_or(0) * self.scale;
+        let height = self.frames.iter().map(|f| f.0.height).max().unwrap_or(0) * self.scale;
+        out.write_all(b"GIF89a")?;
+        out.write_all(&dimension(width)?)?;
+        out.write_all(&dimension(height)?)?;
+        // A global colour table with 2^COLOR_BITS entries, the first of which is the background.
+        out.write_all(&[0xf0 | (COLOR_BITS - 1), 0, 0])?;
+        out.write_all(&[0, 0, 0])?;
+        for i in 0..8u8 {
+            out.write_all(&image::rgb(color(i)))?;
+        }
+        out.write_all(&[0; 3 * 7])?;
+        // Loop forever.
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        for &(ref frame, delay) in self.frames.iter() {
+            let centiseconds = ((delay.as_millis() + 5) / 10).min(u16::MAX as u128) as u16;
+            // Clear each frame to the background once shown, with the first colour transparent.
+            out.write_all(&[0x21, 0xf9, 0x04, 0x09])?;
+            out.write_all(&centiseconds.to_le_bytes())?;
+            out.write_all(&[0, 0])?;
+
+            let (frame_width, frame_height) = (frame.width * self.scale, frame.height * self.scale);
+            out.write_all(&[0x2c, 0, 0, 0, 0])?;
+            out.write_all(&dimension(frame_width)?)?;
+            out.write_all(&dimension(frame_height)?)?;
+            out.write_all(&[0])?;
+
+            let mut indices = Vec::with_capacity(frame_width * frame_height);
+            for y in 0..frame_height {
+                for x in 0..frame_width {
+                    let pixel = frame.pixels[y / self.scale * frame.width + x / self.scale];
+                    indices.push(pixel.map_or(0, |c| c as u8 + 1));
+                }
+            }
+            out.write_all(&[COLOR_BITS])?;
+            for block in lzw(&indices, COLOR_BITS).chunks(255) {
+                out.write_all(&[block.len() as u8])?;
+                out.write_all(block)?;
+            }
+            out.write_all(&[0])?;
+        }
+        out.write_all(b"\x3b")
+    }
+
+    /// Writes the frames recorded so far as an animated GIF to a file at the given path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()
+    }
+}
+
+/// Returns the colour with the given number, the reverse of `color as u8`.
+fn color(i: u8) -> Color {
+    [Color::Black, Color::Red, Color::Green, Color::Yellow,
+     Color::Blue, Color::Magenta, Color::Cyan, Color::White][i as usize]
+}
+
+/// Returns an image dimension as GIF stores it, or an error if it’s too large.
+fn dimension(size: usize) -> io::Result<[u8; 2]> {
+    if size > u16::MAX as usize {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "frame too large for a GIF"));
+    }
+    Ok((size as u16).to_le_bytes())
+}
+
+/// Compresses colour indices with the variable-length LZW coding used by GIF.
+fn lzw(indices: &[u8], min_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_size;
+    let end = clear + 1;
+    let mut out = Bits::default();
+    let mut table = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_size + 1;
+    out.push(clear, size);
+
+    let mut prefix = None;
+    for &index in indices {
+        prefix = Some(match prefix {
+            None => index as u16,
+            Some(code) => match table.get(&(code, index)) {
+                Some(&longer) => longer,
+                None => {
+                    out.push(code, size);
+                    if next == 4096 {
+                        // The table is full, so start again.
+                        out.push(clear, size);
+                        table.clear();
+                        next = end + 1;
+                        size = min_size + 1;
+                    } else {
+                        table.insert((code, index), next);
+                        if next == 1 << size {
+                            size += 1;
+                        }
+                        next += 1;
+                    }
+                    index as u16
+                },
+            },
+        });
+    }
+    if let Some(code) = prefix {
+        out.push(code, size);
+    }
+    out.push(end, size);
+    out.finish()
+}
+
+/// Packs codes into bytes from the least significant bit up.
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    pending: u32,
+    count: u8,
+}
+
+impl Bits {
+    fn push(&mut self, code: u16, size: u8) {
+        self.pending |= (code as u32) << self.count;
+        self.count += size;
+        while self.count >= 8 {
+            self.bytes.push(self.pending as u8);
+            self.pending >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.pending as u8);
+        }
+        self.bytes
+    }
+}
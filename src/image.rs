@@ -0,0 +1,197 @@
+//! Encoding pixels as PNG images for terminals that can show them, through the kitty graphics
+//! protocol or iTerm2’s inline images.
+//!
+//! The canvases use these to draw themselves as images; they’re public for drawing other pixels
+//! the same way.
+
+use block::Color;
+
+/// The red, green and blue of each `Color`.
+pub fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::White => [229, 229, 229],
+    }
+}
+
+/// Encodes an image `width` by `height` pixels as a PNG file, drawing each pixel as a square
+/// `scale` pixels across.
+///
+/// `pixel` gives the colour of each pixel, or `None` to leave it transparent.
+///
+/// ```
+/// use drawille::block::Color;
+/// use drawille::image::png;
+///
+/// let image = png(3, 2, 2, |x, _| if x == 0 { Some(Color::Red) } else { None });
+/// assert_eq!(&image[..8], b"\x89PNG\r\n\x1a\n");
+/// // The header chunk: 13 bytes giving the size, eight-bit RGBA and no interlacing.
+/// assert_eq!(&image[8..16], b"\0\0\0\x0dIHDR");
+/// assert_eq!(&image[16..29], &[0, 0, 0, 6, 0, 0, 0, 4, 8, 6, 0, 0, 0]);
+/// // The end chunk, which is empty, along with its checksum.
+/// assert!(image.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+/// ```
+pub fn png<F: Fn(usize, usize) -> Option<Color>>(width: usize, height: usize, scale: usize,
+                                                 pixel: F) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (out_width, out_height) = (width * scale, height * scale);
+    let mut data = Vec::with_capacity((out_width * 4 + 1) * out_height);
+    for y in 0..out_height {
+        // Each scanline starts with its filter type, which is always none here.
+        data.push(0);
+        for x in 0..out_width {
+            match pixel(x / scale, y / scale) {
+                Some(color) => {
+                    data.extend_from_slice(&rgb(color));
+                    data.push(255);
+                },
+                None => data.extend_from_slice(&[0, 0, 0, 0]),
+            }
+        }
+    }
+
+    let mut header = vec![];
+    header.extend_from_slice(&(out_width as u32).to_be_bytes());
+    header.extend_from_slice(&(out_height as u32).to_be_bytes());
+    // Eight bits per channel, RGBA, with the default compression, filtering and no interlacing.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut result = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut result, b"IHDR", &header);
+    push_chunk(&mut result, b"IDAT", &zlib(&data));
+    push_chunk(&mut result, b"IEND", &[]);
+    result
+}
+
+fn push_chunk(result: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    result.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = result.len();
+    result.extend_from_slice(kind);
+    result.extend_from_slice(data);
+    let crc = crc32(&result[start..]);
+    result.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Wraps data in a zlib stream without compressing it.
+///
+/// Canvas images are small and mostly flat colour, so the terminal’s time decoding them matters
+/// far more than their size.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        result.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        result.push(blocks.peek().is_none() as u8);
+        result.extend_from_slice(&len.to_le_bytes());
+        result.extend_from_slice(&(!len).to_le_bytes());
+        result.extend_from_slice(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    result.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    result
+}
+
+/// Encodes data as standard, padded base64.
+///
+/// ```
+/// use drawille::image::base64;
+///
+/// let vectors = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="),
+///                ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+/// for &(data, encoded) in vectors.iter() {
+///     assert_eq!(base64(data.as_bytes()), encoded);
+/// }
+/// ```
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for group in data.chunks(3) {
+        let bytes = [group[0], *group.get(1).unwrap_or(&0), *group.get(2).unwrap_or(&0)];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= group.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Returns the kitty graphics protocol escape sequences to show a PNG image at the cursor.
+///
+/// Images with more than 4096 bytes of base64 are split across several escape sequences, each
+/// saying whether more follow.
+///
+/// ```
+/// use drawille::block::Color;
+/// use drawille::image::{base64, kitty, png};
+///
+/// let image = png(64, 64, 1, |_, _| Some(Color::Blue));
+/// let escapes = kitty(&image);
+/// let chunks = escapes.split_terminator("\x1b\\").collect::<Vec<_>>();
+/// assert_eq!(chunks.len(), base64(&image).len().div_ceil(4096));
+/// assert!(chunks[0].starts_with("\x1b_Ga=T,f=100,q=2,m=1;"));
+/// assert!(chunks[1..chunks.len() - 1].iter().all(|c| c.starts_with("\x1b_Gm=1;")));
+/// assert!(chunks[chunks.len() - 1].starts_with("\x1b_Gm=0;"));
+/// let data = chunks.iter().map(|c| &c[c.find(';').unwrap() + 1..]).collect::<String>();
+/// assert_eq!(data, base64(&image));
+/// ```
+pub fn kitty(png: &[u8]) -> String {
+    let data = base64(png);
+    // The protocol limits each escape sequence to 4096 bytes of data.
+    let chunks = data.as_bytes().chunks(4096).collect::<Vec<_>>();
+    let mut result = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        if i == 0 {
+            result.push_str(&format!("\x1b_Ga=T,f=100,q=2,m={};", more));
+        } else {
+            result.push_str(&format!("\x1b_Gm={};", more));
+        }
+        result.push_str(::std::str::from_utf8(chunk).unwrap());
+        result.push_str("\x1b\\");
+    }
+    result
+}
+
+/// Returns the iTerm2 inline image escape sequence to show a PNG image at the cursor.
+///
+/// ```
+/// use drawille::block::Color;
+/// use drawille::image::{base64, iterm2, png};
+///
+/// let image = png(4, 4, 2, |_, _| Some(Color::Green));
+/// let escape = iterm2(&image);
+/// let size = format!(";size={};", image.len());
+/// assert!(escape.starts_with("\x1b]1337;File=inline=1") && escape.contains(&size));
+/// assert!(escape.ends_with(&format!(":{}\x07", base64(&image))));
+/// ```
+pub fn iterm2(png: &[u8]) -> String {
+    format!("\x1b]1337;File=inline=1;size={};preserveAspectRatio=1:{}\x07", png.len(), base64(png))
+}
@@ -0,0 +1,233 @@
+//! Keyboard and mouse input from a terminal in raw mode
+//!
+//! `parse` turns the bytes a terminal sends into `Event`s, understanding the common escape
+//! sequences for special keys and SGR mouse reporting. The `animation` module uses it to deliver
+//! input to interactive animations.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::input::{self, Event, Key, Mouse, MouseButton, MouseKind};
+//!
+//! let mut bytes = b"q\x1b[A\x1b[<0;11;3M".to_vec();
+//! let events = input::parse(&mut bytes);
+//! assert_eq!(events, vec![
+//!     Event::Key(Key::Char('q')),
+//!     Event::Key(Key::Up),
+//!     Event::Mouse(Mouse { kind: MouseKind::Press(MouseButton::Left), column: 10, row: 2 }),
+//! ]);
+//! if let Event::Mouse(mouse) = events[2] {
+//!     assert_eq!(mouse.pixel(), (20, 8));
+//! }
+//! ```
+
+use std::str;
+
+/// Something the user did.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Key(Key),
+    Mouse(Mouse),
+}
+
+/// A key pressed on the keyboard.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// A character typed, including a space.
+    Char(char),
+    /// A letter typed with Ctrl held down, given in lower case.
+    Ctrl(char),
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+}
+
+/// A mouse button.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// What the mouse did.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MouseKind {
+    Press(MouseButton),
+    Release(MouseButton),
+    /// The mouse moved with a button held down.
+    Drag(MouseButton),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Something the mouse did over a character of the terminal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mouse {
+    pub kind: MouseKind,
+    /// The column of the character, counting from zero at the left.
+    pub column: usize,
+    /// The row of the character, counting from zero at the top.
+    pub row: usize,
+}
+
+impl Mouse {
+    /// Returns the coordinates of the top left pixel of the character on a `braille::Canvas`
+    /// drawn from the top left corner of the terminal.
+    ///
+    /// The character covers two pixels across and four down from there.
+    pub fn pixel(&self) -> (usize, usize) {
+        (self.column * 2, self.row * 4)
+    }
+
+    /// Returns the coordinates of the middle of the character on a `braille::Canvas` drawn from
+    /// the top left corner of the terminal, which suits hit testing better than its corner.
+    pub fn center(&self) -> (f64, f64) {
+        (self.column as f64 * 2.0 + 1.0, self.row as f64 * 4.0 + 2.0)
+    }
+
+    /// Returns the coordinates of the pixel under the mouse on a `braille::Canvas` drawn from the
+    /// top left corner of a terminal reporting mouse positions in its own pixels rather than in
+    /// characters, with each character `cell_width` by `cell_height` of its pixels.
+    ///
+    /// Terminals that support it report pixels once sent `\x1b[?1016h`; `column` and `row` then
+    /// hold the terminal pixel under the mouse, which this maps to one of the eight dots of the
+    /// character beneath it.
+    ///
+    /// ```
+    /// use drawille::input::{Mouse, MouseButton, MouseKind};
+    ///
+    /// // The right of the second character, a quarter of the way down, with 10 by 20 cells.
+    /// let mouse = Mouse { kind: MouseKind::Press(MouseButton::Left), column: 17, row: 6 };
+    /// assert_eq!(mouse.pixel_from_pixels(10, 20), (3, 1));
+    /// ```
+    pub fn pixel_from_pixels(&self, cell_width: usize, cell_height: usize) -> (usize, usize) {
+        (self.column * 2 / cell_width.max(1), self.row * 4 / cell_height.max(1))
+    }
+}
+
+/// Parses the events in bytes read from a terminal in raw mode, removing them from `bytes`.
+///
+/// A sequence cut off at the end of `bytes` is left there, to be completed by the bytes read next,
+/// except for a lone escape byte, which is taken as the escape key. Bytes that don’t make sense
+/// are skipped.
+pub fn parse(bytes: &mut Vec<u8>) -> Vec<Event> {
+    let mut events = vec![];
+    let mut start = 0;
+    while start < bytes.len() {
+        match parse_one(&bytes[start..]) {
+            Some((event, len)) => {
+                events.extend(event);
+                start += len;
+            },
+            None => break,
+        }
+    }
+    bytes.drain(..start);
+    events
+}
+
+/// Parses the event at the start of `bytes`, returning it, if the bytes made sense, and how many
+/// bytes it took, or `None` if the bytes stop partway through it.
+fn parse_one(bytes: &[u8]) -> Option<(Option<Event>, usize)> {
+    let key = |key| Some((Some(Event::Key(key)), 1));
+    match bytes[0] {
+        b'\r' | b'\n' => key(Key::Enter),
+        b'\t' => key(Key::Tab),
+        0x08 | 0x7f => key(Key::Backspace),
+        c @ 0x01..=0x1a => key(Key::Ctrl((b'a' + c - 1) as char)),
+        0x1b => match bytes.get(1) {
+            None => key(Key::Escape),
+            Some(&b'[') => parse_csi(bytes),
+            Some(&b'O') => {
+                let key = match *bytes.get(2)? {
+                    b'A' => Key::Up,
+                    b'B' => Key::Down,
+                    b'C' => Key::Right,
+                    b'D' => Key::Left,
+                    b'H' => Key::Home,
+                    b'F' => Key::End,
+                    _ => return Some((None, 3)),
+                };
+                Some((Some(Event::Key(key)), 3))
+            },
+            Some(_) => key(Key::Escape),
+        },
+        c => {
+            // The length of a UTF-8 sequence follows from its first byte.
+            let len = match c {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Some((None, 1)),
+            };
+            if bytes.len() < len {
+                return None;
+            }
+            let c = str::from_utf8(&bytes[..len]).ok().and_then(|s| s.chars().next());
+            Some((c.map(|c| Event::Key(Key::Char(c))), len))
+        },
+    }
+}
+
+/// Parses a control sequence starting with `ESC [`.
+fn parse_csi(bytes: &[u8]) -> Option<(Option<Event>, usize)> {
+    let end = 2 + bytes[2..].iter().position(|&b| (0x40..=0x7e).contains(&b))?;
+    let params = str::from_utf8(&bytes[2..end]).unwrap_or("");
+    let event = match (bytes[end], params) {
+        (b'A', _) => Some(Event::Key(Key::Up)),
+        (b'B', _) => Some(Event::Key(Key::Down)),
+        (b'C', _) => Some(Event::Key(Key::Right)),
+        (b'D', _) => Some(Event::Key(Key::Left)),
+        (b'H', _) => Some(Event::Key(Key::Home)),
+        (b'F', _) => Some(Event::Key(Key::End)),
+        (b'~', params) => match params.split(';').next().unwrap_or("") {
+            "1" | "7" => Some(Event::Key(Key::Home)),
+            "2" => Some(Event::Key(Key::Insert)),
+            "3" => Some(Event::Key(Key::Delete)),
+            "4" | "8" => Some(Event::Key(Key::End)),
+            "5" => Some(Event::Key(Key::PageUp)),
+            "6" => Some(Event::Key(Key::PageDown)),
+            _ => None,
+        },
+        (b'M', params) | (b'm', params) if params.starts_with('<') => {
+            parse_mouse(&params[1..], bytes[end] == b'M').map(Event::Mouse)
+        },
+        _ => None,
+    };
+    Some((event, end + 1))
+}
+
+/// Parses the parameters of an SGR mouse report, which are the button and modifiers, then the
+/// column and row counting from one, and whether it was a press rather than a release.
+fn parse_mouse(params: &str, press: bool) -> Option<Mouse> {
+    let mut numbers = params.split(';').map(|n| n.parse::<usize>().ok());
+    let (code, column, row) = (numbers.next()??, numbers.next()??, numbers.next()??);
+    let button = match code & 0b11 {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        _ => MouseButton::Right,
+    };
+    let kind = if code & 64 != 0 {
+        if code & 1 == 0 { MouseKind::ScrollUp } else { MouseKind::ScrollDown }
+    } else if code & 32 != 0 {
+        MouseKind::Drag(button)
+    } else if press {
+        MouseKind::Press(button)
+    } else {
+        MouseKind::Release(button)
+    };
+    Some(Mouse { kind, column: column.saturating_sub(1), row: row.saturating_sub(1) })
+}
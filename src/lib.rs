@@ -17,11 +17,61 @@
 //!     canvas.line(2, 2, 8, 8);
 //!     assert_eq!(canvas.frame(),
 //! " \
-//!  ⢄    
-//!   ⠙⢄  
-//!     ⠁ ");
+//!  ⢄   
+//!   ⠙⢄ 
+//!     ⠁");
 //! }
 //! ```
 
+#[cfg(unix)]
+extern crate libc;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "ratatui")]
+extern crate ratatui;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+extern crate unicode_width;
+
+pub mod animation;
+pub mod backend;
+pub mod banner;
 pub mod braille;
 pub mod block;
+pub mod cast;
+mod cells;
+pub mod chart;
+pub mod colormap;
+pub mod console;
+pub mod context;
+pub mod dither;
+pub mod fractals;
+pub mod frame;
+pub mod geo;
+mod geometry;
+#[cfg(feature = "gif")]
+pub mod gif;
+pub mod image;
+pub mod input;
+pub mod maze;
+#[cfg(feature = "noise")]
+pub mod noise;
+pub mod octant;
+pub mod palette;
+pub mod particles;
+pub mod path;
+pub mod quadrant;
+pub mod scene;
+pub mod sextant;
+pub mod shade;
+#[cfg(feature = "sixel")]
+mod sixel;
+pub mod sprite;
+pub mod table;
+pub mod tape;
+pub mod text;
+pub mod tiles;
+pub mod transform;
+pub mod tween;
+pub mod video;
+pub mod wireframe;
@@ -0,0 +1,291 @@
+//! Mazes
+//!
+//! A `Maze` is a grid of cells with walls between them. It can be carved into a perfect maze—one
+//! with exactly one way between any two cells—by the recursive backtracker, which makes long
+//! winding corridors, or by Kruskal’s algorithm, which makes many short dead ends. Its walls are
+//! drawn as lines of Braille pixels along the edges of the cells, and the way through it as a line
+//! along their centres.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::maze::Maze;
+//!
+//! let maze = Maze::backtracker(8, 4, 1);
+//! let path = maze.solve((0, 0), (7, 3)).unwrap();
+//! assert_eq!(path.first(), Some(&(0, 0)));
+//! assert_eq!(path.last(), Some(&(7, 3)));
+//!
+//! let mut canvas = Canvas::new(0, 0);
+//! maze.draw(&mut canvas, 0, 0, 4);
+//! maze.draw_path(&mut canvas, 0, 0, 4, &path);
+//! println!("{}", canvas.frame());
+//! ```
+
+use std::collections::VecDeque;
+
+use braille::Canvas;
+
+/// One of the four sides of a cell.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] =
+        [Direction::North, Direction::East, Direction::South, Direction::West];
+}
+
+/// A small pseudo-random number generator, so that the same seed makes the same maze.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        // SplitMix64.
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number from zero up to but not including `n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// A grid of cells and the walls between them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Maze {
+    width: usize,
+    height: usize,
+    /// Whether there’s a wall on the east side of each cell, a row at a time.
+    east: Vec<bool>,
+    /// Whether there’s a wall on the south side of each cell, a row at a time.
+    south: Vec<bool>,
+}
+
+impl Maze {
+    /// Creates a new `Maze` `width` by `height` cells with every wall standing.
+    pub fn new(width: usize, height: usize) -> Maze {
+        Maze {
+            width,
+            height,
+            east: vec![true; width * height],
+            south: vec![true; width * height],
+        }
+    }
+
+    /// Creates a new `Maze` `width` by `height` cells carved by the recursive backtracker, which
+    /// wanders from the top left cell knocking down walls until it runs into a dead end, then
+    /// backs up to the last cell it can wander on from.
+    pub fn backtracker(width: usize, height: usize, seed: u64) -> Maze {
+        let mut maze = Maze::new(width, height);
+        if width == 0 || height == 0 {
+            return maze;
+        }
+        let mut rng = Rng(seed);
+        let mut visited = vec![false; width * height];
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+        while let Some(&(x, y)) = stack.last() {
+            let unvisited = Direction::ALL.iter()
+                .filter_map(|&d| maze.neighbor(x, y, d).map(|n| (d, n)))
+                .filter(|&(_, (nx, ny))| !visited[ny * width + nx])
+                .collect::<Vec<_>>();
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+            let (direction, (nx, ny)) = unvisited[rng.below(unvisited.len())];
+            maze.open(x, y, direction);
+            visited[ny * width + nx] = true;
+            stack.push((nx, ny));
+        }
+        maze
+    }
+
+    /// Creates a new `Maze` `width` by `height` cells carved by Kruskal’s algorithm, which goes
+    /// through every wall in a random order and knocks it down if the cells either side of it
+    /// aren’t yet joined.
+    pub fn kruskal(width: usize, height: usize, seed: u64) -> Maze {
+        let mut maze = Maze::new(width, height);
+        let mut rng = Rng(seed);
+        let mut walls = vec![];
+        for y in 0..height {
+            for x in 0..width {
+                if x + 1 < width {
+                    walls.push((x, y, Direction::East));
+                }
+                if y + 1 < height {
+                    walls.push((x, y, Direction::South));
+                }
+            }
+        }
+        for i in (1..walls.len()).rev() {
+            walls.swap(i, rng.below(i + 1));
+        }
+        // Each cell’s parent in a forest of the cells joined so far.
+        let mut parents = (0..width * height).collect::<Vec<_>>();
+        fn root(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        for (x, y, direction) in walls {
+            let (nx, ny) = maze.neighbor(x, y, direction).unwrap();
+            let (a, b) = (root(&mut parents, y * width + x), root(&mut parents, ny * width + nx));
+            if a != b {
+                parents[a] = b;
+                maze.open(x, y, direction);
+            }
+        }
+        maze
+    }
+
+    /// Returns the number of cells across the maze.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of cells down the maze.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Detects whether there’s a wall on the given side of a cell. The outside of the maze is
+    /// always walled, as is anywhere beyond it.
+    pub fn has_wall(&self, x: usize, y: usize, direction: Direction) -> bool {
+        if x >= self.width || y >= self.height {
+            return true;
+        }
+        match direction {
+            Direction::East => self.east[y * self.width + x],
+            Direction::South => self.south[y * self.width + x],
+            Direction::West => x == 0 || self.east[y * self.width + x - 1],
+            Direction::North => y == 0 || self.south[(y - 1) * self.width + x],
+        }
+    }
+
+    /// Knocks down the wall on the given side of a cell, unless it’s on the outside of the maze.
+    pub fn open(&mut self, x: usize, y: usize, direction: Direction) {
+        self.set_wall(x, y, direction, false);
+    }
+
+    /// Puts up a wall on the given side of a cell.
+    pub fn close(&mut self, x: usize, y: usize, direction: Direction) {
+        self.set_wall(x, y, direction, true);
+    }
+
+    /// Returns the shortest way from one cell to another, as every cell along it from the first
+    /// to the last, or `None` if there isn’t one.
+    pub fn solve(&self, from: (usize, usize), to: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        if from.0 >= self.width || from.1 >= self.height {
+            return None;
+        }
+        // The cell each cell was first reached from, searching breadth first from `from`.
+        let mut came_from = vec![None; self.width * self.height];
+        let mut queue = VecDeque::new();
+        came_from[from.1 * self.width + from.0] = Some(from);
+        queue.push_back(from);
+        while let Some((x, y)) = queue.pop_front() {
+            if (x, y) == to {
+                let mut path = vec![to];
+                let mut cell = to;
+                while cell != from {
+                    cell = came_from[cell.1 * self.width + cell.0].unwrap();
+                    path.push(cell);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &direction in Direction::ALL.iter() {
+                if self.has_wall(x, y, direction) {
+                    continue;
+                }
+                if let Some((nx, ny)) = self.neighbor(x, y, direction) {
+                    if came_from[ny * self.width + nx].is_none() {
+                        came_from[ny * self.width + nx] = Some((x, y));
+                        queue.push_back((nx, ny));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Draws the walls of the maze onto the canvas with its top left corner at `(x, y)`, each cell
+    /// `cell_size` pixels across with its walls along its edges.
+    ///
+    /// Neighbouring cells share the walls between them, so the maze takes up
+    /// `width * cell_size + 1` by `height * cell_size + 1` pixels. With a `cell_size` of 2, each
+    /// Braille character holds one cell across, and with 4, one cell down.
+    pub fn draw(&self, canvas: &mut Canvas, x: usize, y: usize, cell_size: usize) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        let (right, bottom) = (x + self.width * cell_size, y + self.height * cell_size);
+        canvas.line(x, y, right, y);
+        canvas.line(x, y, x, bottom);
+        for cy in 0..self.height {
+            for cx in 0..self.width {
+                let (left, top) = (x + cx * cell_size, y + cy * cell_size);
+                if self.has_wall(cx, cy, Direction::East) {
+                    canvas.line(left + cell_size, top, left + cell_size, top + cell_size);
+                }
+                if self.has_wall(cx, cy, Direction::South) {
+                    canvas.line(left, top + cell_size, left + cell_size, top + cell_size);
+                }
+            }
+        }
+    }
+
+    /// Draws a way through the maze, such as one returned by `solve`, as a line joining the
+    /// centres of its cells, for a maze drawn by `draw` at `(x, y)` with the same `cell_size`.
+    pub fn draw_path(&self, canvas: &mut Canvas, x: usize, y: usize, cell_size: usize,
+                     path: &[(usize, usize)]) {
+        let center = |&(cx, cy): &(usize, usize)| {
+            (x + cx * cell_size + cell_size / 2, y + cy * cell_size + cell_size / 2)
+        };
+        if let Some(first) = path.first() {
+            let (px, py) = center(first);
+            canvas.set(px, py);
+        }
+        for pair in path.windows(2) {
+            let ((x1, y1), (x2, y2)) = (center(&pair[0]), center(&pair[1]));
+            canvas.line(x1, y1, x2, y2);
+        }
+    }
+
+    /// Returns the cell on the given side of a cell, or `None` if that’s outside the maze.
+    fn neighbor(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        match direction {
+            Direction::North if y > 0 => Some((x, y - 1)),
+            Direction::East if x + 1 < self.width => Some((x + 1, y)),
+            Direction::South if y + 1 < self.height => Some((x, y + 1)),
+            Direction::West if x > 0 => Some((x - 1, y)),
+            _ => None,
+        }
+    }
+
+    fn set_wall(&mut self, x: usize, y: usize, direction: Direction, wall: bool) {
+        if x >= self.width || y >= self.height || self.neighbor(x, y, direction).is_none() {
+            return;
+        }
+        match direction {
+            Direction::East => self.east[y * self.width + x] = wall,
+            Direction::South => self.south[y * self.width + x] = wall,
+            Direction::West => self.east[y * self.width + x - 1] = wall,
+            Direction::North => self.south[(y - 1) * self.width + x] = wall,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Procedural noise
+//!
+//! This module provides two-dimensional value and Perlin noise: smoothly varying pseudo-random
+//! numbers from 0 to 1 at every point of the plane, the same every time for the same seed. That’s
+//! what terrain, clouds and fire in screensavers and map generators are made from. A canvas can
+//! be filled wherever the noise passes a threshold, or dithered to show the noise in shades of
+//! grey.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::noise;
+//!
+//! let mut land = Canvas::new(80, 40);
+//! noise::fill(&mut land, 80, 40, 0.5, |x, y| noise::fractal(x / 20.0, y / 20.0, 7, 4));
+//! println!("{}", land.frame());
+//!
+//! let n = noise::perlin(1.5, 2.25, 7);
+//! assert!(n >= 0.0 && n <= 1.0);
+//! assert_eq!(n, noise::perlin(1.5, 2.25, 7));
+//! ```
+
+use braille::Canvas;
+use dither;
+
+/// Returns a pseudo-random number for a point of the integer grid.
+fn hash(x: i64, y: i64, seed: u32) -> u64 {
+    let mut h = (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+        ^ (seed as u64).wrapping_mul(0x1656_67b1_9e37_79f9);
+    // The finishing steps of SplitMix64, so that nearby points get unrelated numbers.
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+/// Eases a fraction between zero and one, so that noise is smooth across grid lines.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Blends the values `corner` gives at the four grid points around `(x, y)`, where each value is
+/// given the grid point and the offset of `(x, y)` from it.
+fn interpolate<F: Fn(i64, i64, f64, f64) -> f64>(x: f64, y: f64, corner: F) -> f64 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (ix, iy) = (x0 as i64, y0 as i64);
+    let top = lerp(corner(ix, iy, fx, fy), corner(ix + 1, iy, fx - 1.0, fy), fade(fx));
+    let bottom = lerp(corner(ix, iy + 1, fx, fy - 1.0), corner(ix + 1, iy + 1, fx - 1.0, fy - 1.0),
+                      fade(fx));
+    lerp(top, bottom, fade(fy))
+}
+
+/// Returns value noise at a point: random values from 0 to 1 at each point of the integer grid,
+/// blended smoothly between them.
+///
+/// Value noise is cheaper than Perlin noise but blockier, with its features lined up along the
+/// grid.
+pub fn value(x: f64, y: f64, seed: u32) -> f64 {
+    interpolate(x, y, |ix, iy, _, _| (hash(ix, iy, seed) >> 11) as f64 / (1u64 << 53) as f64)
+}
+
+/// Returns Perlin noise at a point, from 0 to 1, with features about one unit across.
+///
+/// Perlin noise picks a random slope at each point of the integer grid rather than a value, which
+/// hides the grid much better than value noise does. It’s 0.5 at every grid point.
+pub fn perlin(x: f64, y: f64, seed: u32) -> f64 {
+    let n = interpolate(x, y, |ix, iy, dx, dy| {
+        // One of eight directions, each of unit length.
+        match hash(ix, iy, seed) & 7 {
+            0 => dx,
+            1 => -dx,
+            2 => dy,
+            3 => -dy,
+            4 => (dx + dy) * 0.5f64.sqrt(),
+            5 => (dx - dy) * 0.5f64.sqrt(),
+            6 => (-dx + dy) * 0.5f64.sqrt(),
+            _ => (-dx - dy) * 0.5f64.sqrt(),
+        }
+    });
+    // With unit slopes, Perlin noise stays within half of the square root of two of zero.
+    (n * 0.5f64.sqrt() + 0.5).clamp(0.0, 1.0)
+}
+
+/// Returns `octaves` layers of Perlin noise at a point added together, from 0 to 1, each layer
+/// with features half the size and half the strength of the one before.
+///
+/// This gives the rough, detailed look of coastlines and clouds that a single layer lacks.
+pub fn fractal(x: f64, y: f64, seed: u32, octaves: usize) -> f64 {
+    let (mut total, mut strength, mut scale, mut sum) = (0.0, 1.0, 1.0, 0.0);
+    for octave in 0..octaves {
+        total += perlin(x * scale, y * scale, seed.wrapping_add(octave as u32)) * strength;
+        sum += strength;
+        strength /= 2.0;
+        scale *= 2.0;
+    }
+    if sum > 0.0 { total / sum } else { 0.5 }
+}
+
+/// Sets each pixel of the canvas within `width` by `height` pixels where `noise`, given the
+/// pixel’s coordinates, is above `threshold`.
+pub fn fill<F: Fn(f64, f64) -> f64>(canvas: &mut Canvas, width: usize, height: usize,
+                                     threshold: f64, noise: F) {
+    for y in 0..height {
+        for x in 0..width {
+            if noise(x as f64, y as f64) > threshold {
+                canvas.set(x, y);
+            }
+        }
+    }
+}
+
+/// Sets pixels of the canvas within `width` by `height` pixels to show `noise`, given each
+/// pixel’s coordinates, as shades of grey with ordered dithering.
+pub fn fill_dithered<F: Fn(f64, f64) -> f64>(canvas: &mut Canvas, width: usize, height: usize,
+                                              noise: F) {
+    for y in 0..height {
+        for x in 0..width {
+            if dither::is_set(x, y, noise(x as f64, y as f64)) {
+                canvas.set(x, y);
+            }
+        }
+    }
+}
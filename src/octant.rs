@@ -0,0 +1,165 @@
+//! Terminal graphics using octant characters (experimental)
+//!
+//! This module provides a canvas that draws with the octant characters added in Unicode 16,
+//! giving two by four pixels in each character: the same resolution and pixel layout as the
+//! `braille` module, but with solid pixels that meet their neighbours. Few fonts have these
+//! characters yet, though some terminals draw them themselves; `supported` guesses whether the
+//! current terminal is one of them, so that programs can fall back to Braille otherwise.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::octant::{self, Canvas};
+//!
+//! let mut canvas = Canvas::new(4, 4);
+//! canvas.line(0, 0, 3, 3);
+//! assert_eq!(canvas.rows()[0], "\u{1cd04}\u{1cd80}");
+//! if octant::supported() {
+//!     println!("{}", canvas.frame());
+//! }
+//! ```
+
+use std::char;
+use std::env;
+
+use block::Color;
+use cells::CellCanvas;
+
+/// The arrangements of pixels that already had characters before Unicode 16, and so have no
+/// octant character of their own, as masks with bits for the pixels from left to right and then
+/// top to bottom, from the lowest bit, paired with the character used for them instead.
+static EXISTING_GLYPHS: [(u8, char); 26] = [
+    (0x00, ' '), (0x01, '\u{1cea8}'), (0x02, '\u{1ceab}'), (0x03, '\u{1fb82}'), (0x05, '▘'),
+    (0x0a, '▝'), (0x0f, '▀'), (0x14, '\u{1fbe6}'), (0x28, '\u{1fbe7}'), (0x3f, '\u{1fb85}'),
+    (0x40, '\u{1cea3}'), (0x50, '▖'), (0x55, '▌'), (0x5a, '▞'), (0x5f, '▛'), (0x80, '\u{1cea0}'),
+    (0xa0, '▗'), (0xa5, '▚'), (0xaa, '▐'), (0xaf, '▜'), (0xc0, '▂'), (0xf0, '▄'), (0xf5, '▙'),
+    (0xfa, '▟'), (0xfc, '▆'), (0xff, '█'),
+];
+
+/// Returns the character for an arrangement of pixels in a cell, given as a mask with bits for
+/// the pixels from left to right and then top to bottom, from the lowest bit.
+fn octant(mask: u8) -> char {
+    // The octant characters run in order of their masks, skipping those that already existed.
+    let mut skipped = 0;
+    for &(existing, c) in EXISTING_GLYPHS.iter() {
+        if existing == mask {
+            return c;
+        }
+        if existing < mask {
+            skipped += 1;
+        }
+    }
+    char::from_u32(0x1CD00 + mask as u32 - skipped).unwrap()
+}
+
+/// Guesses whether the terminal can show octant characters.
+///
+/// Setting the `DRAWILLE_OCTANTS` environment variable to `1` or `0` overrides the guess.
+/// Otherwise, octants are assumed to work in terminals known to draw block characters themselves
+/// rather than relying on the font: kitty, WezTerm, foot and Ghostty.
+pub fn supported() -> bool {
+    match env::var("DRAWILLE_OCTANTS").as_ref().map(|s| &s[..]) {
+        Ok("1") => return true,
+        Ok("0") => return false,
+        _ => {},
+    }
+    let term = env::var("TERM").unwrap_or_default();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+    term == "xterm-kitty" || term.starts_with("foot") || term == "xterm-ghostty" ||
+    program == "WezTerm" || program == "ghostty"
+}
+
+/// A canvas object that can be used to draw to the terminal using octant characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    cells: CellCanvas,
+}
+
+impl Canvas {
+    /// Creates a new `Canvas` with the given width and height in pixels.
+    ///
+    /// Note that the `Canvas` can still draw outside the given dimensions (expanding the canvas)
+    /// if a pixel is set outside the dimensions.
+    pub fn new(width: usize, height: usize) -> Canvas {
+        Canvas { cells: CellCanvas::new(width, height, (2, 4)) }
+    }
+
+    /// Returns the width of the canvas in pixels, as given to `new`.
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    /// Returns the height of the canvas in pixels, as given to `new`.
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Returns the width of the canvas in characters, rounded up to cover the whole width.
+    pub fn char_width(&self) -> usize {
+        self.cells.char_width()
+    }
+
+    /// Returns the height of the canvas in characters, rounded up to cover the whole height.
+    pub fn char_height(&self) -> usize {
+        self.cells.char_height()
+    }
+
+    /// Sets whether the canvas is drawn without colours, and returns the canvas for use again.
+    ///
+    /// New canvases are drawn without colours if colour has been turned off with `NO_COLOR`.
+    pub fn monochrome(mut self, monochrome: bool) -> Canvas {
+        self.cells.set_monochrome(monochrome);
+        self
+    }
+
+    /// Clears the canvas.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Sets a pixel at the specified coordinates.
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.cells.set(x, y);
+    }
+
+    /// Sets a pixel at the specified coordinates, and colours the character containing it.
+    ///
+    /// Since a colour applies to a whole character, this also changes the colour of the other
+    /// pixels already set in the same character.
+    pub fn set_colored(&mut self, x: usize, y: usize, color: Color) {
+        self.cells.set_colored(x, y, color);
+    }
+
+    /// Deletes a pixel at the specified coordinates.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        self.cells.unset(x, y);
+    }
+
+    /// Toggles a pixel at the specified coordinates.
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        self.cells.toggle(x, y);
+    }
+
+    /// Detects whether the pixel at the given coordinates is set.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells.get(x, y)
+    }
+
+    /// Returns a `Vec` of each row of the `Canvas`.
+    ///
+    /// Each row is four pixels high. Coloured characters are wrapped in ANSI escape codes,
+    /// unless the canvas is monochrome.
+    pub fn rows(&self) -> Vec<String> {
+        self.cells.rows(octant)
+    }
+
+    /// Draws the canvas to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        self.rows().join("\n")
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)` onto the `Canvas`.
+    pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        self.cells.line(x1, y1, x2, y2);
+    }
+}
@@ -0,0 +1,194 @@
+//! Matching arbitrary colours to the terminal’s palette
+//!
+//! Terminal colour schemes such as Gruvbox and Solarized change what the eight colours of a
+//! `block::Canvas` look like. A `Palette` records what each colour really looks like, so that an
+//! RGB colour can be matched to the one that looks closest, and images can be drawn with it, with
+//! dithering to make up for the palette having so few colours.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::block::{Canvas, Color};
+//! use drawille::palette::{Dithering, Palette, Rgb};
+//!
+//! let palette = Palette::gruvbox();
+//! assert_eq!(palette.map(Rgb(200, 40, 30)), Color::Red);
+//!
+//! // A gradient from black to orange.
+//! let mut canvas = Canvas::new(32, 8);
+//! palette.draw(&mut canvas, 32, 8, Dithering::Ordered, |x, _| Rgb(x as u8 * 8, x as u8 * 4, 0));
+//! println!("{}", canvas.frame());
+//! ```
+
+use std::borrow::Cow;
+
+use block::{self, Color};
+use dither;
+
+/// The default xterm colours, kept in a table of their own so that the xterm palette can be had
+/// for every pixel drawn without building it anew.
+static XTERM: [(Color, Rgb); 8] = [
+    (Color::Black, Rgb(0, 0, 0)), (Color::Red, Rgb(205, 0, 0)),
+    (Color::Green, Rgb(0, 205, 0)), (Color::Yellow, Rgb(205, 205, 0)),
+    (Color::Blue, Rgb(0, 0, 238)), (Color::Magenta, Rgb(205, 0, 205)),
+    (Color::Cyan, Rgb(0, 205, 205)), (Color::White, Rgb(229, 229, 229)),
+];
+
+/// The colours of the dark Gruvbox colour scheme.
+static GRUVBOX: [(Color, Rgb); 8] = [
+    (Color::Black, Rgb(40, 40, 40)), (Color::Red, Rgb(204, 36, 29)),
+    (Color::Green, Rgb(152, 151, 26)), (Color::Yellow, Rgb(215, 153, 33)),
+    (Color::Blue, Rgb(69, 133, 136)), (Color::Magenta, Rgb(177, 98, 134)),
+    (Color::Cyan, Rgb(104, 157, 106)), (Color::White, Rgb(168, 153, 132)),
+];
+
+/// The colours of the Solarized colour scheme.
+static SOLARIZED: [(Color, Rgb); 8] = [
+    (Color::Black, Rgb(7, 54, 66)), (Color::Red, Rgb(220, 50, 47)),
+    (Color::Green, Rgb(133, 153, 0)), (Color::Yellow, Rgb(181, 137, 0)),
+    (Color::Blue, Rgb(38, 139, 210)), (Color::Magenta, Rgb(211, 54, 130)),
+    (Color::Cyan, Rgb(42, 161, 152)), (Color::White, Rgb(238, 232, 213)),
+];
+
+/// A colour given by its red, green and blue components.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Returns the squared distance between two colours, weighting the components roughly by
+    /// how sensitive the eye is to each.
+    fn distance(self, other: Rgb) -> f64 {
+        let (r, g, b) = (self.0 as f64 - other.0 as f64, self.1 as f64 - other.1 as f64,
+                         self.2 as f64 - other.2 as f64);
+        2.0 * r * r + 4.0 * g * g + 3.0 * b * b
+    }
+}
+
+/// How an image is dithered when it’s drawn with a `Palette`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Dithering {
+    /// Each pixel is simply drawn in the closest colour.
+    #[default]
+    None,
+    /// Pixels are nudged lighter or darker following a Bayer matrix, which gives a regular
+    /// pattern that stays put in animations.
+    Ordered,
+    /// The difference between each pixel and the colour it’s drawn in is spread onto the pixels
+    /// after it with Floyd–Steinberg error diffusion, which is more accurate but noisier.
+    ErrorDiffusion,
+}
+
+/// What each of the colours of a `block::Canvas` looks like in a terminal’s colour scheme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: Cow<'static, [(Color, Rgb)]>,
+}
+
+impl Palette {
+    /// Creates a new `Palette` from the colours the terminal shows each `Color` as.
+    ///
+    /// Colours left out of the palette aren’t used; colours given more than once use the first.
+    /// Returns `None` if no colours are given, as there would be nothing to draw with.
+    ///
+    /// ```
+    /// use drawille::block::Color;
+    /// use drawille::palette::{Palette, Rgb};
+    ///
+    /// let palette = Palette::new(&[(Color::Black, Rgb(0, 0, 0)), (Color::Red, Rgb(255, 0, 0))]);
+    /// assert_eq!(palette.unwrap().map(Rgb(200, 20, 10)), Color::Red);
+    /// assert!(Palette::new(&[]).is_none());
+    /// ```
+    pub fn new(colors: &[(Color, Rgb)]) -> Option<Palette> {
+        if colors.is_empty() {
+            return None;
+        }
+        Some(Palette { colors: Cow::Owned(colors.to_vec()) })
+    }
+
+    /// Returns the palette of the default xterm colours, which images are drawn with.
+    ///
+    /// This doesn’t allocate, so it’s cheap enough to call for each pixel.
+    pub fn xterm() -> Palette {
+        Palette { colors: Cow::Borrowed(&XTERM) }
+    }
+
+    /// Returns the palette of the dark Gruvbox colour scheme.
+    pub fn gruvbox() -> Palette {
+        Palette { colors: Cow::Borrowed(&GRUVBOX) }
+    }
+
+    /// Returns the palette of the Solarized colour scheme.
+    pub fn solarized() -> Palette {
+        Palette { colors: Cow::Borrowed(&SOLARIZED) }
+    }
+
+    /// Returns what a `Color` looks like in the palette, or `None` if it isn’t in it.
+    pub fn rgb(&self, color: Color) -> Option<Rgb> {
+        self.colors.iter().find(|&&(c, _)| c == color).map(|&(_, rgb)| rgb)
+    }
+
+    /// Returns the colour in the palette that looks closest to `rgb`.
+    pub fn map(&self, rgb: Rgb) -> Color {
+        self.nearest(rgb).0
+    }
+
+    /// Returns the colour in the palette closest to `rgb`, and what it looks like.
+    fn nearest(&self, rgb: Rgb) -> (Color, Rgb) {
+        *self.colors.iter().min_by(|a, b| {
+            rgb.distance(a.1).partial_cmp(&rgb.distance(b.1)).unwrap()
+        }).expect("palettes always have a colour")
+    }
+
+    /// Draws an image `width` by `height` pixels onto the `Canvas`, with its top left corner
+    /// at the origin, setting each pixel to the palette colour closest to `pixel(x, y)`.
+    pub fn draw<F: Fn(usize, usize) -> Rgb>(&self, canvas: &mut block::Canvas, width: usize,
+                                             height: usize, dithering: Dithering, pixel: F) {
+        // The error carried onto the pixels of this row and the next, for error diffusion.
+        let mut errors = vec![[0.0; 3]; width + 2];
+        let mut next = vec![[0.0; 3]; width + 2];
+        for y in 0..height {
+            for x in 0..width {
+                let Rgb(r, g, b) = pixel(x, y);
+                let mut wanted = [r as f64, g as f64, b as f64];
+                match dithering {
+                    Dithering::None => {},
+                    Dithering::Ordered => {
+                        // Spread pixels over about the gap between neighbouring palette colours.
+                        let nudge = (dither::threshold(x, y) - 0.5) * 96.0;
+                        for channel in wanted.iter_mut() {
+                            *channel += nudge;
+                        }
+                    },
+                    Dithering::ErrorDiffusion => {
+                        for (channel, error) in wanted.iter_mut().zip(errors[x + 1].iter()) {
+                            *channel += error;
+                        }
+                    },
+                }
+                let clamped = |c: f64| c.round().clamp(0.0, 255.0) as u8;
+                let (color, shown) = self.nearest(Rgb(clamped(wanted[0]), clamped(wanted[1]),
+                                                      clamped(wanted[2])));
+                canvas.set(x, y, color);
+
+                if dithering == Dithering::ErrorDiffusion {
+                    let shown = [shown.0 as f64, shown.1 as f64, shown.2 as f64];
+                    for i in 0..3 {
+                        let error = wanted[i] - shown[i];
+                        errors[x + 2][i] += error * 7.0 / 16.0;
+                        next[x][i] += error * 3.0 / 16.0;
+                        next[x + 1][i] += error * 5.0 / 16.0;
+                        next[x + 2][i] += error / 16.0;
+                    }
+                }
+            }
+            errors = next;
+            next = vec![[0.0; 3]; width + 2];
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette::xterm()
+    }
+}
@@ -0,0 +1,153 @@
+//! Particle effects
+//!
+//! A `ParticleSystem` keeps track of many small moving points—sparks, raindrops, snow—each with
+//! a position, a velocity and a lifetime. Stepping the system moves them all, pulls them along
+//! with gravity and forgets the ones that have run out of time; drawing it sets a Braille pixel
+//! for each one.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::particles::{Particle, ParticleSystem};
+//!
+//! // A firework bursting at the middle of the canvas.
+//! let mut sparks = ParticleSystem::new().gravity(0.0, 20.0);
+//! for i in 0..16 {
+//!     let angle = i as f64 * std::f64::consts::PI / 8.0;
+//!     sparks.spawn(Particle::new(40.0, 20.0, angle.cos() * 15.0, angle.sin() * 15.0, 1.0));
+//! }
+//! let mut canvas = Canvas::new(80, 40);
+//! for _ in 0..10 {
+//!     sparks.step(0.05);
+//!     sparks.redraw(&mut canvas);
+//! }
+//! assert_eq!(sparks.len(), 16);
+//! sparks.step(1.0);
+//! sparks.redraw(&mut canvas);
+//! assert!(sparks.is_empty());
+//! assert_eq!(canvas.bounding_box(), None);
+//! ```
+
+use block::Color;
+use braille::Canvas;
+
+/// A single moving point, in pixels and pixels per second.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Particle {
+    pub x: f64,
+    pub y: f64,
+    pub vx: f64,
+    pub vy: f64,
+    /// How many seconds the particle has left before it disappears.
+    pub life: f64,
+    pub color: Option<Color>,
+}
+
+impl Particle {
+    /// Creates a new, uncoloured `Particle` at `(x, y)`, moving at `(vx, vy)` pixels per second,
+    /// that disappears after `life` seconds.
+    pub fn new(x: f64, y: f64, vx: f64, vy: f64, life: f64) -> Particle {
+        Particle { x, y, vx, vy, life, color: None }
+    }
+
+    /// Sets the colour the particle is drawn in, and returns the particle for use again.
+    pub fn color(mut self, color: Color) -> Particle {
+        self.color = Some(color);
+        self
+    }
+
+    /// Returns the pixel the particle is drawn at, or `None` if it’s above or left of the canvas.
+    fn pixel(&self) -> Option<(usize, usize)> {
+        let (x, y) = (self.x.round(), self.y.round());
+        if x >= 0.0 && y >= 0.0 && x.is_finite() && y.is_finite() {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+}
+
+/// A collection of particles moved and drawn together.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    gravity: (f64, f64),
+    /// The pixels set by the last call to `redraw`.
+    drawn: Vec<(usize, usize)>,
+}
+
+impl ParticleSystem {
+    /// Creates a new `ParticleSystem` without any particles or gravity.
+    pub fn new() -> ParticleSystem {
+        ParticleSystem::default()
+    }
+
+    /// Sets the acceleration of every particle, in pixels per second per second, and returns the
+    /// system for use again.
+    ///
+    /// Since the y axis points down the canvas, gravity pulling things down is positive.
+    pub fn gravity(mut self, x: f64, y: f64) -> ParticleSystem {
+        self.gravity = (x, y);
+        self
+    }
+
+    /// Adds a particle to the system.
+    pub fn spawn(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
+
+    /// Returns the particles still alive.
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Returns how many particles are still alive.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Detects whether every particle has disappeared.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Moves every particle on by `dt` seconds, and forgets those whose lifetimes have run out.
+    pub fn step(&mut self, dt: f64) {
+        let gravity = self.gravity;
+        for p in self.particles.iter_mut() {
+            p.vx += gravity.0 * dt;
+            p.vy += gravity.1 * dt;
+            p.x += p.vx * dt;
+            p.y += p.vy * dt;
+            p.life -= dt;
+        }
+        self.particles.retain(|p| p.life > 0.0);
+    }
+
+    /// Sets the pixel under each particle on the canvas, coloured if the particle has a colour.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        for p in self.particles.iter() {
+            if let Some((x, y)) = p.pixel() {
+                match p.color {
+                    Some(color) => canvas.set_colored(x, y, color),
+                    None => canvas.set(x, y),
+                }
+            }
+        }
+    }
+
+    /// Unsets the pixels set by the last call to `redraw`, then draws the particles just like
+    /// `draw`.
+    ///
+    /// This suits a canvas that isn’t cleared between frames: particles leave no trails behind
+    /// them and disappear once they’ve expired. Pixels the particles happened to cross that were
+    /// already set are unset too.
+    pub fn redraw(&mut self, canvas: &mut Canvas) {
+        for &(x, y) in self.drawn.iter() {
+            canvas.unset(x, y);
+        }
+        self.draw(canvas);
+        self.drawn = self.particles.iter().filter_map(Particle::pixel).collect();
+    }
+}
@@ -0,0 +1,174 @@
+//! Building shapes from lines and curves
+//!
+//! A `Path` is made of one or more subpaths, each starting with `move_to` and continuing with
+//! straight lines and quadratic or cubic Bézier curves, optionally closed back to its start. Once
+//! built, a path can be stroked to draw its outline or filled, using the nonzero winding rule, on
+//! a `braille::Canvas` directly or through a `DrawContext`.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::path::Path;
+//!
+//! let mut heart = Path::new();
+//! heart.move_to((20.0, 36.0))
+//!      .cubic_to((0.0, 20.0), (8.0, 0.0), (20.0, 12.0))
+//!      .cubic_to((32.0, 0.0), (40.0, 20.0), (20.0, 36.0))
+//!      .close();
+//! let mut canvas = Canvas::new(40, 40);
+//! heart.fill(&mut canvas);
+//! println!("{}", canvas.frame());
+//! ```
+
+use braille::Canvas;
+use geometry;
+use transform::Transform;
+
+/// The most line segments a single curve is flattened into.
+const MAX_CURVE_SEGMENTS: usize = 256;
+
+/// A part of a path starting with a `move_to`.
+#[derive(Clone, Debug, PartialEq)]
+struct Subpath {
+    /// The points of the subpath, with curves already flattened into lines.
+    points: Vec<(f64, f64)>,
+    closed: bool,
+}
+
+/// A shape built from lines and curves.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    subpaths: Vec<Subpath>,
+}
+
+impl Path {
+    /// Creates a new, empty `Path`.
+    pub fn new() -> Path {
+        Path::default()
+    }
+
+    /// Starts a new subpath at a point.
+    pub fn move_to(&mut self, p: (f64, f64)) -> &mut Path {
+        self.subpaths.push(Subpath { points: vec![p], closed: false });
+        self
+    }
+
+    /// Adds a straight line from the current point to `p`.
+    ///
+    /// If there is no current point, this starts a new subpath at `p` instead.
+    pub fn line_to(&mut self, p: (f64, f64)) -> &mut Path {
+        match self.current() {
+            Some(_) => self.subpaths.last_mut().unwrap().points.push(p),
+            None => { self.move_to(p); },
+        }
+        self
+    }
+
+    /// Adds a quadratic Bézier curve from the current point to `p`, pulled towards `control`.
+    pub fn quad_to(&mut self, control: (f64, f64), p: (f64, f64)) -> &mut Path {
+        let start = self.start_point(control);
+        self.curve(&[start, control, p], |t| {
+            let u = 1.0 - t;
+            [u * u, 2.0 * u * t, t * t]
+        })
+    }
+
+    /// Adds a cubic Bézier curve from the current point to `p`, leaving towards `control1` and
+    /// arriving from `control2`.
+    pub fn cubic_to(&mut self, control1: (f64, f64), control2: (f64, f64),
+                    p: (f64, f64)) -> &mut Path {
+        let start = self.start_point(control1);
+        self.curve(&[start, control1, control2, p], |t| {
+            let u = 1.0 - t;
+            [u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t]
+        })
+    }
+
+    /// Closes the current subpath with a straight line back to its start. The next line or curve
+    /// starts a new subpath from there.
+    pub fn close(&mut self) -> &mut Path {
+        let start = match self.subpaths.last_mut() {
+            Some(subpath) if !subpath.closed => {
+                subpath.closed = true;
+                subpath.points[0]
+            },
+            _ => return self,
+        };
+        self.move_to(start);
+        self
+    }
+
+    /// Returns the point the next line or curve starts from, if any.
+    pub fn current(&self) -> Option<(f64, f64)> {
+        self.subpaths.last().and_then(|subpath| subpath.points.last().cloned())
+    }
+
+    /// Returns a copy of the path with every point transformed.
+    pub fn transformed(&self, transform: &Transform) -> Path {
+        Path {
+            subpaths: self.subpaths.iter().map(|subpath| Subpath {
+                points: subpath.points.iter().map(|&p| transform.apply(p)).collect(),
+                closed: subpath.closed,
+            }).collect(),
+        }
+    }
+
+    /// Returns the points of each subpath with more than one point, with curves flattened into
+    /// lines and closed subpaths ending back at their start.
+    pub fn flatten(&self) -> Vec<Vec<(f64, f64)>> {
+        self.subpaths.iter().filter(|s| s.points.len() > 1).map(|subpath| {
+            let mut points = subpath.points.clone();
+            if subpath.closed {
+                points.push(points[0]);
+            }
+            points
+        }).collect()
+    }
+
+    /// Draws the outline of the path onto the `Canvas`.
+    pub fn stroke(&self, canvas: &mut Canvas) {
+        for points in self.flatten() {
+            for pair in points.windows(2) {
+                geometry::segment(pair[0], pair[1], geometry::BOUNDS, |x, y| canvas.set(x, y));
+            }
+        }
+    }
+
+    /// Fills the inside of the path on the `Canvas`, treating every subpath as closed.
+    pub fn fill(&self, canvas: &mut Canvas) {
+        geometry::fill(&self.flatten(), geometry::BOUNDS, |x, y| canvas.set(x, y));
+    }
+
+    /// Returns the current point, first starting a subpath at `fallback` if there isn’t one.
+    fn start_point(&mut self, fallback: (f64, f64)) -> (f64, f64) {
+        match self.current() {
+            Some(p) => p,
+            None => {
+                self.move_to(fallback);
+                fallback
+            },
+        }
+    }
+
+    /// Adds a Bézier curve with the given control points, flattened into lines, where `weights`
+    /// gives the weight of each control point at each point along the curve.
+    fn curve<F, W>(&mut self, controls: &[(f64, f64)], weights: F) -> &mut Path
+        where F: Fn(f64) -> W, W: AsRef<[f64]>
+    {
+        let length = controls.windows(2).map(|pair| {
+            ((pair[1].0 - pair[0].0).powi(2) + (pair[1].1 - pair[0].1).powi(2)).sqrt()
+        }).sum::<f64>();
+        let segments = ((length / 2.0).ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS);
+        let points = &mut self.subpaths.last_mut().unwrap().points;
+        for i in 1..segments + 1 {
+            let weights = weights(i as f64 / segments as f64);
+            let weights = weights.as_ref();
+            let (x, y) = controls.iter().zip(weights).fold((0.0, 0.0), |(x, y), (p, w)| {
+                (x + p.0 * w, y + p.1 * w)
+            });
+            points.push((x, y));
+        }
+        self
+    }
+}
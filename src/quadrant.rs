@@ -0,0 +1,122 @@
+//! Terminal graphics using quadrant block characters
+//!
+//! This module provides a canvas that draws with the quadrant block characters (`▘`, `▞`, `▙`,
+//! `█` and so on), giving two by two pixels in each character. That’s half the resolution of
+//! Braille, but the pixels are solid and fill the character, so shapes read better, and colours
+//! are much clearer: a colour applies to a whole character at a time, as with Braille, but it
+//! covers far fewer pixels.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::block::Color;
+//! use drawille::quadrant::Canvas;
+//!
+//! let mut canvas = Canvas::new(8, 4).monochrome(false);
+//! canvas.line(0, 0, 7, 3);
+//! canvas.set_colored(7, 0, Color::Red);
+//! assert_eq!(canvas.rows()[0], "▀▚▖\u{1b}[31m▝\u{1b}[0m");
+//! ```
+
+use block::Color;
+use cells::CellCanvas;
+
+/// The characters for each arrangement of pixels in a cell, indexed by a mask with bits for the
+/// top left, top right, bottom left and bottom right pixels, from the lowest bit.
+static QUADRANTS: [char; 16] = [' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛',
+                                '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█'];
+
+/// A canvas object that can be used to draw to the terminal using quadrant block characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    cells: CellCanvas,
+}
+
+impl Canvas {
+    /// Creates a new `Canvas` with the given width and height in pixels.
+    ///
+    /// Note that the `Canvas` can still draw outside the given dimensions (expanding the canvas)
+    /// if a pixel is set outside the dimensions.
+    pub fn new(width: usize, height: usize) -> Canvas {
+        Canvas { cells: CellCanvas::new(width, height, (2, 2)) }
+    }
+
+    /// Returns the width of the canvas in pixels, as given to `new`.
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    /// Returns the height of the canvas in pixels, as given to `new`.
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Returns the width of the canvas in characters, rounded up to cover the whole width.
+    pub fn char_width(&self) -> usize {
+        self.cells.char_width()
+    }
+
+    /// Returns the height of the canvas in characters, rounded up to cover the whole height.
+    pub fn char_height(&self) -> usize {
+        self.cells.char_height()
+    }
+
+    /// Sets whether the canvas is drawn without colours, and returns the canvas for use again.
+    ///
+    /// New canvases are drawn without colours if colour has been turned off with `NO_COLOR`.
+    pub fn monochrome(mut self, monochrome: bool) -> Canvas {
+        self.cells.set_monochrome(monochrome);
+        self
+    }
+
+    /// Clears the canvas.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Sets a pixel at the specified coordinates.
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.cells.set(x, y);
+    }
+
+    /// Sets a pixel at the specified coordinates, and colours the character containing it.
+    ///
+    /// Since a colour applies to a whole character, this also changes the colour of the other
+    /// pixels already set in the same character.
+    pub fn set_colored(&mut self, x: usize, y: usize, color: Color) {
+        self.cells.set_colored(x, y, color);
+    }
+
+    /// Deletes a pixel at the specified coordinates.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        self.cells.unset(x, y);
+    }
+
+    /// Toggles a pixel at the specified coordinates.
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        self.cells.toggle(x, y);
+    }
+
+    /// Detects whether the pixel at the given coordinates is set.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells.get(x, y)
+    }
+
+    /// Returns a `Vec` of each row of the `Canvas`.
+    ///
+    /// Each row is two pixels high. Coloured characters are wrapped in ANSI escape codes,
+    /// unless the canvas is monochrome.
+    pub fn rows(&self) -> Vec<String> {
+        self.cells.rows(|mask| QUADRANTS[mask as usize])
+    }
+
+    /// Draws the canvas to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        self.rows().join("\n")
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)` onto the `Canvas`.
+    pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        self.cells.line(x1, y1, x2, y2);
+    }
+}
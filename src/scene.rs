@@ -0,0 +1,266 @@
+//! Retained-mode drawing with a scene of shapes
+//!
+//! A `Scene` keeps the shapes to be drawn, each with its own transform, colour and visibility, and
+//! draws them all onto a canvas whenever asked. Moving a shape between frames is then just a
+//! matter of changing its transform and drawing the scene again.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::scene::{Scene, Shape};
+//! use drawille::transform::Transform;
+//!
+//! let mut scene = Scene::new();
+//! let square = scene.add(Shape::Polygon(vec![(-4.0, -4.0), (4.0, -4.0), (4.0, 4.0), (-4.0, 4.0)]));
+//! scene.add(Shape::Circle { center: (20.0, 8.0), radius: 6.0 });
+//! for angle in 0..3 {
+//!     scene.node_mut(square).unwrap().transform =
+//!         Transform::rotation(angle as f64 * 30.0).then(&Transform::translation(8.0, 8.0));
+//!     let mut canvas = Canvas::new(32, 16);
+//!     scene.draw(&mut canvas);
+//!     println!("{}", canvas.frame());
+//! }
+//! ```
+
+use block::Color;
+use braille::Canvas;
+use geometry;
+use sprite::Sprite;
+use transform::Transform;
+
+/// Something that can be drawn in a `Scene`, in coordinates before its node’s transform.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    /// A line between two points.
+    Line((f64, f64), (f64, f64)),
+    /// Lines joining a sequence of points.
+    Polyline(Vec<(f64, f64)>),
+    /// The outline of a polygon with the given corners.
+    Polygon(Vec<(f64, f64)>),
+    /// The outline of a circle, which transforms into an ellipse if stretched.
+    Circle { center: (f64, f64), radius: f64 },
+    /// Text written from the character containing a point. Only the point is transformed.
+    Text { position: (f64, f64), text: String },
+    /// A sprite drawn with its top left corner at a point. Only the point is transformed.
+    Sprite { position: (f64, f64), sprite: Sprite },
+}
+
+/// A shape in a `Scene`, along with how it’s drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub shape: Shape,
+    /// The transform applied to the shape when it’s drawn.
+    pub transform: Transform,
+    /// Whether the shape is drawn at all.
+    pub visible: bool,
+    /// The colour of the shape, if any.
+    pub color: Option<Color>,
+}
+
+impl Node {
+    /// Creates a new `Node` that draws a shape as it is, visible and uncoloured.
+    pub fn new(shape: Shape) -> Node {
+        Node { shape, transform: Transform::identity(), visible: true, color: None }
+    }
+
+    /// Returns the points joined by lines to draw the node, after applying `transform` to them,
+    /// or `None` if it isn’t drawn with lines.
+    fn points(&self, transform: &Transform) -> Option<Vec<(f64, f64)>> {
+        let points = match self.shape {
+            Shape::Line(a, b) => vec![a, b],
+            Shape::Polyline(ref points) => points.clone(),
+            Shape::Polygon(ref points) => {
+                points.iter().chain(points.first()).cloned().collect()
+            },
+            Shape::Circle { center, radius } => {
+                geometry::circle_points(center, radius, transform.scale_factor())
+            },
+            Shape::Text { .. } | Shape::Sprite { .. } => return None,
+        };
+        Some(points.into_iter().map(|p| transform.apply(p)).collect())
+    }
+
+    /// Detects whether a point lies on the node, after first applying `parent` to it: within
+    /// `tolerance` pixels of its lines, inside a polygon or circle, or on the characters of text
+    /// or the area of a sprite.
+    fn hit(&self, (x, y): (f64, f64), parent: &Transform, tolerance: f64) -> bool {
+        let transform = self.transform.then(parent);
+        let (left, top, right, bottom) = match self.shape {
+            Shape::Text { position, ref text } => {
+                // The text starts at the character containing the point it’s written from.
+                let (px, py) = transform.apply(position);
+                let left = (px.round() / 2.0).floor() * 2.0;
+                let top = (py.round() / 4.0).floor() * 4.0;
+                (left, top, left + text.chars().count() as f64 * 2.0, top + 4.0)
+            },
+            Shape::Sprite { position, ref sprite } => {
+                let (left, top) = transform.apply(position);
+                let (left, top) = (left.round(), top.round());
+                (left, top, left + sprite.width() as f64, top + sprite.height() as f64)
+            },
+            _ => {
+                let points = self.points(&transform).unwrap_or_default();
+                let filled = match self.shape {
+                    Shape::Polygon(_) | Shape::Circle { .. } => geometry::contains(&points, (x, y)),
+                    _ => false,
+                };
+                let near = |a, b| geometry::distance_to_segment((x, y), a, b) <= tolerance;
+                return filled || points.first().is_some_and(|&p| near(p, p))
+                    || points.windows(2).any(|pair| near(pair[0], pair[1]));
+            },
+        };
+        x >= left - tolerance && x < right + tolerance && y >= top - tolerance
+            && y < bottom + tolerance
+    }
+
+    /// Draws the node onto the `Canvas`, after first applying `parent` to it.
+    fn draw(&self, canvas: &mut Canvas, parent: &Transform) {
+        let transform = self.transform.then(parent);
+        let points = match self.shape {
+            Shape::Text { position, ref text } => {
+                let (x, y) = transform.apply(position);
+                if x >= 0.0 && y >= 0.0 && x <= geometry::LIMIT && y <= geometry::LIMIT {
+                    canvas.text(x.round() as usize, y.round() as usize, text);
+                }
+                return;
+            },
+            Shape::Sprite { position, ref sprite } => {
+                let (x, y) = transform.apply(position);
+                if x >= -geometry::LIMIT && y >= -geometry::LIMIT
+                    && x <= geometry::LIMIT && y <= geometry::LIMIT {
+                    sprite.draw_at(canvas, x.round() as isize, y.round() as isize);
+                }
+                return;
+            },
+            _ => self.points(&transform).unwrap_or_default(),
+        };
+        let color = self.color;
+        let mut plot = |x, y| match color {
+            Some(color) => canvas.set_colored(x, y, color),
+            None => canvas.set(x, y),
+        };
+        if points.len() == 1 {
+            geometry::segment(points[0], points[0], geometry::BOUNDS, &mut plot);
+        }
+        for pair in points.windows(2) {
+            geometry::segment(pair[0], pair[1], geometry::BOUNDS, &mut plot);
+        }
+    }
+}
+
+/// Identifies a node added to a `Scene`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+/// A collection of shapes which can be drawn onto a canvas as often as needed.
+///
+/// Nodes are drawn in the order they were added, so later nodes’ colours win where they overlap
+/// earlier ones.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scene {
+    /// The transform applied to every node after its own, like a camera.
+    pub transform: Transform,
+    /// The nodes, in the order they were added, and so sorted by id.
+    nodes: Vec<(NodeId, Node)>,
+    next: usize,
+}
+
+impl Scene {
+    /// Creates a new, empty `Scene`.
+    pub fn new() -> Scene {
+        Scene::default()
+    }
+
+    /// Adds a visible, uncoloured shape to the scene, returning the id of its node.
+    pub fn add(&mut self, shape: Shape) -> NodeId {
+        self.add_node(Node::new(shape))
+    }
+
+    /// Adds a node to the scene, returning its id.
+    pub fn add_node(&mut self, node: Node) -> NodeId {
+        let id = NodeId(self.next);
+        self.next += 1;
+        self.nodes.push((id, node));
+        id
+    }
+
+    /// Returns the node with the given id, if it’s still in the scene.
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.index(id).map(|i| &self.nodes[i].1)
+    }
+
+    /// Returns the node with the given id to be changed, if it’s still in the scene.
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.index(id).map(move |i| &mut self.nodes[i].1)
+    }
+
+    /// Removes the node with the given id from the scene, returning it.
+    pub fn remove(&mut self, id: NodeId) -> Option<Node> {
+        self.index(id).map(|i| self.nodes.remove(i).1)
+    }
+
+    /// Returns the number of nodes in the scene.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Detects whether the scene has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Removes every node from the scene.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Draws the visible nodes onto the `Canvas`.
+    ///
+    /// Anything transformed above or left of the canvas is left out.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        for (_, node) in self.nodes.iter().filter(|n| n.1.visible) {
+            node.draw(canvas, &self.transform);
+        }
+    }
+
+    /// Returns the topmost visible node under a point on the canvas, such as the pixel under the
+    /// mouse, counting points within `tolerance` pixels of a node’s lines as on it.
+    ///
+    /// Polygons and circles are hit anywhere inside them, text anywhere on its characters, and
+    /// sprites anywhere within their width and height, transparent or not.
+    ///
+    /// ```
+    /// use drawille::scene::{Scene, Shape};
+    ///
+    /// let mut scene = Scene::new();
+    /// let line = scene.add(Shape::Line((0.0, 0.0), (20.0, 0.0)));
+    /// let circle = scene.add(Shape::Circle { center: (10.0, 10.0), radius: 5.0 });
+    /// assert_eq!(scene.hit_test((10.0, 1.0), 1.5), Some(line));
+    /// assert_eq!(scene.hit_test((11.0, 9.0), 1.5), Some(circle));
+    /// assert_eq!(scene.hit_test((30.0, 30.0), 1.5), None);
+    /// ```
+    pub fn hit_test(&self, point: (f64, f64), tolerance: f64) -> Option<NodeId> {
+        self.hits(point, tolerance).into_iter().next()
+    }
+
+    /// Returns every visible node under a point on the canvas, just as `hit_test` finds them,
+    /// from the topmost down.
+    pub fn hits(&self, point: (f64, f64), tolerance: f64) -> Vec<NodeId> {
+        self.nodes.iter().rev()
+            .filter(|(_, node)| node.visible && node.hit(point, &self.transform, tolerance))
+            .map(|&(id, _)| id)
+            .collect()
+    }
+
+    /// Draws the scene onto a new `Canvas` of the given size in pixels, and returns its frame.
+    pub fn frame(&self, width: usize, height: usize) -> String {
+        let mut canvas = Canvas::new(width, height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+
+    fn index(&self, id: NodeId) -> Option<usize> {
+        self.nodes.binary_search_by_key(&id, |n| n.0).ok()
+    }
+}
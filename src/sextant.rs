@@ -0,0 +1,134 @@
+//! Terminal graphics using sextant characters
+//!
+//! This module provides a canvas that draws with the sextant characters from Unicode 13’s
+//! Symbols for Legacy Computing block, giving two by three pixels in each character. With a font
+//! that supports them, the pixels are solid blocks that meet their neighbours, so lines and
+//! filled shapes look more even than with Braille’s separated dots, at three quarters of the
+//! resolution.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::sextant::Canvas;
+//!
+//! let mut canvas = Canvas::new(6, 3);
+//! canvas.line(0, 0, 5, 2);
+//! assert_eq!(canvas.rows()[0], "\u{1fb02}\u{1fb08}\u{1fb22}");
+//! ```
+
+use std::char;
+
+use block::Color;
+use cells::CellCanvas;
+
+/// Returns the character for an arrangement of pixels in a cell, given as a mask with bits for
+/// the pixels from left to right and then top to bottom, from the lowest bit.
+fn sextant(mask: u8) -> char {
+    match mask {
+        0 => ' ',
+        // The left and right halves and the full block were already in Unicode, so the sextant
+        // characters skip them.
+        0b01_0101 => '▌',
+        0b10_1010 => '▐',
+        0b11_1111 => '█',
+        _ => {
+            let skipped = (mask > 0b01_0101) as u32 + (mask > 0b10_1010) as u32;
+            char::from_u32(0x1FB00 + mask as u32 - 1 - skipped).unwrap()
+        },
+    }
+}
+
+/// A canvas object that can be used to draw to the terminal using sextant characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    cells: CellCanvas,
+}
+
+impl Canvas {
+    /// Creates a new `Canvas` with the given width and height in pixels.
+    ///
+    /// Note that the `Canvas` can still draw outside the given dimensions (expanding the canvas)
+    /// if a pixel is set outside the dimensions.
+    pub fn new(width: usize, height: usize) -> Canvas {
+        Canvas { cells: CellCanvas::new(width, height, (2, 3)) }
+    }
+
+    /// Returns the width of the canvas in pixels, as given to `new`.
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    /// Returns the height of the canvas in pixels, as given to `new`.
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Returns the width of the canvas in characters, rounded up to cover the whole width.
+    pub fn char_width(&self) -> usize {
+        self.cells.char_width()
+    }
+
+    /// Returns the height of the canvas in characters, rounded up to cover the whole height.
+    pub fn char_height(&self) -> usize {
+        self.cells.char_height()
+    }
+
+    /// Sets whether the canvas is drawn without colours, and returns the canvas for use again.
+    ///
+    /// New canvases are drawn without colours if colour has been turned off with `NO_COLOR`.
+    pub fn monochrome(mut self, monochrome: bool) -> Canvas {
+        self.cells.set_monochrome(monochrome);
+        self
+    }
+
+    /// Clears the canvas.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Sets a pixel at the specified coordinates.
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.cells.set(x, y);
+    }
+
+    /// Sets a pixel at the specified coordinates, and colours the character containing it.
+    ///
+    /// Since a colour applies to a whole character, this also changes the colour of the other
+    /// pixels already set in the same character.
+    pub fn set_colored(&mut self, x: usize, y: usize, color: Color) {
+        self.cells.set_colored(x, y, color);
+    }
+
+    /// Deletes a pixel at the specified coordinates.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        self.cells.unset(x, y);
+    }
+
+    /// Toggles a pixel at the specified coordinates.
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        self.cells.toggle(x, y);
+    }
+
+    /// Detects whether the pixel at the given coordinates is set.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells.get(x, y)
+    }
+
+    /// Returns a `Vec` of each row of the `Canvas`.
+    ///
+    /// Each row is three pixels high. Coloured characters are wrapped in ANSI escape codes,
+    /// unless the canvas is monochrome.
+    pub fn rows(&self) -> Vec<String> {
+        self.cells.rows(sextant)
+    }
+
+    /// Draws the canvas to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        self.rows().join("\n")
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)` onto the `Canvas`.
+    pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        self.cells.line(x1, y1, x2, y2);
+    }
+}
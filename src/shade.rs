@@ -0,0 +1,167 @@
+//! Terminal graphics using shade characters
+//!
+//! This module provides a grayscale canvas: each character holds an intensity rather than a set of
+//! pixels, shown with the shade characters `░▒▓█` or another ramp of characters from light to
+//! dark. Drawing adds to the intensities already there, so overlapping points and lines build up
+//! into heat or density maps without needing colour.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::shade::Canvas;
+//!
+//! let mut canvas = Canvas::new(4, 1);
+//! canvas.line(0, 0, 3, 0, 1.0);
+//! canvas.line(2, 0, 3, 0, 1.0);
+//! canvas.add(3, 0, 2.0);
+//! assert_eq!(canvas.frame(), "░░▒█");
+//! ```
+
+use std::cmp;
+use std::collections::HashMap;
+
+/// The ramp used by a new `Canvas`, from no intensity to full intensity.
+static SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// A canvas object that can be used to draw to the terminal using shade characters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Canvas {
+    cells: HashMap<(usize, usize), f64>,
+    ramp: Vec<char>,
+    scale: Option<f64>,
+    width:  usize,
+    height: usize,
+}
+
+impl Canvas {
+    /// Creates a new `Canvas` with the given width and height in characters.
+    ///
+    /// Note that the `Canvas` can still draw outside the given dimensions (expanding the canvas)
+    /// if a character is drawn outside the dimensions.
+    pub fn new(width: usize, height: usize) -> Canvas {
+        Canvas {
+            cells: HashMap::new(),
+            ramp: SHADES.to_vec(),
+            scale: None,
+            width,
+            height,
+        }
+    }
+
+    /// Sets the characters used to show intensities, from none to full, and returns the canvas for
+    /// use again.
+    ///
+    /// The first character is used only where nothing has been drawn. Ramps with fewer than two
+    /// characters are ignored.
+    pub fn ramp<S: AsRef<str>>(mut self, ramp: S) -> Canvas {
+        let ramp = ramp.as_ref().chars().collect::<Vec<_>>();
+        if ramp.len() >= 2 {
+            self.ramp = ramp;
+        }
+        self
+    }
+
+    /// Sets the intensity shown at full strength, and returns the canvas for use again.
+    ///
+    /// Without one, the canvas scales its intensities so that the most intense character is shown
+    /// at full strength.
+    pub fn scale(mut self, full: f64) -> Canvas {
+        self.scale = Some(full);
+        self
+    }
+
+    /// Returns the width of the canvas in characters, as given to `new`.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas in characters, as given to `new`.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Clears the canvas.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Sets the intensity of the character at the specified coordinates.
+    pub fn set(&mut self, x: usize, y: usize, intensity: f64) {
+        self.cells.insert((x, y), intensity);
+    }
+
+    /// Adds to the intensity of the character at the specified coordinates.
+    pub fn add(&mut self, x: usize, y: usize, amount: f64) {
+        *self.cells.entry((x, y)).or_insert(0.0) += amount;
+    }
+
+    /// Returns the intensity of the character at the specified coordinates.
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        *self.cells.get(&(x, y)).unwrap_or(&0.0)
+    }
+
+    /// Adds `amount` of intensity at a point between characters, sharing it between the four
+    /// characters around the point in proportion to how close it is to each.
+    ///
+    /// Points outside the top or left of the canvas are ignored.
+    pub fn add_point(&mut self, x: f64, y: f64, amount: f64) {
+        if !x.is_finite() || !y.is_finite() || x < 0.0 || y < 0.0 {
+            return;
+        }
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as usize, y0 as usize);
+        for &(dx, dy, weight) in [(0, 0, (1.0 - fx) * (1.0 - fy)), (1, 0, fx * (1.0 - fy)),
+                                  (0, 1, (1.0 - fx) * fy), (1, 1, fx * fy)].iter() {
+            if weight > 0.0 {
+                self.add(x0 + dx, y0 + dy, amount * weight);
+            }
+        }
+    }
+
+    /// Adds `amount` of intensity to each character along a line from `(x1, y1)` to `(x2, y2)`.
+    pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, amount: f64) {
+        let xdiff = cmp::max(x1, x2) - cmp::min(x1, x2);
+        let ydiff = cmp::max(y1, y2) - cmp::min(y1, y2);
+        let r = cmp::max(xdiff, ydiff);
+        for i in 0..r + 1 {
+            let step = |from: usize, to: usize, diff: usize| {
+                if diff == 0 {
+                    from
+                } else if from <= to {
+                    from + i * diff / r
+                } else {
+                    from - i * diff / r
+                }
+            };
+            self.add(step(x1, x2, xdiff), step(y1, y2, ydiff), amount);
+        }
+    }
+
+    /// Returns a `Vec` of each row of the `Canvas`.
+    pub fn rows(&self) -> Vec<String> {
+        let columns = self.cells.keys().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let rows = self.cells.keys().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        let (columns, rows) = (cmp::max(self.width, columns), cmp::max(self.height, rows));
+        let full = self.scale.unwrap_or_else(|| self.cells.values().cloned().fold(0.0, f64::max));
+        let levels = self.ramp.len() - 1;
+
+        let mut result = vec![];
+        for y in 0..rows {
+            let mut row = String::new();
+            for x in 0..columns {
+                let t = self.get(x, y) / full;
+                // Rounding up means anything drawn at all shows at least the lightest shade.
+                let level = if t > 0.0 { (t.min(1.0) * levels as f64).ceil() as usize } else { 0 };
+                row.push(self.ramp[level]);
+            }
+            result.push(row);
+        }
+        result
+    }
+
+    /// Draws the canvas to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        self.rows().join("\n")
+    }
+}
@@ -0,0 +1,99 @@
+//! Encoding pixels as sixel graphics, for terminals that can show true raster images.
+//!
+//! Sixel draws an image in bands six pixels high, with each column of a band sent as a single
+//! character for each colour used in it.
+
+use block::Color;
+
+/// The red, green and blue of each `Color`, as percentages.
+fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => (0, 0, 0),
+        Color::Red => (80, 0, 0),
+        Color::Green => (0, 80, 0),
+        Color::Yellow => (80, 80, 0),
+        Color::Blue => (0, 0, 93),
+        Color::Magenta => (80, 0, 80),
+        Color::Cyan => (0, 80, 80),
+        Color::White => (90, 90, 90),
+    }
+}
+
+static COLORS: [Color; 8] = [Color::Black, Color::Red, Color::Green, Color::Yellow,
+                             Color::Blue, Color::Magenta, Color::Cyan, Color::White];
+
+/// Encodes an image `width` by `height` pixels as a sixel escape sequence, drawing each pixel as a
+/// square `scale` pixels across.
+///
+/// `pixel` gives the colour of each pixel, or `None` to leave it transparent.
+pub fn encode<F: Fn(usize, usize) -> Option<Color>>(width: usize, height: usize, scale: usize,
+                                                    pixel: F) -> String {
+    let scale = scale.max(1);
+    let (out_width, out_height) = (width * scale, height * scale);
+    // Background pixels are left alone rather than painted with colour zero.
+    let mut result = format!("\x1bP0;1;0q\"1;1;{};{}", out_width, out_height);
+    for (i, &color) in COLORS.iter().enumerate() {
+        let (r, g, b) = rgb(color);
+        result.push_str(&format!("#{};2;{};{};{}", i, r, g, b));
+    }
+
+    let mut band = vec![[0u8; 8]; width];
+    for top in (0..out_height).step_by(6) {
+        for column in band.iter_mut() {
+            *column = [0; 8];
+        }
+        for row in top..(top + 6).min(out_height) {
+            for (x, column) in band.iter_mut().enumerate() {
+                if let Some(color) = pixel(x, row / scale) {
+                    column[color as usize] |= 1 << (row - top);
+                }
+            }
+        }
+
+        let mut first = true;
+        for i in 0..COLORS.len() {
+            if band.iter().all(|column| column[i] == 0) {
+                continue;
+            }
+            if !first {
+                result.push('$');
+            }
+            first = false;
+            result.push_str(&format!("#{}", i));
+            let sixels = band.iter().flat_map(|column| {
+                let c = (0x3f + column[i]) as char;
+                (0..scale).map(move |_| c)
+            });
+            push_runs(&mut result, sixels);
+        }
+        result.push('-');
+    }
+    result.push_str("\x1b\\");
+    result
+}
+
+/// Appends sixel characters to `result`, with repeated characters run-length encoded.
+fn push_runs<I: Iterator<Item = char>>(result: &mut String, sixels: I) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |result: &mut String, (c, count): (char, usize)| {
+        if count > 3 {
+            result.push_str(&format!("!{}{}", count, c));
+        } else {
+            result.extend((0..count).map(|_| c));
+        }
+    };
+    for c in sixels {
+        run = match run {
+            Some((last, count)) if last == c => Some((c, count + 1)),
+            Some(last) => {
+                flush(result, last);
+                Some((c, 1))
+            },
+            None => Some((c, 1)),
+        };
+    }
+    // Empty sixels at the end of a line don’t need sending.
+    if let Some(last) = run.filter(|&(c, _)| c != '?') {
+        flush(result, last);
+    }
+}
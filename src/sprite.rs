@@ -0,0 +1,400 @@
+//! Small reusable pictures stamped onto a canvas
+//!
+//! A `Sprite` is a little bitmap in which each pixel is set, cleared or transparent. Drawing it
+//! onto a `braille::Canvas` sets and clears the canvas’s pixels under it while leaving those under
+//! transparent pixels alone, so sprites can move over a background without wiping it out.
+//!
+//! A sprite sheet holding several frames side by side can be cut up with `Sprite::slice`, and the
+//! frames played back with an `AnimatedSprite`.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::sprite::Sprite;
+//!
+//! let invader = Sprite::from_rows(&[
+//!     "  #  ",
+//!     " ### ",
+//!     "## ##",
+//!     "# . #",
+//! ]);
+//! let mut canvas = Canvas::new(20, 8);
+//! canvas.line(0, 3, 19, 3);
+//! invader.draw_at(&mut canvas, 2, 0);
+//! invader.flipped_vertically().draw_at(&mut canvas, 10, 0);
+//! assert!(!canvas.get(4, 3));
+//! assert!(canvas.get(5, 3) && canvas.get(3, 3));
+//! ```
+
+use std::time::Duration;
+
+use block::Color;
+use braille::Canvas;
+
+/// A pixel of a `Sprite`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Pixel {
+    Transparent,
+    Set,
+    Clear,
+}
+
+/// A small picture that can be drawn onto a canvas many times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sprite {
+    width: usize,
+    height: usize,
+    pixels: Vec<Pixel>,
+    color: Option<Color>,
+}
+
+impl Sprite {
+    /// Creates a new, entirely transparent `Sprite` of the given size in pixels.
+    pub fn new(width: usize, height: usize) -> Sprite {
+        Sprite { width, height, pixels: vec![Pixel::Transparent; width * height], color: None }
+    }
+
+    /// Creates a new `Sprite` from rows of text, one character per pixel, where a space is
+    /// transparent, `.` clears the pixel under it and any other character sets it.
+    ///
+    /// The sprite is as wide as the longest row, with shorter rows padded with transparency.
+    pub fn from_rows<S: AsRef<str>>(rows: &[S]) -> Sprite {
+        let width = rows.iter().map(|row| row.as_ref().chars().count()).max().unwrap_or(0);
+        let mut sprite = Sprite::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.as_ref().chars().enumerate() {
+                match c {
+                    ' ' => {},
+                    '.' => sprite.clear(x, y),
+                    _ => sprite.set(x, y),
+                }
+            }
+        }
+        sprite
+    }
+
+    /// Creates a new `Sprite` from part of a canvas, `width` by `height` pixels from `(x, y)`,
+    /// with the canvas’s set pixels set and the rest transparent.
+    pub fn from_canvas(canvas: &Canvas, x: usize, y: usize, width: usize, height: usize) -> Sprite {
+        let mut sprite = Sprite::new(width, height);
+        for sy in 0..height {
+            for sx in 0..width {
+                if canvas.get(x + sx, y + sy) {
+                    sprite.set(sx, sy);
+                }
+            }
+        }
+        sprite
+    }
+
+    /// Sets the colour the sprite’s set pixels are drawn in, and returns the sprite for use again.
+    pub fn color(mut self, color: Color) -> Sprite {
+        self.color = Some(color);
+        self
+    }
+
+    /// Returns the width of the sprite in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the sprite in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Makes the pixel at the specified coordinates set when drawn.
+    ///
+    /// Pixels outside the sprite are ignored.
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.put(x, y, Pixel::Set);
+    }
+
+    /// Makes the pixel at the specified coordinates clear the pixel under it when drawn.
+    pub fn clear(&mut self, x: usize, y: usize) {
+        self.put(x, y, Pixel::Clear);
+    }
+
+    /// Makes the pixel at the specified coordinates transparent.
+    pub fn make_transparent(&mut self, x: usize, y: usize) {
+        self.put(x, y, Pixel::Transparent);
+    }
+
+    /// Detects whether the pixel at the given coordinates is set.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.pixel(x, y) == Pixel::Set
+    }
+
+    /// Detects whether the pixel at the given coordinates is transparent, as all pixels outside
+    /// the sprite are.
+    pub fn is_transparent(&self, x: usize, y: usize) -> bool {
+        self.pixel(x, y) == Pixel::Transparent
+    }
+
+    /// Returns the smallest box containing every set pixel, as the coordinates of its top left
+    /// and bottom right pixels, or `None` if no pixels are set.
+    pub fn bounding_box(&self) -> Option<((usize, usize), (usize, usize))> {
+        let mut set = (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y)))
+                                      .filter(|&(x, y)| self.get(x, y));
+        let first = set.next()?;
+        Some(set.fold((first, first), |(min, max), (x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        }))
+    }
+
+    /// Detects whether the bounding boxes of this sprite at `(x, y)` and `other` at
+    /// `(other_x, other_y)` overlap, which is quicker to check than whether the sprites do.
+    pub fn boxes_overlap(&self, x: isize, y: isize, other: &Sprite, other_x: isize,
+                         other_y: isize) -> bool {
+        match (self.bounding_box(), other.bounding_box()) {
+            (Some((min1, max1)), Some((min2, max2))) => {
+                let (left1, top1) = (x + min1.0 as isize, y + min1.1 as isize);
+                let (right1, bottom1) = (x + max1.0 as isize, y + max1.1 as isize);
+                let (left2, top2) = (other_x + min2.0 as isize, other_y + min2.1 as isize);
+                let (right2, bottom2) = (other_x + max2.0 as isize, other_y + max2.1 as isize);
+                left1 <= right2 && left2 <= right1 && top1 <= bottom2 && top2 <= bottom1
+            },
+            _ => false,
+        }
+    }
+
+    /// Detects whether any set pixel of this sprite overlaps a set pixel of `other`, when `other`
+    /// is moved `dx` pixels right and `dy` pixels down from this sprite.
+    ///
+    /// ```
+    /// use drawille::sprite::Sprite;
+    ///
+    /// let ship = Sprite::from_rows(&[" # ", "###"]);
+    /// let bullet = Sprite::from_rows(&["#"]);
+    /// assert!(ship.collides_with(&bullet, 1, 0));
+    /// assert!(!ship.collides_with(&bullet, 0, 0));
+    /// ```
+    pub fn collides_with(&self, other: &Sprite, dx: isize, dy: isize) -> bool {
+        (0..other.height).any(|oy| (0..other.width).any(|ox| {
+            let (x, y) = (ox as isize + dx, oy as isize + dy);
+            other.get(ox, oy) && x >= 0 && y >= 0 && self.get(x as usize, y as usize)
+        }))
+    }
+
+    /// Detects whether any set pixel of the sprite, with its top left corner at `(x, y)`, lands on
+    /// a pixel set on the `Canvas`.
+    pub fn collides_with_canvas(&self, canvas: &Canvas, x: isize, y: isize) -> bool {
+        (0..self.height).any(|sy| (0..self.width).any(|sx| {
+            let (cx, cy) = (x + sx as isize, y + sy as isize);
+            self.get(sx, sy) && cx >= 0 && cy >= 0 && canvas.get(cx as usize, cy as usize)
+        }))
+    }
+
+    /// Draws the sprite onto the `Canvas` with its top left corner at `(x, y)`.
+    ///
+    /// The corner can be above or left of the canvas, in which case only the part of the sprite
+    /// on the canvas is drawn.
+    pub fn draw_at(&self, canvas: &mut Canvas, x: isize, y: isize) {
+        for sy in 0..self.height {
+            for sx in 0..self.width {
+                let (cx, cy) = (x + sx as isize, y + sy as isize);
+                if cx < 0 || cy < 0 {
+                    continue;
+                }
+                self.draw_pixel(canvas, self.pixel(sx, sy), cx as usize, cy as usize);
+            }
+        }
+    }
+
+    /// Draws the sprite onto the `Canvas` turned clockwise by `angle` degrees about `pivot`, a
+    /// point on the sprite given in its own pixels, with the pivot landing at `(x, y)`.
+    ///
+    /// Each pixel of the canvas the turned sprite covers takes the sprite’s pixel under its
+    /// centre, found by turning back the other way, so the sprite’s shape is redrawn cleanly rather
+    /// than its pixels being moved and leaving gaps. Pixels that would land above or left of the
+    /// canvas aren’t drawn.
+    ///
+    /// ```
+    /// use drawille::braille::Canvas;
+    /// use drawille::sprite::Sprite;
+    ///
+    /// let corner = Sprite::from_rows(&["#  ", "#  ", "###"]);
+    /// let (mut turned, mut expected) = (Canvas::new(20, 20), Canvas::new(20, 20));
+    /// corner.draw_rotated(&mut turned, 11.5, 6.5, 90.0, (1.5, 1.5));
+    /// corner.rotated(1).draw_at(&mut expected, 10, 5);
+    /// assert_eq!(turned, expected);
+    /// ```
+    pub fn draw_rotated(&self, canvas: &mut Canvas, x: f64, y: f64, angle: f64,
+                        pivot: (f64, f64)) {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let corners = [(0.0, 0.0), (self.width as f64, 0.0), (0.0, self.height as f64),
+                       (self.width as f64, self.height as f64)];
+        let turned = corners.iter().map(|&(cx, cy)| {
+            let (dx, dy) = (cx - pivot.0, cy - pivot.1);
+            (x + dx * cos - dy * sin, y + dx * sin + dy * cos)
+        }).collect::<Vec<_>>();
+        let left = turned.iter().map(|p| p.0).fold(f64::INFINITY, f64::min).floor().max(0.0);
+        let top = turned.iter().map(|p| p.1).fold(f64::INFINITY, f64::min).floor().max(0.0);
+        let right = turned.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max).ceil();
+        let bottom = turned.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max).ceil();
+        if !(left < right && top < bottom) {
+            return;
+        }
+        for cy in top as usize..bottom as usize {
+            for cx in left as usize..right as usize {
+                let (dx, dy) = (cx as f64 + 0.5 - x, cy as f64 + 0.5 - y);
+                let (sx, sy) = (pivot.0 + dx * cos + dy * sin, pivot.1 - dx * sin + dy * cos);
+                if sx < 0.0 || sy < 0.0 {
+                    continue;
+                }
+                self.draw_pixel(canvas, self.pixel(sx as usize, sy as usize), cx, cy);
+            }
+        }
+    }
+
+    /// Cuts the sprite into frames `width` by `height` pixels, as for a sprite sheet laid out in a
+    /// grid, returning them left to right and then top to bottom.
+    ///
+    /// Frames not wholly inside the sprite are left out.
+    ///
+    /// ```
+    /// use drawille::sprite::Sprite;
+    ///
+    /// let sheet = Sprite::from_rows(&["#  # ## #", " #  #  # "]);
+    /// let frames = sheet.slice(3, 2);
+    /// assert_eq!(frames.len(), 3);
+    /// assert_eq!(frames[1], Sprite::from_rows(&["# #", " # "]));
+    /// ```
+    pub fn slice(&self, width: usize, height: usize) -> Vec<Sprite> {
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+        let mut frames = vec![];
+        for top in (0..self.height / height).map(|row| row * height) {
+            for left in (0..self.width / width).map(|column| column * width) {
+                frames.push(self.remap(width, height, |x, y| (left + x, top + y)));
+            }
+        }
+        frames
+    }
+
+    /// Returns a copy of the sprite mirrored left to right.
+    pub fn flipped_horizontally(&self) -> Sprite {
+        self.remap(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// Returns a copy of the sprite mirrored top to bottom.
+    pub fn flipped_vertically(&self) -> Sprite {
+        self.remap(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    /// Returns a copy of the sprite turned clockwise by the given number of quarter turns.
+    pub fn rotated(&self, quarter_turns: u32) -> Sprite {
+        let (w, h) = (self.width, self.height);
+        match quarter_turns % 4 {
+            0 => self.clone(),
+            1 => self.remap(h, w, |x, y| (y, h - 1 - x)),
+            2 => self.remap(w, h, |x, y| (w - 1 - x, h - 1 - y)),
+            _ => self.remap(h, w, |x, y| (w - 1 - y, x)),
+        }
+    }
+
+    /// Returns a new sprite `width` by `height` pixels, taking each pixel from the pixel of this
+    /// sprite given by `source`.
+    fn remap<F: Fn(usize, usize) -> (usize, usize)>(&self, width: usize, height: usize,
+                                                    source: F) -> Sprite {
+        let mut sprite = Sprite::new(width, height);
+        sprite.color = self.color;
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                sprite.pixels[y * width + x] = self.pixel(sx, sy);
+            }
+        }
+        sprite
+    }
+
+    /// Draws one of the sprite’s pixels onto the canvas at `(x, y)`.
+    fn draw_pixel(&self, canvas: &mut Canvas, pixel: Pixel, x: usize, y: usize) {
+        match (pixel, self.color) {
+            (Pixel::Set, Some(color)) => canvas.set_colored(x, y, color),
+            (Pixel::Set, None) => canvas.set(x, y),
+            (Pixel::Clear, _) => canvas.unset(x, y),
+            (Pixel::Transparent, _) => {},
+        }
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> Pixel {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x]
+        } else {
+            Pixel::Transparent
+        }
+    }
+
+    fn put(&mut self, x: usize, y: usize, pixel: Pixel) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = pixel;
+        }
+    }
+}
+
+/// A sequence of sprites shown one after another, each for the same length of time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnimatedSprite {
+    frames: Vec<Sprite>,
+    frame_time: Duration,
+    elapsed: Duration,
+    looping: bool,
+}
+
+impl AnimatedSprite {
+    /// Creates a new `AnimatedSprite` showing each frame for `frame_time`, and starting again from
+    /// the first frame after the last.
+    pub fn new(frames: Vec<Sprite>, frame_time: Duration) -> AnimatedSprite {
+        AnimatedSprite { frames, frame_time, elapsed: Duration::from_secs(0), looping: true }
+    }
+
+    /// Sets whether the animation starts again after the last frame, rather than staying on it,
+    /// and returns the animation for use again.
+    pub fn looping(mut self, looping: bool) -> AnimatedSprite {
+        self.looping = looping;
+        self
+    }
+
+    /// Moves the animation on by `dt`, such as the time since the last frame of an animation loop.
+    pub fn update(&mut self, dt: Duration) {
+        self.elapsed += dt;
+        let total = self.frame_time * self.frames.len() as u32;
+        if self.looping && total > Duration::from_secs(0) && self.elapsed >= total {
+            self.elapsed = Duration::from_nanos((self.elapsed.as_nanos() % total.as_nanos()) as u64);
+        }
+    }
+
+    /// Starts the animation again from the first frame.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::from_secs(0);
+    }
+
+    /// Returns the number of the frame being shown, counting from zero.
+    pub fn frame_index(&self) -> usize {
+        if self.frame_time == Duration::from_secs(0) {
+            return 0;
+        }
+        let index = (self.elapsed.as_nanos() / self.frame_time.as_nanos()) as usize;
+        index.min(self.frames.len().saturating_sub(1))
+    }
+
+    /// Detects whether an animation that doesn’t loop has reached its last frame’s end.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.elapsed >= self.frame_time * self.frames.len() as u32
+    }
+
+    /// Returns the frame being shown, or `None` if there are no frames.
+    pub fn current(&self) -> Option<&Sprite> {
+        self.frames.get(self.frame_index())
+    }
+
+    /// Draws the frame being shown onto the `Canvas` with its top left corner at `(x, y)`.
+    pub fn draw_at(&self, canvas: &mut Canvas, x: isize, y: isize) {
+        if let Some(sprite) = self.current() {
+            sprite.draw_at(canvas, x, y);
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Tables with rules drawn in Braille pixels
+//!
+//! A `Table` lays out rows of cells in columns under a row of headers, and draws the rules between
+//! them as lines of Braille pixels rather than box-drawing characters, so that a rule takes up a
+//! single pixel of the character it's drawn through. Besides text, a cell can hold a sparkline or
+//! a bar drawn in Braille, which makes tables of processes or hosts much like `top`'s, each with a
+//! small chart of its own.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::table::{Cell, Table};
+//! use drawille::text::Align;
+//!
+//! let table = Table::new(&["host", "load", "cpu"])
+//!     .row(vec!["web-1".into(), Cell::Sparkline(vec![0.2, 0.4, 0.9, 0.5]), Cell::Bar(0.5)])
+//!     .row(vec!["db-1".into(), Cell::Sparkline(vec![0.1, 0.1, 0.2, 0.3]), Cell::Bar(0.25)])
+//!     .width(1, 4).width(2, 4).align(0, Align::Right);
+//! assert_eq!(table.frame().lines().collect::<Vec<_>>(), vec![" host ⡇ load ⡇ cpu ",
+//!                                                         "⠤⠤⠤⠤⠤⠤⡧⠤⠤⠤⠤⠤⠤⡧⠤⠤⠤⠤⠤",
+//!                                                         "web-1 ⡇ ⣀⣤⣿⣤ ⡇ ⣿⣿  ",
+//!                                                         " db-1 ⡇ ⣀⣀⣶⣿ ⡇ ⣿   "]);
+//! ```
+
+use std::cmp;
+
+use braille::{Canvas, LineStyle};
+use chart::{self, Chart};
+use text::Align;
+
+/// The width in characters of columns with no text to fit, only sparklines and bars.
+const GRAPHIC_WIDTH: usize = 8;
+
+/// What a cell of a `Table` shows.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cell {
+    /// Text, cut short if it's wider than its column.
+    Text(String),
+    /// A sparkline of the values, as wide as its column, as drawn by `chart::sparkline`.
+    Sparkline(Vec<f64>),
+    /// A bar filled to the given fraction of the width of its column, as drawn by
+    /// `chart::progress_bar`.
+    Bar(f64),
+}
+
+impl Cell {
+    /// Returns the width of the cell's text in characters, or zero for a cell without text.
+    fn text_width(&self) -> usize {
+        match *self {
+            Cell::Text(ref text) => text.chars().count(),
+            _ => 0,
+        }
+    }
+
+    /// Returns what the cell shows in a column `width` characters wide, aligned in it.
+    fn render(&self, width: usize, align: Align) -> String {
+        let content = match *self {
+            Cell::Text(ref text) => text.chars().take(width).collect(),
+            Cell::Sparkline(ref values) => chart::sparkline(values, width),
+            Cell::Bar(fraction) => chart::progress_bar(fraction, width),
+        };
+        let offset = align.offset(content.chars().count(), width);
+        format!("{:offset$}{}", "", content, offset = offset)
+    }
+}
+
+impl<'a> From<&'a str> for Cell {
+    fn from(text: &'a str) -> Cell {
+        Cell::Text(text.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Cell {
+        Cell::Text(text)
+    }
+}
+
+/// A table of cells in columns, under a row of headers, with rules drawn in Braille pixels.
+///
+/// Columns are separated by vertical rules, and the headers from the rows by a horizontal rule.
+/// Each column is as wide as its widest text, or as given by `width`, and columns holding only
+/// sparklines and bars are eight characters wide. Rules can also be drawn between rows, and
+/// around the whole table as a border.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+    widths: Vec<Option<usize>>,
+    aligns: Vec<Align>,
+    border: bool,
+    row_rules: bool,
+    style: LineStyle,
+}
+
+impl Table {
+    /// Creates a new `Table` with the given column headers and no rows. With no headers, the
+    /// table has no header row or rule under it.
+    pub fn new<S: AsRef<str>>(headers: &[S]) -> Table {
+        Table {
+            headers: headers.iter().map(|h| h.as_ref().to_string()).collect(),
+            rows: vec![],
+            widths: vec![],
+            aligns: vec![],
+            border: false,
+            row_rules: false,
+            style: LineStyle::Solid,
+        }
+    }
+
+    /// Adds a row of cells, one for each column in order, and returns the table for use again.
+    /// Columns the row has no cell for are left blank.
+    pub fn row(mut self, cells: Vec<Cell>) -> Table {
+        self.rows.push(cells);
+        self
+    }
+
+    /// Sets the width of a column in characters, instead of fitting it to the column's text, and
+    /// returns the table for use again.
+    pub fn width(mut self, column: usize, width: usize) -> Table {
+        if self.widths.len() <= column {
+            self.widths.resize(column + 1, None);
+        }
+        self.widths[column] = Some(width);
+        self
+    }
+
+    /// Sets how the cells of a column, and its header, are aligned, and returns the table for use
+    /// again. Columns are aligned left unless set otherwise.
+    pub fn align(mut self, column: usize, align: Align) -> Table {
+        if self.aligns.len() <= column {
+            self.aligns.resize(column + 1, Align::Left);
+        }
+        self.aligns[column] = align;
+        self
+    }
+
+    /// Draws a border of rules around the table, and returns the table for use again.
+    pub fn border(mut self) -> Table {
+        self.border = true;
+        self
+    }
+
+    /// Draws a rule between each row, and returns the table for use again.
+    pub fn row_rules(mut self) -> Table {
+        self.row_rules = true;
+        self
+    }
+
+    /// Sets the style every rule is drawn in, and returns the table for use again.
+    pub fn style(mut self, style: LineStyle) -> Table {
+        self.style = style;
+        self
+    }
+
+    /// Returns the width of each column in characters.
+    fn column_widths(&self) -> Vec<usize> {
+        let columns = self.rows.iter().map(|row| row.len()).fold(self.headers.len(), cmp::max);
+        (0..columns).map(|c| {
+            if let Some(&Some(width)) = self.widths.get(c) {
+                return width;
+            }
+            let header = self.headers.get(c).map_or(0, |h| h.chars().count());
+            let cells = self.rows.iter().filter_map(|row| row.get(c));
+            let text = cells.clone().map(Cell::text_width).fold(header, cmp::max);
+            let graphic = cells.clone().any(|cell| cell.text_width() == 0 && text == 0);
+            if graphic { GRAPHIC_WIDTH } else { text }
+        }).collect()
+    }
+
+    /// Returns the character column each column of cells starts in, and the character columns of
+    /// the vertical rules, along with the width of the table in characters.
+    fn column_layout(&self, widths: &[usize]) -> (Vec<usize>, Vec<usize>, usize) {
+        let (mut starts, mut rules) = (vec![], vec![]);
+        let mut col = 0;
+        if self.border {
+            rules.push(0);
+            col = 2;
+        }
+        for (i, &width) in widths.iter().enumerate() {
+            starts.push(col);
+            col += width;
+            if i + 1 < widths.len() || self.border {
+                // A space either side of the rule.
+                rules.push(col + 1);
+                col += 3;
+            }
+        }
+        // The border's rule ends the table, without the space after it.
+        let width = if self.border { col - 1 } else { col };
+        (starts, rules, width)
+    }
+
+    /// Returns the character row of each row of text, with the headers first if there are any,
+    /// and the pixel rows of the horizontal rules, along with the height of the table in
+    /// characters.
+    fn row_layout(&self) -> (Vec<usize>, Vec<usize>, usize) {
+        let (mut text, mut rules) = (vec![], vec![]);
+        let mut row = 0;
+        if self.border {
+            // Along the bottom of the row, close to the first row of text.
+            rules.push(3);
+            row = 1;
+        }
+        let headers = !self.headers.is_empty();
+        let count = self.rows.len() + headers as usize;
+        for i in 0..count {
+            text.push(row);
+            row += 1;
+            if i + 1 < count && ((headers && i == 0) || self.row_rules) {
+                rules.push(row * 4 + 2);
+                row += 1;
+            }
+        }
+        if self.border {
+            rules.push(row * 4);
+            row += 1;
+        }
+        (text, rules, row)
+    }
+
+    /// Returns the width and height of the table in pixels.
+    pub fn size(&self) -> (usize, usize) {
+        (self.column_layout(&self.column_widths()).2 * 2, self.row_layout().2 * 4)
+    }
+
+    /// Draws the table onto the `Canvas`, with its top left corner at the origin.
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let widths = self.column_widths();
+        let (starts, column_rules, width) = self.column_layout(&widths);
+        let (text_rows, row_rules, height) = self.row_layout();
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // Vertical rules run between the border's rules, if there are any, so that the corners
+        // are closed without overhanging.
+        let (top, bottom) = match (row_rules.first(), row_rules.last()) {
+            (Some(&top), Some(&bottom)) if self.border => (top, bottom),
+            _ => (0, height * 4 - 1),
+        };
+        for &col in column_rules.iter() {
+            for y in (top..bottom + 1).filter(|&y| self.style.draws(y - top)) {
+                canvas.set(col * 2, y);
+            }
+        }
+        let (left, right) = if self.border { (0, width * 2 - 2) } else { (0, width * 2 - 1) };
+        for &y in row_rules.iter() {
+            for x in (left..right + 1).filter(|&x| self.style.draws(x - left)) {
+                canvas.set(x, y);
+            }
+        }
+
+        let headers = self.headers.iter().map(|h| Cell::Text(h.clone())).collect::<Vec<_>>();
+        let headers = if headers.is_empty() { None } else { Some(&headers) };
+        for (&row, cells) in text_rows.iter().zip(headers.into_iter().chain(self.rows.iter())) {
+            for (i, cell) in cells.iter().enumerate() {
+                let align = self.aligns.get(i).cloned().unwrap_or_default();
+                canvas.text(starts[i] * 2, row * 4, cell.render(widths[i], align));
+            }
+        }
+    }
+
+    /// Draws the table to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        let (width, height) = self.size();
+        let mut canvas = Canvas::new(width, height);
+        self.draw(&mut canvas);
+        canvas.frame()
+    }
+}
+
+impl Chart for Table {
+    fn size(&self) -> (usize, usize) {
+        Table::size(self)
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        Table::draw(self, canvas);
+    }
+}
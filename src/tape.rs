@@ -0,0 +1,170 @@
+//! Recording canvases for playback
+//!
+//! A `FrameTape` records a sequence of `braille::Canvas`es with the time each was shown, storing
+//! for each only the characters that changed since the one before. A long session of slowly
+//! changing frames takes up little memory, and can be played back, sought through, or exported to
+//! an asciinema cast afterwards.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use drawille::braille::Canvas;
+//! use drawille::tape::FrameTape;
+//!
+//! let mut tape = FrameTape::new();
+//! let mut canvas = Canvas::new(8, 4);
+//! for i in 0..8 {
+//!     canvas.set(i, 0);
+//!     tape.record(&canvas, Duration::from_millis(i as u64 * 100));
+//! }
+//! assert_eq!(tape.len(), 8);
+//! assert_eq!(tape.frame(2).unwrap(), "⠉⠁  ");
+//! assert_eq!(tape.frame(7).unwrap(), canvas.frame());
+//! assert_eq!(tape.seek(Duration::from_millis(250)), Some(2));
+//! ```
+
+use std::time::Duration;
+
+use braille::{Canvas, Cell};
+use cast::Recorder;
+use console;
+
+/// The cell every character starts out as, before anything is recorded over it.
+const BLANK: Cell = Cell { char: ' ', color: None };
+
+/// One recorded frame: its size in characters and the characters that changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Delta {
+    time: Duration,
+    columns: usize,
+    rows: usize,
+    changes: Vec<(usize, usize, Cell)>,
+}
+
+/// A recording of canvases, storing only what changed between them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameTape {
+    deltas: Vec<Delta>,
+    /// The characters of the last canvas recorded, to compare the next against.
+    last: Vec<Vec<Cell>>,
+    monochrome: bool,
+}
+
+impl FrameTape {
+    /// Creates a new, empty `FrameTape`, played back without colours if colour has been turned
+    /// off with `NO_COLOR`.
+    pub fn new() -> FrameTape {
+        FrameTape { deltas: vec![], last: vec![], monochrome: console::no_color() }
+    }
+
+    /// Sets whether frames are played back without colours, and returns the tape for use again.
+    pub fn monochrome(mut self, monochrome: bool) -> FrameTape {
+        self.monochrome = monochrome;
+        self
+    }
+
+    /// Records the characters drawn on a canvas, shown `time` after the start of the recording.
+    ///
+    /// Frames should be recorded in order of time; seeking assumes they are.
+    pub fn record(&mut self, canvas: &Canvas, time: Duration) {
+        let (columns, rows) = canvas.extent();
+        let mut changes = vec![];
+        let mut grid = Vec::with_capacity(rows);
+        for row in 0..rows {
+            let line = (0..columns).map(|column| canvas.cell(column, row)).collect::<Vec<_>>();
+            for (column, &cell) in line.iter().enumerate() {
+                let before = self.last.get(row).and_then(|l| l.get(column)).cloned();
+                if before.unwrap_or(BLANK) != cell {
+                    changes.push((column, row, cell));
+                }
+            }
+            grid.push(line);
+        }
+        self.deltas.push(Delta { time, columns, rows, changes });
+        self.last = grid;
+    }
+
+    /// Returns the number of frames recorded.
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    /// Detects whether no frames have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// Returns the time the last frame was recorded at, or zero if none have been.
+    pub fn duration(&self) -> Duration {
+        self.deltas.last().map_or(Duration::from_secs(0), |d| d.time)
+    }
+
+    /// Returns the index of the frame being shown `time` after the start of the recording: the
+    /// last one recorded at or before then, or `None` if that’s before the first frame.
+    pub fn seek(&self, time: Duration) -> Option<usize> {
+        self.deltas.iter().rposition(|d| d.time <= time)
+    }
+
+    /// Draws the frame at the given index to a `String` just as `Canvas::frame` drew it, or
+    /// returns `None` if there aren’t that many frames.
+    ///
+    /// The frame is rebuilt from the changes recorded since the first, so frames far into a long
+    /// recording take longer to find; use `playback` to go through them all in order.
+    pub fn frame(&self, index: usize) -> Option<String> {
+        self.playback().nth(index).map(|(_, frame)| frame)
+    }
+
+    /// Returns an iterator over every frame recorded, in order, with the time it was recorded at.
+    pub fn playback(&self) -> Playback<'_> {
+        Playback { deltas: self.deltas.iter(), grid: vec![], monochrome: self.monochrome }
+    }
+
+    /// Copies the frames into a `Recorder` for a terminal `width` columns by `height` rows, each
+    /// shown until the next was recorded, to be saved as an asciinema cast.
+    pub fn to_recorder(&self, width: usize, height: usize) -> Recorder {
+        let mut recorder = Recorder::new(width, height);
+        let frames = self.playback().collect::<Vec<_>>();
+        for (i, &(time, ref frame)) in frames.iter().enumerate() {
+            let next = frames.get(i + 1).map_or(time, |&(next, _)| next);
+            recorder.push(frame, next.checked_sub(time).unwrap_or_default());
+        }
+        recorder
+    }
+}
+
+impl Default for FrameTape {
+    fn default() -> FrameTape {
+        FrameTape::new()
+    }
+}
+
+/// The frames of a `FrameTape`, rebuilt one after another.
+pub struct Playback<'a> {
+    deltas: ::std::slice::Iter<'a, Delta>,
+    grid: Vec<Vec<Cell>>,
+    monochrome: bool,
+}
+
+impl<'a> Iterator for Playback<'a> {
+    type Item = (Duration, String);
+
+    fn next(&mut self) -> Option<(Duration, String)> {
+        let delta = self.deltas.next()?;
+        self.grid.resize(delta.rows, vec![]);
+        for line in self.grid.iter_mut() {
+            line.resize(delta.columns, BLANK);
+        }
+        for &(column, row, cell) in delta.changes.iter() {
+            self.grid[row][column] = cell;
+        }
+        let rows = self.grid.iter().map(|line| {
+            line.iter().map(|cell| match cell.color {
+                Some(color) if !self.monochrome => format!("\x1b[3{}m{}\x1b[0m", color as u32, cell.char),
+                _ => cell.char.to_string(),
+            }).collect::<String>()
+        }).collect::<Vec<_>>();
+        Some((delta.time, rows.join("\n")))
+    }
+}
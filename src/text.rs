@@ -0,0 +1,146 @@
+//! Measuring, wrapping and aligning text
+//!
+//! This module lays out text to be written onto a canvas a character at a time: `text_width`
+//! measures it in terminal cells, `wrap` breaks it into lines that fit a given width, and a
+//! `TextBox` wraps and aligns it within a box before writing it onto a `block::Canvas` or
+//! `braille::Canvas`.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::block::{Canvas, Color};
+//! use drawille::text::{Align, TextBox};
+//!
+//! let mut canvas = Canvas::new(20, 6);
+//! let text = TextBox::new(10, 3).align(Align::Center);
+//! assert_eq!(text.lines("Hello there, world"), vec![(2, "Hello".to_string()),
+//!                                                    (2, "there,".to_string()),
+//!                                                    (2, "world".to_string())]);
+//! text.draw_block(&mut canvas, 0, 0, Color::White, Color::Black, "Hello there, world");
+//! ```
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use block::{self, Color};
+use braille;
+
+/// Returns the width of a string in terminal cells, counting wide characters such as most CJK
+/// characters as two and combining characters as none.
+pub fn text_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Breaks text into lines no wider than `width` cells, breaking between words where it can.
+///
+/// Line breaks in the text are kept, runs of spaces between words become a single space, and
+/// words too long for a line of their own are broken wherever they reach the end of it.
+///
+/// ```
+/// use drawille::text::wrap;
+///
+/// assert_eq!(wrap("The quick brown fox\njumps", 10), vec!["The quick", "brown fox", "jumps"]);
+/// assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+/// ```
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    if width == 0 {
+        return lines;
+    }
+    for paragraph in s.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let needed = if line.is_empty() { 0 } else { text_width(&line) + 1 };
+            if needed + text_width(word) <= width {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(line);
+                line = String::new();
+            }
+            for c in word.chars() {
+                if !line.is_empty() && text_width(&line) + c.width().unwrap_or(0) > width {
+                    lines.push(line);
+                    line = String::new();
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Where a line of text goes across the space it’s given.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    /// Returns how many cells from the left a line `text_width` cells wide starts, to be aligned
+    /// within `width` cells.
+    pub fn offset(self, text_width: usize, width: usize) -> usize {
+        let space = width.saturating_sub(text_width);
+        match self {
+            Align::Left => 0,
+            Align::Center => space / 2,
+            Align::Right => space,
+        }
+    }
+}
+
+/// A box of a fixed size in characters that text is wrapped and aligned within.
+///
+/// Lines that don’t fit in the height of the box are left out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TextBox {
+    width: usize,
+    height: usize,
+    align: Align,
+}
+
+impl TextBox {
+    /// Creates a new `TextBox` `width` characters wide and `height` lines high, aligning text to
+    /// the left.
+    pub fn new(width: usize, height: usize) -> TextBox {
+        TextBox { width, height, align: Align::Left }
+    }
+
+    /// Sets how each line is aligned, and returns the box for use again.
+    pub fn align(mut self, align: Align) -> TextBox {
+        self.align = align;
+        self
+    }
+
+    /// Returns the lines of text that fit in the box, each with how many characters from the
+    /// left of the box it starts.
+    pub fn lines(&self, s: &str) -> Vec<(usize, String)> {
+        wrap(s, self.width).into_iter().take(self.height).map(|line| {
+            (self.align.offset(text_width(&line), self.width), line)
+        }).collect()
+    }
+
+    /// Writes text onto a `block::Canvas` in the given colours, with the top left corner of the
+    /// box at the character containing the pixel at the specified coordinates.
+    pub fn draw_block(&self, canvas: &mut block::Canvas, x: usize, y: usize, fg: Color, bg: Color,
+                      s: &str) {
+        for (i, (offset, line)) in self.lines(s).into_iter().enumerate() {
+            canvas.text(x + offset, y + i * 2, fg, bg, line);
+        }
+    }
+
+    /// Writes text onto a `braille::Canvas`, with the top left corner of the box at the character
+    /// containing the pixel at the specified coordinates.
+    pub fn draw_braille(&self, canvas: &mut braille::Canvas, x: usize, y: usize, s: &str) {
+        for (i, (offset, line)) in self.lines(s).into_iter().enumerate() {
+            canvas.text(x + offset * 2, y + i * 4, line);
+        }
+    }
+}
@@ -0,0 +1,464 @@
+//! Braille canvases stored in tiles
+//!
+//! A `braille::Canvas` keeps each character in a hash map of its own, which is simple but slow
+//! and wasteful once a drawing covers millions of pixels. A `TiledCanvas` draws just the same
+//! Braille characters, but keeps them in square tiles of 64 by 64 characters, each a flat array:
+//! setting a pixel only looks up its tile, whole rows are drawn from a few tiles at a time, and
+//! untouched areas take no memory at all.
+//!
+//! Each tile also remembers whether it’s been drawn on since the last call to `take_dirty`, so a
+//! program showing part of a huge canvas can tell which parts to redraw.
+//!
+//! An `InfiniteCanvas` goes further, with no edges at all: its tiles are worked out by a function
+//! only once they’re needed, and it’s drawn through a viewport that can be panned around.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::tiles::TiledCanvas;
+//!
+//! let mut canvas = TiledCanvas::new(0, 0);
+//! canvas.line(0, 0, 999, 999);
+//! canvas.set(200_000, 3);
+//! assert!(canvas.get(500, 500));
+//! assert_eq!(canvas.pixel_count(), 1001);
+//! assert_eq!(canvas.take_dirty().len(), 9);
+//! assert!(canvas.take_dirty().is_empty());
+//! ```
+
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+use braille::{self, Canvas, GlyphTable};
+use geometry;
+
+/// The width and height of a tile in characters.
+pub const TILE_SIZE: usize = 64;
+
+/// A square of characters, each a mask of Braille dots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Tile {
+    cells: Box<[u8]>,
+    /// How many of the cells have any dots set.
+    occupied: usize,
+    dirty: bool,
+}
+
+impl Tile {
+    fn new() -> Tile {
+        Tile { cells: vec![0; TILE_SIZE * TILE_SIZE].into_boxed_slice(), occupied: 0, dirty: true }
+    }
+
+    /// Replaces the mask of the cell at the given position in the tile.
+    fn put(&mut self, column: usize, row: usize, mask: u8) {
+        let cell = &mut self.cells[row * TILE_SIZE + column];
+        match (*cell != 0, mask != 0) {
+            (false, true) => self.occupied += 1,
+            (true, false) => self.occupied -= 1,
+            _ => {},
+        }
+        if *cell != mask {
+            *cell = mask;
+            self.dirty = true;
+        }
+    }
+}
+
+/// A canvas drawn with Braille characters, stored in tiles so that it can grow very large.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TiledCanvas {
+    tiles: HashMap<(usize, usize), Tile>,
+    /// The tiles forgotten since the last call to `take_dirty`, which are dirty too: whatever
+    /// was shown of them has to be blanked.
+    forgotten: HashSet<(usize, usize)>,
+    glyphs: GlyphTable,
+    width: usize,
+    height: usize,
+}
+
+impl TiledCanvas {
+    /// Creates a new `TiledCanvas` with the given width and height in pixels.
+    ///
+    /// Just as for a `braille::Canvas`, the canvas still grows to fit anything drawn outside the
+    /// given size.
+    pub fn new(width: usize, height: usize) -> TiledCanvas {
+        TiledCanvas {
+            tiles: HashMap::new(),
+            forgotten: HashSet::new(),
+            glyphs: GlyphTable::braille(),
+            width,
+            height,
+        }
+    }
+
+    /// Sets the characters drawn for each arrangement of dots, and returns the canvas for use
+    /// again.
+    pub fn glyphs(mut self, glyphs: GlyphTable) -> TiledCanvas {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Returns the width of the canvas in pixels, as given to `new`.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas in pixels, as given to `new`.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Clears the canvas, forgetting all of its tiles.
+    pub fn clear(&mut self) {
+        self.forgotten.extend(self.tiles.drain().map(|(key, _)| key));
+    }
+
+    /// Returns the mask of dots set in the character at the given column and row.
+    pub fn get_cell(&self, column: usize, row: usize) -> u8 {
+        match self.tiles.get(&(column / TILE_SIZE, row / TILE_SIZE)) {
+            Some(tile) => tile.cells[row % TILE_SIZE * TILE_SIZE + column % TILE_SIZE],
+            None => 0,
+        }
+    }
+
+    /// Sets the dots in the character at the given column and row to exactly those in `mask`.
+    ///
+    /// A tile left with nothing set is forgotten.
+    pub fn set_cell(&mut self, column: usize, row: usize, mask: u8) {
+        let key = (column / TILE_SIZE, row / TILE_SIZE);
+        if mask == 0 && !self.tiles.contains_key(&key) {
+            return;
+        }
+        let tile = self.tiles.entry(key).or_insert_with(Tile::new);
+        tile.put(column % TILE_SIZE, row % TILE_SIZE, mask);
+        if tile.occupied == 0 {
+            self.tiles.remove(&key);
+            self.forgotten.insert(key);
+        }
+    }
+
+    /// Sets a pixel at the specified coordinates.
+    pub fn set(&mut self, x: usize, y: usize) {
+        let mask = self.get_cell(x / 2, y / 4);
+        self.set_cell(x / 2, y / 4, mask | braille::dot(x, y));
+    }
+
+    /// Deletes a pixel at the specified coordinates.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        let mask = self.get_cell(x / 2, y / 4);
+        self.set_cell(x / 2, y / 4, mask & !braille::dot(x, y));
+    }
+
+    /// Toggles a pixel at the specified coordinates.
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        let mask = self.get_cell(x / 2, y / 4);
+        self.set_cell(x / 2, y / 4, mask ^ braille::dot(x, y));
+    }
+
+    /// Detects whether the pixel at the given coordinates is set.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.get_cell(x / 2, y / 4) & braille::dot(x, y) != 0
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)` onto the canvas.
+    pub fn line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        geometry::line_pixels(x1, y1, x2, y2, |x, y| self.set(x, y));
+    }
+
+    /// Returns how many pixels are set on the canvas.
+    pub fn pixel_count(&self) -> usize {
+        self.tiles.values().map(|tile| {
+            tile.cells.iter().map(|mask| mask.count_ones() as usize).sum::<usize>()
+        }).sum()
+    }
+
+    /// Returns the column and row of every character with dots set, with its mask of dots,
+    /// a tile at a time.
+    pub fn cells(&self) -> Vec<((usize, usize), u8)> {
+        let mut cells = vec![];
+        for (&(tx, ty), tile) in self.tiles.iter() {
+            for (i, &mask) in tile.cells.iter().enumerate().filter(|&(_, &mask)| mask != 0) {
+                cells.push(((tx * TILE_SIZE + i % TILE_SIZE, ty * TILE_SIZE + i / TILE_SIZE),
+                            mask));
+            }
+        }
+        cells
+    }
+
+    /// Returns the position of each tile drawn on since this was last called, as its column and
+    /// row of tiles, and marks every tile as clean.
+    ///
+    /// The characters of the tile at `(x, y)` are those from column `x * TILE_SIZE` and row
+    /// `y * TILE_SIZE`, for `TILE_SIZE` columns and rows. Tiles that have been forgotten, because
+    /// nothing in them is set any more or the canvas was cleared, are listed too.
+    ///
+    /// ```
+    /// use drawille::tiles::TiledCanvas;
+    ///
+    /// let mut canvas = TiledCanvas::new(0, 0);
+    /// canvas.set(0, 0);
+    /// canvas.set(200, 0);
+    /// canvas.take_dirty();
+    /// canvas.unset(0, 0);
+    /// assert_eq!(canvas.take_dirty(), vec![(0, 0)]);
+    /// canvas.clear();
+    /// assert_eq!(canvas.take_dirty(), vec![(1, 0)]);
+    /// ```
+    pub fn take_dirty(&mut self) -> Vec<(usize, usize)> {
+        let mut dirty = self.forgotten.drain().collect::<Vec<_>>();
+        for (&key, tile) in self.tiles.iter_mut() {
+            if tile.dirty {
+                tile.dirty = false;
+                dirty.push(key);
+            }
+        }
+        dirty.sort_by_key(|&(x, y)| (y, x));
+        dirty.dedup();
+        dirty
+    }
+
+    /// Returns how many columns and rows of characters `rows` draws: enough for the size given
+    /// to `new`, and for everything drawn.
+    pub fn extent(&self) -> (usize, usize) {
+        let (mut columns, mut rows) = (self.width.div_ceil(2), self.height.div_ceil(4));
+        for (&(tx, ty), tile) in self.tiles.iter() {
+            // Only tiles reaching past the extent found so far need looking inside.
+            if (tx + 1) * TILE_SIZE <= columns && (ty + 1) * TILE_SIZE <= rows {
+                continue;
+            }
+            for (i, _) in tile.cells.iter().enumerate().filter(|&(_, &mask)| mask != 0) {
+                columns = cmp::max(columns, tx * TILE_SIZE + i % TILE_SIZE + 1);
+                rows = cmp::max(rows, ty * TILE_SIZE + i / TILE_SIZE + 1);
+            }
+        }
+        (columns, rows)
+    }
+
+    /// Returns the rows of characters in the rectangle `columns` characters wide and `rows`
+    /// high from the given column and row, such as to show part of a huge canvas.
+    pub fn rows_within(&self, column: usize, row: usize, columns: usize, rows: usize)
+                       -> Vec<String> {
+        (row..row + rows).map(|y| {
+            let mut line = String::with_capacity(columns * 3);
+            let mut x = column;
+            while x < column + columns {
+                // Draw as many characters as there are left in this tile at once.
+                let end = cmp::min(column + columns, (x / TILE_SIZE + 1) * TILE_SIZE);
+                match self.tiles.get(&(x / TILE_SIZE, y / TILE_SIZE)) {
+                    Some(tile) => {
+                        let start = y % TILE_SIZE * TILE_SIZE + x % TILE_SIZE;
+                        let cells = &tile.cells[start..start + (end - x)];
+                        line.extend(cells.iter().map(|&mask| self.glyphs.glyph(mask)));
+                    },
+                    None => line.extend((x..end).map(|_| self.glyphs.glyph(0))),
+                }
+                x = end;
+            }
+            line
+        }).collect()
+    }
+
+    /// Returns a `Vec` of each row of the canvas, just as `braille::Canvas::rows` draws it.
+    pub fn rows(&self) -> Vec<String> {
+        let (columns, rows) = self.extent();
+        self.rows_within(0, 0, columns, rows)
+    }
+
+    /// Draws the canvas to a `String` and returns it.
+    pub fn frame(&self) -> String {
+        self.rows().join("\n")
+    }
+
+    /// Copies the canvas onto a new `braille::Canvas` of the same size.
+    ///
+    /// ```
+    /// use drawille::tiles::TiledCanvas;
+    ///
+    /// let mut canvas = TiledCanvas::new(10, 10);
+    /// canvas.line(1, 1, 300, 20);
+    /// assert_eq!(canvas.frame(), canvas.to_canvas().frame());
+    /// ```
+    pub fn to_canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height).glyphs(self.glyphs.clone());
+        for ((column, row), mask) in self.cells() {
+            canvas.set_cell(column, row, mask);
+        }
+        canvas
+    }
+}
+
+/// A canvas without any edges, whose pixels are worked out by a function as they’re needed and
+/// shown through a viewport.
+///
+/// The canvas is made of tiles `TILE_SIZE` characters across, the same as a `TiledCanvas`. The
+/// first time anything in a tile is drawn or looked at, the tile is filled in by calling the
+/// function with each pixel’s coordinates, which can be negative, and kept for next time. Panning
+/// around something expensive to work out, such as a fractal, only costs anything for the parts
+/// not yet seen.
+///
+/// ```
+/// use drawille::tiles::InfiniteCanvas;
+///
+/// // Stripes running diagonally forever in every direction.
+/// let mut canvas = InfiniteCanvas::new(|x, y| (x + y).rem_euclid(4) == 0).view(-1, -1, 3, 1);
+/// assert_eq!(canvas.frame(), "⠔⢁⠔");
+/// canvas.pan(1, 0);
+/// canvas.set(0, -3);
+/// assert_eq!(canvas.frame(), "⢃⠔⢁");
+/// assert_eq!(canvas.tile_count(), 2);
+/// ```
+pub struct InfiniteCanvas<F> {
+    generate: F,
+    tiles: HashMap<(isize, isize), Box<[u8]>>,
+    glyphs: GlyphTable,
+    /// The column and row of the character at the top left of the viewport.
+    origin: (isize, isize),
+    /// The size of the viewport in characters.
+    size: (usize, usize),
+}
+
+impl<F: Fn(isize, isize) -> bool> InfiniteCanvas<F> {
+    /// Creates a new `InfiniteCanvas` whose pixels are set wherever `generate` returns `true` for
+    /// their coordinates, with a viewport 80 characters by 24 from the origin.
+    pub fn new(generate: F) -> InfiniteCanvas<F> {
+        InfiniteCanvas {
+            generate,
+            tiles: HashMap::new(),
+            glyphs: GlyphTable::braille(),
+            origin: (0, 0),
+            size: (80, 24),
+        }
+    }
+
+    /// Sets the characters drawn for each arrangement of dots, and returns the canvas for use
+    /// again.
+    pub fn glyphs(mut self, glyphs: GlyphTable) -> InfiniteCanvas<F> {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Sets the viewport to `columns` by `rows` characters with its top left corner at the given
+    /// column and row, and returns the canvas for use again.
+    pub fn view(mut self, column: isize, row: isize, columns: usize, rows: usize)
+                -> InfiniteCanvas<F> {
+        self.origin = (column, row);
+        self.size = (columns, rows);
+        self
+    }
+
+    /// Moves the viewport `columns` characters right and `rows` down.
+    pub fn pan(&mut self, columns: isize, rows: isize) {
+        self.origin = (self.origin.0 + columns, self.origin.1 + rows);
+    }
+
+    /// Changes the size of the viewport to `columns` by `rows` characters, keeping its top left
+    /// corner where it is.
+    pub fn resize(&mut self, columns: usize, rows: usize) {
+        self.size = (columns, rows);
+    }
+
+    /// Returns the column and row of the character at the top left of the viewport.
+    pub fn origin(&self) -> (isize, isize) {
+        self.origin
+    }
+
+    /// Returns how many tiles have been worked out and kept.
+    pub fn tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Forgets every tile that doesn’t overlap the viewport, to be worked out again if it’s
+    /// needed later.
+    pub fn forget_hidden(&mut self) {
+        let (first, last) = self.visible_tiles();
+        self.tiles.retain(|&(tx, ty), _| {
+            tx >= first.0 && tx <= last.0 && ty >= first.1 && ty <= last.1
+        });
+    }
+
+    /// Sets a pixel at the specified coordinates.
+    pub fn set(&mut self, x: isize, y: isize) {
+        let (cell, dot) = self.locate(x, y);
+        *cell |= dot;
+    }
+
+    /// Deletes a pixel at the specified coordinates.
+    pub fn unset(&mut self, x: isize, y: isize) {
+        let (cell, dot) = self.locate(x, y);
+        *cell &= !dot;
+    }
+
+    /// Toggles a pixel at the specified coordinates.
+    pub fn toggle(&mut self, x: isize, y: isize) {
+        let (cell, dot) = self.locate(x, y);
+        *cell ^= dot;
+    }
+
+    /// Detects whether the pixel at the given coordinates is set, working out its tile if it
+    /// hasn’t been already.
+    pub fn get(&mut self, x: isize, y: isize) -> bool {
+        let (cell, dot) = self.locate(x, y);
+        *cell & dot != 0
+    }
+
+    /// Returns a `Vec` of each row of characters in the viewport.
+    pub fn rows(&mut self) -> Vec<String> {
+        let ((column, row), (columns, rows)) = (self.origin, self.size);
+        let mut result = Vec::with_capacity(rows);
+        for y in row..row + rows as isize {
+            let mut line = String::with_capacity(columns * 3);
+            for x in column..column + columns as isize {
+                let mask = *self.cell(x, y);
+                line.push(self.glyphs.glyph(mask));
+            }
+            result.push(line);
+        }
+        result
+    }
+
+    /// Draws the viewport to a `String` and returns it.
+    pub fn frame(&mut self) -> String {
+        self.rows().join("\n")
+    }
+
+    /// Returns the tiles at the top left and bottom right of the viewport.
+    fn visible_tiles(&self) -> ((isize, isize), (isize, isize)) {
+        let tile = TILE_SIZE as isize;
+        let (column, row) = self.origin;
+        let columns = cmp::max(self.size.0, 1) as isize;
+        let rows = cmp::max(self.size.1, 1) as isize;
+        ((column.div_euclid(tile), row.div_euclid(tile)),
+         ((column + columns - 1).div_euclid(tile), (row + rows - 1).div_euclid(tile)))
+    }
+
+    /// Returns the mask of dots of the character containing a pixel, and the pixel’s dot.
+    fn locate(&mut self, x: isize, y: isize) -> (&mut u8, u8) {
+        let dot = braille::dot(x.rem_euclid(2) as usize, y.rem_euclid(4) as usize);
+        (self.cell(x.div_euclid(2), y.div_euclid(4)), dot)
+    }
+
+    /// Returns the mask of dots of the character at the given column and row, working out its
+    /// tile if it hasn’t been already.
+    fn cell(&mut self, column: isize, row: isize) -> &mut u8 {
+        let tile = TILE_SIZE as isize;
+        let key = (column.div_euclid(tile), row.div_euclid(tile));
+        let generate = &self.generate;
+        let cells = self.tiles.entry(key).or_insert_with(|| {
+            let mut cells = vec![0; TILE_SIZE * TILE_SIZE].into_boxed_slice();
+            for (i, mask) in cells.iter_mut().enumerate() {
+                let (x, y) = ((key.0 * tile + (i % TILE_SIZE) as isize) * 2,
+                              (key.1 * tile + (i / TILE_SIZE) as isize) * 4);
+                for dy in 0..4 {
+                    for dx in 0..2 {
+                        if generate(x + dx as isize, y + dy as isize) {
+                            *mask |= braille::dot(dx, dy);
+                        }
+                    }
+                }
+            }
+            cells
+        });
+        let index = row.rem_euclid(tile) as usize * TILE_SIZE + column.rem_euclid(tile) as usize;
+        &mut cells[index]
+    }
+}
@@ -0,0 +1,86 @@
+//! Affine transforms of canvas coordinates
+//!
+//! A `Transform` moves, turns and stretches points before they’re drawn. Angles are in degrees
+//! and, as with a `Turtle`, positive angles turn clockwise on the screen, since y increases
+//! downwards.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::transform::Transform;
+//!
+//! let transform = Transform::rotation(90.0).then(&Transform::translation(10.0, 0.0));
+//! let (x, y) = transform.apply((5.0, 0.0));
+//! assert!((x - 10.0).abs() < 1e-9 && (y - 5.0).abs() < 1e-9);
+//! ```
+
+/// A transform of points on a canvas: any combination of translation, rotation, scaling and
+/// shearing.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// The matrix `[[a, c, e], [b, d, f]]`, mapping `(x, y)` to `(a x + c y + e, b x + d y + f)`.
+    matrix: [f64; 6],
+}
+
+impl Transform {
+    /// Returns the transform that leaves every point where it is.
+    pub fn identity() -> Transform {
+        Transform { matrix: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0] }
+    }
+
+    /// Returns a transform moving points `dx` right and `dy` down.
+    pub fn translation(dx: f64, dy: f64) -> Transform {
+        Transform { matrix: [1.0, 0.0, 0.0, 1.0, dx, dy] }
+    }
+
+    /// Returns a transform turning points clockwise about the origin by `degrees`.
+    pub fn rotation(degrees: f64) -> Transform {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Transform { matrix: [cos, sin, -sin, cos, 0.0, 0.0] }
+    }
+
+    /// Returns a transform stretching points away from the origin by `sx` across and `sy` down.
+    pub fn scaling(sx: f64, sy: f64) -> Transform {
+        Transform { matrix: [sx, 0.0, 0.0, sy, 0.0, 0.0] }
+    }
+
+    /// Returns the transform that applies this one and then `other`.
+    pub fn then(&self, other: &Transform) -> Transform {
+        let [a1, b1, c1, d1, e1, f1] = self.matrix;
+        let [a2, b2, c2, d2, e2, f2] = other.matrix;
+        Transform {
+            matrix: [a2 * a1 + c2 * b1, b2 * a1 + d2 * b1,
+                     a2 * c1 + c2 * d1, b2 * c1 + d2 * d1,
+                     a2 * e1 + c2 * f1 + e2, b2 * e1 + d2 * f1 + f2],
+        }
+    }
+
+    /// Returns the transform that undoes this one, or `None` if it squashes points onto a line.
+    pub fn inverse(&self) -> Option<Transform> {
+        let [a, b, c, d, e, f] = self.matrix;
+        let det = a * d - b * c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let (a, b, c, d) = (d / det, -b / det, -c / det, a / det);
+        Some(Transform { matrix: [a, b, c, d, -(a * e + c * f), -(b * e + d * f)] })
+    }
+
+    /// Transforms a point.
+    pub fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let [a, b, c, d, e, f] = self.matrix;
+        (a * x + c * y + e, b * x + d * y + f)
+    }
+
+    /// Returns how much the transform scales lengths on average, such as the width of a line.
+    pub fn scale_factor(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.matrix;
+        (a * d - b * c).abs().sqrt()
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::identity()
+    }
+}
@@ -0,0 +1,146 @@
+//! Easing and interpolation for animations
+//!
+//! A `Tween` moves a value from one end to another over a number of frames, with an `Easing` to
+//! shape how it speeds up and slows down. Values can be numbers, points or `Angle`s, which turn the
+//! shortest way round, so tweens can drive a `Turtle` or the shapes drawn in each frame of an
+//! animation.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::tween::{Easing, Tween};
+//!
+//! let tween = Tween::new((0.0, 0.0), (100.0, 40.0), 10).easing(Easing::CubicInOut);
+//! assert_eq!(tween.at(0), (0.0, 0.0));
+//! assert_eq!(tween.at(5), (50.0, 20.0));
+//! assert_eq!(tween.at(20), (100.0, 40.0));
+//! ```
+
+use std::f64::consts::PI;
+
+/// A way of mapping progress through a tween, from 0 to 1, to how far the value has moved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Easing {
+    /// Moves at a constant speed.
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+    /// Like `EaseIn`, but more sharply.
+    CubicIn,
+    /// Like `EaseOut`, but more sharply.
+    CubicOut,
+    /// Like `EaseInOut`, but more sharply.
+    CubicInOut,
+    /// Overshoots the end and springs back and forth before settling.
+    Elastic,
+}
+
+impl Easing {
+    /// Returns how far along the value is at progress `t`, clamped to between 0 and 1.
+    ///
+    /// Every easing starts at 0 and ends at 1; `Elastic` goes beyond 1 on the way.
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - 2.0 * (1.0 - t).powi(2) },
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 { 4.0 * t.powi(3) } else { 1.0 - 4.0 * (1.0 - t).powi(3) }
+            },
+            Easing::Elastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            },
+        }
+    }
+}
+
+/// A value that can be interpolated between two others.
+pub trait Lerp {
+    /// Returns the value a fraction `t` of the way from `self` to `other`.
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &f64, t: f64) -> f64 {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &f32, t: f64) -> f32 {
+        self + (other - self) * t as f32
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    fn lerp(&self, other: &(A, B), t: f64) -> (A, B) {
+        (self.0.lerp(&other.0, t), self.1.lerp(&other.1, t))
+    }
+}
+
+/// An angle in degrees, like a `Turtle`’s rotation, which interpolates the shortest way round.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Angle(pub f32);
+
+impl Lerp for Angle {
+    fn lerp(&self, other: &Angle, t: f64) -> Angle {
+        let turn = (other.0 - self.0).rem_euclid(360.0);
+        let turn = if turn > 180.0 { turn - 360.0 } else { turn };
+        Angle(self.0 + turn * t as f32)
+    }
+}
+
+/// A value moving from one end to another over a number of frames.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    frames: u64,
+    easing: Easing,
+}
+
+impl<T: Lerp + Clone> Tween<T> {
+    /// Creates a new `Tween` from `from` to `to` over the given number of frames, moving at a
+    /// constant speed.
+    pub fn new(from: T, to: T, frames: u64) -> Tween<T> {
+        Tween { from, to, frames, easing: Easing::Linear }
+    }
+
+    /// Sets the easing used, and returns the tween for use again.
+    pub fn easing(mut self, easing: Easing) -> Tween<T> {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns the value at a frame, counting from the first frame of the tween.
+    ///
+    /// Frames after the last give the end value.
+    pub fn at(&self, frame: u64) -> T {
+        if frame >= self.frames {
+            return self.to.clone();
+        }
+        self.from.lerp(&self.to, self.easing.apply(frame as f64 / self.frames as f64))
+    }
+
+    /// Detects whether the tween has reached its end by a frame.
+    pub fn is_finished(&self, frame: u64) -> bool {
+        frame >= self.frames
+    }
+
+    /// Returns a tween going back from the end to the start, with the same easing.
+    pub fn reversed(&self) -> Tween<T> {
+        Tween { from: self.to.clone(), to: self.from.clone(), ..*self }
+    }
+}
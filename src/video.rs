@@ -0,0 +1,195 @@
+//! Playing video in Braille
+//!
+//! A `VideoStream` takes frames of RGB pixels one after another—from a video decoder, a webcam or
+//! anything else—and turns each into Braille characters: scaling it to the size of the stream,
+//! converting it to grey, dithering it to set and unset pixels, and working out which rows
+//! changed since the last frame. The whole pipeline works on plain buffers rather than drawing
+//! each pixel onto a `Canvas`, so it keeps up with full-screen video.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::palette::Dithering;
+//! use drawille::video::VideoStream;
+//!
+//! // A frame 4 by 4 pixels, white on the left and black on the right.
+//! let mut frame = vec![0; 4 * 4 * 3];
+//! for y in 0..4 {
+//!     for x in 0..2 {
+//!         for c in 0..3 {
+//!             frame[(y * 4 + x) * 3 + c] = 255;
+//!         }
+//!     }
+//! }
+//! let mut stream = VideoStream::new(8, 4).dithering(Dithering::Ordered);
+//! assert_eq!(stream.push_frame(&frame, 4, 4), "\x1b[1;1H⣿⣿  \x1b[K");
+//! // Nothing changed, so nothing needs redrawing.
+//! assert_eq!(stream.push_frame(&frame, 4, 4), "");
+//! assert_eq!(stream.frame(), "⣿⣿  ");
+//! ```
+
+use std::char;
+
+use braille::{self, Canvas};
+use dither;
+use palette::Dithering;
+
+/// Converts frames of RGB pixels to Braille, redrawing only what changed between them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VideoStream {
+    width: usize,
+    height: usize,
+    dithering: Dithering,
+    invert: bool,
+    /// The grey of each pixel of the frame being converted, reused between frames.
+    levels: Vec<f32>,
+    /// The rows of the last frame converted.
+    rows: Vec<String>,
+}
+
+impl VideoStream {
+    /// Creates a new `VideoStream` drawing frames `width` by `height` pixels, two pixels across
+    /// and four down to a character.
+    ///
+    /// Pixels are set wherever the frame is lighter than middle grey, unless the stream is
+    /// dithered.
+    pub fn new(width: usize, height: usize) -> VideoStream {
+        VideoStream {
+            width,
+            height,
+            dithering: Dithering::None,
+            invert: false,
+            levels: vec![],
+            rows: vec![],
+        }
+    }
+
+    /// Sets how shades of grey are turned into set and unset pixels, and returns the stream for
+    /// use again.
+    pub fn dithering(mut self, dithering: Dithering) -> VideoStream {
+        self.dithering = dithering;
+        self
+    }
+
+    /// Sets whether dark pixels are set rather than light ones, for terminals with dark text on
+    /// a light background, and returns the stream for use again.
+    pub fn invert(mut self, invert: bool) -> VideoStream {
+        self.invert = invert;
+        self
+    }
+
+    /// Changes the size frames are drawn at, such as when the terminal is resized.
+    ///
+    /// The next frame is then redrawn in full.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.rows.clear();
+    }
+
+    /// Converts a frame of `width` by `height` pixels, given as red, green and blue bytes a row at
+    /// a time from the top left, and returns the escape codes that redraw the rows that changed
+    /// since the last frame, with the frame’s top left corner at the terminal’s.
+    ///
+    /// The frame is stretched to the size of the stream, each pixel taking the colour of the
+    /// frame’s pixel under its centre. A frame with fewer bytes than its size needs is skipped.
+    pub fn push_frame(&mut self, rgb: &[u8], width: usize, height: usize) -> String {
+        if width == 0 || height == 0 || rgb.len() < width * height * 3 {
+            return String::new();
+        }
+        self.sample(rgb, width, height);
+        let (columns, rows) = (self.width.div_ceil(2), self.height.div_ceil(4));
+        let mut masks = vec![0u8; columns * rows];
+        let mut set = |x: usize, y: usize| masks[y / 4 * columns + x / 2] |= braille::dot(x, y);
+        match self.dithering {
+            Dithering::None => {
+                for (i, &level) in self.levels.iter().enumerate() {
+                    if level > 0.5 {
+                        set(i % self.width, i / self.width);
+                    }
+                }
+            },
+            Dithering::Ordered => {
+                for (i, &level) in self.levels.iter().enumerate() {
+                    let (x, y) = (i % self.width, i / self.width);
+                    if dither::is_set(x, y, level as f64) {
+                        set(x, y);
+                    }
+                }
+            },
+            Dithering::ErrorDiffusion => {
+                let w = self.width;
+                for i in 0..self.levels.len() {
+                    let (x, y) = (i % w, i / w);
+                    let level = self.levels[i];
+                    let on = level > 0.5;
+                    if on {
+                        set(x, y);
+                    }
+                    let error = level - if on { 1.0 } else { 0.0 };
+                    let mut spread = |dx: isize, dy: usize, weight: f32| {
+                        let nx = x as isize + dx;
+                        if nx >= 0 && (nx as usize) < w && y + dy < self.height {
+                            self.levels[(y + dy) * w + nx as usize] += error * weight;
+                        }
+                    };
+                    spread(1, 0, 7.0 / 16.0);
+                    spread(-1, 1, 3.0 / 16.0);
+                    spread(0, 1, 5.0 / 16.0);
+                    spread(1, 1, 1.0 / 16.0);
+                }
+            },
+        }
+
+        let mut output = String::new();
+        let new_rows = (0..rows).map(|row| {
+            masks[row * columns..(row + 1) * columns].iter().map(|&mask| {
+                if mask == 0 { ' ' } else { char::from_u32(0x2800 + mask as u32).unwrap() }
+            }).collect::<String>()
+        }).collect::<Vec<_>>();
+        for (i, row) in new_rows.iter().enumerate() {
+            if self.rows.get(i) != Some(row) {
+                output.push_str(&format!("\x1b[{};1H{}\x1b[K", i + 1, row));
+            }
+        }
+        if new_rows.len() < self.rows.len() {
+            output.push_str(&format!("\x1b[{};1H\x1b[J", new_rows.len() + 1));
+        }
+        self.rows = new_rows;
+        output
+    }
+
+    /// Returns the last frame converted, drawn to a `String` just as `Canvas::frame` draws.
+    pub fn frame(&self) -> String {
+        self.rows.join("\n")
+    }
+
+    /// Returns the last frame converted drawn onto a new `Canvas` the size of the stream.
+    pub fn canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        for (row, line) in self.rows.iter().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                if c != ' ' {
+                    canvas.set_cell(column, row, (c as u32 - 0x2800) as u8);
+                }
+            }
+        }
+        canvas
+    }
+
+    /// Fills `levels` with the grey, from 0 to 1, of each pixel of the stream, where 1 is a pixel
+    /// to set.
+    fn sample(&mut self, rgb: &[u8], width: usize, height: usize) {
+        self.levels.clear();
+        for y in 0..self.height {
+            let sy = (y * 2 + 1) * height / (self.height * 2);
+            for x in 0..self.width {
+                let sx = (x * 2 + 1) * width / (self.width * 2);
+                let i = (sy * width + sx) * 3;
+                let (r, g, b) = (rgb[i] as u32, rgb[i + 1] as u32, rgb[i + 2] as u32);
+                let level = (299 * r + 587 * g + 114 * b) as f32 / 255_000.0;
+                self.levels.push(if self.invert { 1.0 - level } else { level });
+            }
+        }
+    }
+}
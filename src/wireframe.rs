@@ -0,0 +1,717 @@
+//! 3D wireframe rendering
+//!
+//! This module provides a small perspective camera and a `Mesh` type that can be projected onto a
+//! `braille::Canvas` as a wireframe, as well as a renderer for point clouds.
+//!
+//! # Example
+//!
+//! ```
+//! use drawille::braille::Canvas;
+//! use drawille::wireframe::{Camera, Mesh};
+//!
+//! let mut canvas = Canvas::new(80, 40);
+//! let mut camera = Camera::new(80.0, 40.0);
+//! camera.yaw = 0.6;
+//! camera.pitch = 0.4;
+//! Mesh::cube(1.0).draw(&mut canvas, &camera);
+//! println!("{}", canvas.frame());
+//! ```
+
+use std::cmp;
+use std::collections::HashSet;
+use std::error::Error;
+use std::f64;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use braille::Canvas;
+use dither;
+
+/// A point or direction in 3D space.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    /// Creates a new `Vec3` from its components.
+    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    /// Returns the dot product of two vectors.
+    pub fn dot(self, other: Vec3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product of two vectors.
+    pub fn cross(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.y * other.z - self.z * other.y,
+                  self.z * other.x - self.x * other.z,
+                  self.x * other.y - self.y * other.x)
+    }
+
+    /// Returns the length of the vector.
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scale: f64) -> Vec3 {
+        Vec3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+/// A perspective camera orbiting a target point.
+///
+/// The camera looks at `target` from `distance` units away, rotated by `yaw` about the vertical
+/// (y) axis and then by `pitch` about the horizontal (x) axis, both in radians. Projected points
+/// are centred in a viewport of `width` by `height` pixels, with `scale` pixels per unit at a
+/// depth of one unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Camera {
+    pub target: Vec3,
+    pub distance: f64,
+    pub yaw: f64,
+    pub pitch: f64,
+    pub scale: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Camera {
+    /// Creates a new `Camera` for a viewport of the given size in pixels.
+    ///
+    /// The camera looks at the origin from five units away, scaled so that a unit cube at the
+    /// origin comfortably fits the viewport.
+    pub fn new(width: f64, height: f64) -> Camera {
+        Camera {
+            target: Vec3::default(),
+            distance: 5.0,
+            yaw: 0.0,
+            pitch: 0.0,
+            scale: width.min(height) * 2.0,
+            width,
+            height,
+        }
+    }
+
+    /// Transforms a point from world space into camera space, where the camera is at the origin,
+    /// x points right, y points up and z is the depth into the screen.
+    pub fn to_view(&self, p: Vec3) -> Vec3 {
+        let p = p - self.target;
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (x, z) = (p.x * cos_yaw - p.z * sin_yaw, p.x * sin_yaw + p.z * cos_yaw);
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let (y, z) = (p.y * cos_pitch - z * sin_pitch, p.y * sin_pitch + z * cos_pitch);
+        Vec3::new(x, y, self.distance - z)
+    }
+
+    /// Projects a point onto the viewport, returning its pixel coordinates and its depth.
+    ///
+    /// Returns `None` for points at or behind the camera.
+    pub fn project(&self, p: Vec3) -> Option<(f64, f64, f64)> {
+        let v = self.to_view(p);
+        if v.z <= NEAR_PLANE {
+            return None;
+        }
+        let (x, y) = self.screen_position(v);
+        Some((x, y, v.z))
+    }
+
+    /// Draws the line between two points in world space onto the `Canvas`.
+    ///
+    /// Parts of the line outside the viewport or behind the camera aren’t drawn.
+    pub fn draw_line(&self, canvas: &mut Canvas, a: Vec3, b: Vec3) {
+        if let Some((a, b)) = self.screen_segment(a, b) {
+            canvas.line(a.0.round() as usize, a.1.round() as usize,
+                        b.0.round() as usize, b.1.round() as usize);
+        }
+    }
+
+    fn screen_position(&self, v: Vec3) -> (f64, f64) {
+        (self.width / 2.0 + self.scale * v.x / v.z, self.height / 2.0 - self.scale * v.y / v.z)
+    }
+
+    /// Projects the line between two points in world space, clipped to the viewport, returning the
+    /// pixel coordinates and inverse depth of each end.
+    fn screen_segment(&self, a: Vec3, b: Vec3) -> Option<(ScreenPoint, ScreenPoint)> {
+        let (mut a, mut b) = (self.to_view(a), self.to_view(b));
+        if a.z <= NEAR_PLANE && b.z <= NEAR_PLANE {
+            return None;
+        }
+        // Cut the line at the near plane so that it doesn't wrap around behind the camera.
+        if a.z <= NEAR_PLANE {
+            a = a + (b - a) * ((NEAR_PLANE - a.z) / (b.z - a.z));
+        } else if b.z <= NEAR_PLANE {
+            b = b + (a - b) * ((NEAR_PLANE - b.z) / (a.z - b.z));
+        }
+        let (sa, sb) = (self.screen_position(a), self.screen_position(b));
+        let (t0, t1) = clip_to_viewport(self.width, self.height, sa, sb)?;
+        // Inverse depth varies linearly across the screen, unlike depth itself.
+        let at = |t: f64| (sa.0 + t * (sb.0 - sa.0),
+                           sa.1 + t * (sb.1 - sa.1),
+                           1.0 / a.z + t * (1.0 / b.z - 1.0 / a.z));
+        Some((at(t0), at(t1)))
+    }
+}
+
+const NEAR_PLANE: f64 = 1e-3;
+
+/// A position on the screen in pixels, along with its inverse depth.
+type ScreenPoint = (f64, f64, f64);
+
+/// Clips the segment from `a` to `b` to the rectangle from the origin to `(width - 1, height -
+/// 1)` using the Liang–Barsky algorithm, returning the range of the segment that remains as
+/// fractions of its length.
+fn clip_to_viewport(width: f64, height: f64, a: (f64, f64), b: (f64, f64)) -> Option<(f64, f64)> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (max_x, max_y) = (width - 1.0, height - 1.0);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    for &(p, q) in [(-dx, a.0), (dx, max_x - a.0), (-dy, a.1), (dy, max_y - a.1)].iter() {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    Some((t0, t1))
+}
+
+/// A polygon mesh made of vertices, faces and stand-alone line segments.
+///
+/// Faces and lines refer to vertices by their index in `vertices`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Mesh {
+    pub vertices: Vec<Vec3>,
+    pub faces: Vec<Vec<usize>>,
+    pub lines: Vec<(usize, usize)>,
+}
+
+impl Mesh {
+    /// Creates a new, empty `Mesh`.
+    pub fn new() -> Mesh {
+        Default::default()
+    }
+
+    /// Creates an axis-aligned cube centred on the origin with sides of length `size`.
+    ///
+    /// Faces are wound anticlockwise when seen from outside the cube.
+    pub fn cube(size: f64) -> Mesh {
+        let h = size / 2.0;
+        let vertices = (0..8).map(|i| {
+            Vec3::new(if i & 1 == 0 { -h } else { h },
+                      if i & 2 == 0 { -h } else { h },
+                      if i & 4 == 0 { -h } else { h })
+        }).collect();
+        Mesh {
+            vertices,
+            faces: vec![vec![0, 2, 3, 1], vec![4, 5, 7, 6], vec![0, 1, 5, 4],
+                        vec![2, 6, 7, 3], vec![0, 4, 6, 2], vec![1, 3, 7, 5]],
+            lines: vec![],
+        }
+    }
+
+    /// Creates a grid mesh of the surface `y = f(x, z)`, sampled at `steps` intervals along each
+    /// of the given ranges of `x` and `z`.
+    ///
+    /// Grid squares with a corner where `f` isn’t finite are left out, so gaps in the function
+    /// show up as holes in the mesh.
+    pub fn surface<F>(f: F, x_range: (f64, f64), z_range: (f64, f64), steps: usize) -> Mesh
+        where F: Fn(f64, f64) -> f64
+    {
+        let steps = cmp::max(steps, 1);
+        let mut mesh = Mesh::new();
+        for j in 0..steps + 1 {
+            let z = z_range.0 + (z_range.1 - z_range.0) * j as f64 / steps as f64;
+            for i in 0..steps + 1 {
+                let x = x_range.0 + (x_range.1 - x_range.0) * i as f64 / steps as f64;
+                mesh.vertices.push(Vec3::new(x, f(x, z), z));
+            }
+        }
+        let is_finite = |v: &Vec3| v.y.is_finite();
+        for j in 0..steps {
+            for i in 0..steps {
+                let corner = j * (steps + 1) + i;
+                let face = vec![corner, corner + steps + 1, corner + steps + 2, corner + 1];
+                if face.iter().all(|&v| is_finite(&mesh.vertices[v])) {
+                    mesh.faces.push(face);
+                }
+            }
+        }
+        mesh
+    }
+
+    /// Returns every distinct edge of the mesh, from both its faces and its lines.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut seen = HashSet::new();
+        let mut edges = vec![];
+        let face_edges = self.faces.iter().flat_map(|face| {
+            (0..face.len()).map(move |i| (face[i], face[(i + 1) % face.len()]))
+        });
+        for (a, b) in face_edges.chain(self.lines.iter().cloned()) {
+            if a != b && seen.insert((a.min(b), a.max(b))) {
+                edges.push((a, b));
+            }
+        }
+        edges
+    }
+
+    /// Draws the mesh as a wireframe onto the `Canvas`, as seen through the `Camera`.
+    pub fn draw(&self, canvas: &mut Canvas, camera: &Camera) {
+        for (a, b) in self.edges() {
+            if let (Some(&a), Some(&b)) = (self.vertices.get(a), self.vertices.get(b)) {
+                camera.draw_line(canvas, a, b);
+            }
+        }
+    }
+
+    /// Draws the mesh onto the `Canvas` as seen through the `Camera`, hiding edges according to
+    /// `options`.
+    ///
+    /// Backface culling assumes that faces are wound anticlockwise when seen from outside the
+    /// mesh, as with `Mesh::cube`.
+    pub fn draw_with(&self, canvas: &mut Canvas, camera: &Camera, options: DrawOptions) {
+        if !options.backface_culling && !options.hidden_lines {
+            return self.draw(canvas, camera);
+        }
+
+        let front_faces = self.faces.iter().filter(|face| {
+            !options.backface_culling || self.is_front_facing(face, camera)
+        }).collect::<Vec<_>>();
+        let mut edges = HashSet::new();
+        for face in front_faces.iter() {
+            for i in 0..face.len() {
+                let (a, b) = (face[i], face[(i + 1) % face.len()]);
+                edges.insert((a.min(b), a.max(b)));
+            }
+        }
+        for &(a, b) in self.lines.iter() {
+            edges.insert((a.min(b), a.max(b)));
+        }
+
+        let depth = if options.hidden_lines {
+            Some(DepthBuffer::from_faces(self, &front_faces, camera))
+        } else {
+            None
+        };
+        for (a, b) in edges {
+            let (a, b) = match (self.vertices.get(a), self.vertices.get(b)) {
+                (Some(&a), Some(&b)) => (a, b),
+                _ => continue,
+            };
+            let depth = match depth {
+                Some(ref depth) => depth,
+                None => {
+                    camera.draw_line(canvas, a, b);
+                    continue;
+                },
+            };
+            let (a, b) = match camera.screen_segment(a, b) {
+                Some(segment) => segment,
+                None => continue,
+            };
+            let steps = (b.0 - a.0).abs().max((b.1 - a.1).abs()).ceil().max(1.0) as usize;
+            for i in 0..steps + 1 {
+                let t = i as f64 / steps as f64;
+                let (x, y) = ((a.0 + t * (b.0 - a.0)).round(), (a.1 + t * (b.1 - a.1)).round());
+                if depth.is_visible(x as usize, y as usize, a.2 + t * (b.2 - a.2)) {
+                    canvas.set(x as usize, y as usize);
+                }
+            }
+        }
+    }
+
+    fn is_front_facing(&self, face: &[usize], camera: &Camera) -> bool {
+        let points = face.iter().filter_map(|&i| self.vertices.get(i))
+                                .filter_map(|&v| camera.project(v))
+                                .collect::<Vec<_>>();
+        if points.len() < face.len() || points.len() < 3 {
+            // Faces that cross behind the camera can’t sensibly be tested, so keep them.
+            return true;
+        }
+        // Screen y points down, so an anticlockwise face has a negative signed area on screen.
+        let area: f64 = (0..points.len()).map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a.0 * b.1 - b.0 * a.1
+        }).sum();
+        area < 0.0
+    }
+
+    /// Parses a mesh from the text of a Wavefront OBJ file.
+    ///
+    /// Only vertex positions (`v`), faces (`f`) and polylines (`l`) are read; everything else,
+    /// such as normals, texture coordinates and materials, is ignored. Vertices are numbered from
+    /// 1, or counted back from the last one read with negative numbers.
+    ///
+    /// ```
+    /// use drawille::wireframe::{Mesh, MeshError};
+    ///
+    /// let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1/1/1 2//1 -1\nl 1 2 3\n";
+    /// let mesh = Mesh::from_obj(obj).unwrap();
+    /// assert_eq!(mesh.vertices.len(), 3);
+    /// assert_eq!(mesh.faces, vec![vec![0, 1, 2]]);
+    /// assert_eq!(mesh.lines, vec![(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(Mesh::from_obj("v 0 0 0\nf 1 1 2"), Err(MeshError::InvalidIndex(2)));
+    /// assert_eq!(Mesh::from_obj("v 0 0 0\nf 1 1 -2"), Err(MeshError::InvalidIndex(2)));
+    /// assert_eq!(Mesh::from_obj("v 0 0 0\nl 1 x"), Err(MeshError::Parse(2)));
+    /// assert_eq!(Mesh::from_obj("v 0 0"), Err(MeshError::Parse(1)));
+    /// ```
+    #[cfg(feature = "obj")]
+    pub fn from_obj(source: &str) -> Result<Mesh, MeshError> {
+        let mut mesh = Mesh::new();
+        for (i, line) in source.lines().enumerate() {
+            let line_no = i + 1;
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("v") => {
+                    let mut coord = || -> Result<f64, MeshError> {
+                        words.next().and_then(|w| w.parse().ok())
+                                    .ok_or(MeshError::Parse(line_no))
+                    };
+                    let (x, y, z) = (coord()?, coord()?, coord()?);
+                    mesh.vertices.push(Vec3::new(x, y, z));
+                },
+                Some(kind @ "f") | Some(kind @ "l") => {
+                    let count = mesh.vertices.len();
+                    let indices = words.map(|w| {
+                        // Faces may be given as `v/vt/vn`; only the vertex index matters here.
+                        let index = w.split('/').next().unwrap_or("");
+                        match index.parse::<isize>() {
+                            Ok(n) if n > 0 && n as usize <= count => Ok(n as usize - 1),
+                            Ok(n) if n < 0 && (-n) as usize <= count => Ok(count - (-n) as usize),
+                            Ok(_) => Err(MeshError::InvalidIndex(line_no)),
+                            Err(_) => Err(MeshError::Parse(line_no)),
+                        }
+                    }).collect::<Result<Vec<usize>, MeshError>>()?;
+                    if kind == "f" {
+                        if indices.len() < 3 {
+                            return Err(MeshError::Parse(line_no));
+                        }
+                        mesh.faces.push(indices);
+                    } else {
+                        mesh.lines.extend(indices.windows(2).map(|w| (w[0], w[1])));
+                    }
+                },
+                _ => {},
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Parses a mesh from the contents of an STL file, in either its ASCII or binary form.
+    ///
+    /// The triangles’ vertices are merged wherever they are exactly equal, and normals are
+    /// ignored. Data is read as binary if its length matches the triangle count in its header,
+    /// and otherwise as ASCII, which starts with `solid`.
+    ///
+    /// ```
+    /// use drawille::wireframe::{Mesh, MeshError};
+    ///
+    /// let ascii = "solid t\nfacet normal 0 0 1\nouter loop\n\
+    ///              vertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid t\n";
+    /// let mesh = Mesh::from_stl(ascii.as_bytes()).unwrap();
+    /// assert_eq!((mesh.vertices.len(), mesh.faces.len()), (3, 1));
+    /// let broken = ascii.replace("vertex 0 1 0\n", "");
+    /// assert_eq!(Mesh::from_stl(broken.as_bytes()), Err(MeshError::Parse(7)));
+    ///
+    /// // Two triangles sharing an edge, each a normal, three corners and an attribute count.
+    /// let mut binary = vec![0; 80];
+    /// binary.extend_from_slice(&2u32.to_le_bytes());
+    /// for corners in [[[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+    ///                 [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]].iter() {
+    ///     binary.extend_from_slice(&[0; 12]);
+    ///     for value in corners.iter().flat_map(|c| c.iter()) {
+    ///         binary.extend_from_slice(&value.to_le_bytes());
+    ///     }
+    ///     binary.extend_from_slice(&[0; 2]);
+    /// }
+    /// let mesh = Mesh::from_stl(&binary).unwrap();
+    /// assert_eq!(mesh.vertices.len(), 4);
+    /// assert_eq!(mesh.faces, vec![vec![0, 1, 2], vec![1, 3, 2]]);
+    /// assert_eq!(Mesh::from_stl(&binary[..binary.len() - 10]), Err(MeshError::Parse(0)));
+    /// ```
+    #[cfg(feature = "stl")]
+    pub fn from_stl(data: &[u8]) -> Result<Mesh, MeshError> {
+        use std::collections::HashMap;
+        use std::str;
+
+        let mut triangles = vec![];
+        let is_binary = data.len() >= 84 && {
+            let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as usize;
+            data.len() == 84 + count * 50
+        };
+        if is_binary {
+            let float = |at: usize| {
+                f32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+            };
+            for triangle in 0..(data.len() - 84) / 50 {
+                // Skip the normal at the start of each triangle.
+                let start = 84 + triangle * 50 + 12;
+                let mut corners = [[0.0f32; 3]; 3];
+                for (corner, c) in corners.iter_mut().enumerate() {
+                    for (axis, value) in c.iter_mut().enumerate() {
+                        *value = float(start + corner * 12 + axis * 4);
+                    }
+                }
+                triangles.push(corners);
+            }
+        } else {
+            let source = str::from_utf8(data).map_err(|_| MeshError::Parse(0))?;
+            if !source.trim_start().starts_with("solid") {
+                return Err(MeshError::Parse(0));
+            }
+            let mut corners = vec![];
+            for (i, line) in source.lines().enumerate() {
+                let mut words = line.split_whitespace();
+                match words.next() {
+                    Some("vertex") => {
+                        let mut corner = [0.0f32; 3];
+                        for value in corner.iter_mut() {
+                            *value = words.next().and_then(|w| w.parse().ok())
+                                                 .ok_or(MeshError::Parse(i + 1))?;
+                        }
+                        corners.push(corner);
+                    },
+                    Some("endfacet") => {
+                        if corners.len() != 3 {
+                            return Err(MeshError::Parse(i + 1));
+                        }
+                        triangles.push([corners[0], corners[1], corners[2]]);
+                        corners.clear();
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        let mut mesh = Mesh::new();
+        let mut indices = HashMap::new();
+        for triangle in triangles.iter() {
+            let face = triangle.iter().map(|c| {
+                let key = (c[0].to_bits(), c[1].to_bits(), c[2].to_bits());
+                let vertices = &mut mesh.vertices;
+                *indices.entry(key).or_insert_with(|| {
+                    vertices.push(Vec3::new(c[0] as f64, c[1] as f64, c[2] as f64));
+                    vertices.len() - 1
+                })
+            }).collect();
+            mesh.faces.push(face);
+        }
+        Ok(mesh)
+    }
+}
+
+/// Plots the surface `z = f(x, y)` over the given ranges onto the `Canvas` as a wireframe, as seen
+/// through the `Camera`.
+///
+/// The surface is scaled to fit in a cube two units wide centred on the origin, with `x` and `y`
+/// across the horizontal plane and `z` pointing up, so the default `Camera` shows all of it.
+///
+/// ```
+/// use drawille::braille::Canvas;
+/// use drawille::wireframe::{self, Camera};
+///
+/// let mut canvas = Canvas::new(80, 60);
+/// let mut camera = Camera::new(80.0, 60.0);
+/// camera.yaw = 0.5;
+/// camera.pitch = 0.5;
+/// wireframe::plot_surface(&mut canvas, |x, y| (x * x + y * y).sqrt().sin(),
+///                         (-6.0, 6.0), (-6.0, 6.0), &camera);
+/// ```
+pub fn plot_surface<F>(canvas: &mut Canvas, f: F, x_range: (f64, f64), y_range: (f64, f64),
+                       camera: &Camera)
+    where F: Fn(f64, f64) -> f64
+{
+    let mut mesh = Mesh::surface(f, x_range, y_range, SURFACE_STEPS);
+    let (low, high) = mesh.vertices.iter().filter(|v| v.y.is_finite())
+                                   .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), v| {
+        (low.min(v.y), high.max(v.y))
+    });
+    let fit = |n: f64, (start, end): (f64, f64)| if end == start {
+        0.0
+    } else {
+        (n - start) / (end - start) * 2.0 - 1.0
+    };
+    for v in mesh.vertices.iter_mut() {
+        *v = Vec3::new(fit(v.x, x_range), fit(v.y, (low, high)), -fit(v.z, y_range));
+    }
+    mesh.draw(canvas, camera);
+}
+
+/// The number of grid squares along each side of a surface drawn by `plot_surface`.
+const SURFACE_STEPS: usize = 16;
+
+/// How `draw_points` conveys the depth of each point.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DepthShading {
+    /// Every visible point is drawn the same way.
+    Flat,
+    /// Points further from the camera are drawn more sparsely, using an ordered dither so that
+    /// distant regions of a dense cloud look lighter.
+    Density,
+}
+
+/// Draws a cloud of points onto the `Canvas` as seen through the `Camera`, one pixel per point.
+///
+/// Points outside the viewport or behind the camera aren’t drawn.
+pub fn draw_points(canvas: &mut Canvas, camera: &Camera, points: &[Vec3], shading: DepthShading) {
+    let projected = points.iter().filter_map(|&p| camera.project(p)).filter(|&(x, y, _)| {
+        x >= -0.5 && y >= -0.5 && x < camera.width - 0.5 && y < camera.height - 0.5
+    }).collect::<Vec<_>>();
+    let (near, far) = projected.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(near, far), p| {
+        (near.min(p.2), far.max(p.2))
+    });
+
+    for &(x, y, depth) in projected.iter() {
+        let (x, y) = (x.round() as usize, y.round() as usize);
+        if shading == DepthShading::Density && far > near {
+            let nearness = (far - depth) / (far - near);
+            let density = MIN_DENSITY + (1.0 - MIN_DENSITY) * nearness;
+            if !dither::is_set(x, y, density) {
+                continue;
+            }
+        }
+        canvas.set(x, y);
+    }
+}
+
+/// The fraction of points still drawn at the far end of a cloud shaded by density.
+const MIN_DENSITY: f64 = 0.25;
+
+/// Options controlling which edges `Mesh::draw_with` hides.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct DrawOptions {
+    /// Skips edges that only belong to faces pointing away from the camera.
+    pub backface_culling: bool,
+    /// Hides parts of edges that are behind the mesh’s faces, using a depth buffer with one entry
+    /// per pixel.
+    pub hidden_lines: bool,
+}
+
+/// The inverse depth of the nearest face at each pixel of a camera’s viewport.
+struct DepthBuffer {
+    width: usize,
+    height: usize,
+    inverse_depths: Vec<f64>,
+}
+
+impl DepthBuffer {
+    fn from_faces(mesh: &Mesh, faces: &[&Vec<usize>], camera: &Camera) -> DepthBuffer {
+        let (width, height) = (camera.width.max(0.0).ceil() as usize,
+                               camera.height.max(0.0).ceil() as usize);
+        let mut buffer = DepthBuffer { width, height, inverse_depths: vec![0.0; width * height] };
+        for face in faces.iter() {
+            let points = face.iter().filter_map(|&i| mesh.vertices.get(i))
+                                    .filter_map(|&v| camera.project(v))
+                                    .map(|(x, y, z)| (x, y, 1.0 / z))
+                                    .collect::<Vec<_>>();
+            if points.len() < face.len() {
+                continue;
+            }
+            for i in 1..points.len().saturating_sub(1) {
+                buffer.fill_triangle(points[0], points[i], points[i + 1]);
+            }
+        }
+        buffer
+    }
+
+    fn fill_triangle(&mut self, a: ScreenPoint, b: ScreenPoint, c: ScreenPoint) {
+        let area = (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1);
+        if area == 0.0 || self.width == 0 || self.height == 0 {
+            return;
+        }
+        let clamp = |n: f64, max: usize| n.max(0.0).min(max as f64 - 1.0) as usize;
+        let (x0, x1) = (clamp(a.0.min(b.0).min(c.0).floor(), self.width),
+                        clamp(a.0.max(b.0).max(c.0).ceil(), self.width));
+        let (y0, y1) = (clamp(a.1.min(b.1).min(c.1).floor(), self.height),
+                        clamp(a.1.max(b.1).max(c.1).ceil(), self.height));
+        for y in y0..y1 + 1 {
+            for x in x0..x1 + 1 {
+                let (px, py) = (x as f64, y as f64);
+                let wa = ((b.0 - px) * (c.1 - py) - (c.0 - px) * (b.1 - py)) / area;
+                let wb = ((c.0 - px) * (a.1 - py) - (a.0 - px) * (c.1 - py)) / area;
+                let wc = 1.0 - wa - wb;
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+                let inverse_depth = wa * a.2 + wb * b.2 + wc * c.2;
+                let entry = &mut self.inverse_depths[y * self.width + x];
+                if inverse_depth > *entry {
+                    *entry = inverse_depth;
+                }
+            }
+        }
+    }
+
+    fn is_visible(&self, x: usize, y: usize, inverse_depth: f64) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        // Edges lie on the faces that hide them and are rounded to whole pixels, so compare
+        // against the furthest face among the neighbouring pixels, with a little slack.
+        let mut furthest = self.inverse_depths[y * self.width + x];
+        for ny in y.saturating_sub(1)..cmp::min(y + 2, self.height) {
+            for nx in x.saturating_sub(1)..cmp::min(x + 2, self.width) {
+                furthest = furthest.min(self.inverse_depths[ny * self.width + nx]);
+            }
+        }
+        inverse_depth >= furthest * (1.0 - DEPTH_TOLERANCE)
+    }
+}
+
+const DEPTH_TOLERANCE: f64 = 0.01;
+
+/// An error encountered while loading a `Mesh`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MeshError {
+    /// The given line couldn’t be parsed (or, for line 0, the data was neither ASCII nor binary
+    /// STL).
+    Parse(usize),
+    /// The given line referred to a vertex that doesn’t exist.
+    InvalidIndex(usize),
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MeshError::Parse(0) => write!(f, "mesh data is neither valid text nor binary STL"),
+            MeshError::Parse(line) => write!(f, "malformed mesh data on line {}", line),
+            MeshError::InvalidIndex(line) => write!(f, "invalid vertex index on line {}", line),
+        }
+    }
+}
+
+impl Error for MeshError {}